@@ -93,5 +93,37 @@ fn act(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, load, deploy, start, act);
+fn script(c: &mut Criterion) {
+    c.bench_function("script", |b| {
+        let rt = Runtime::new().unwrap();
+
+        b.to_async(rt).iter_custom(|iters| async move {
+            let engine = Engine::new();
+
+            let (s, sig) = engine.signal(()).double();
+            let text = include_str!("./script.yml");
+            let workflow = Workflow::from_yml(text).unwrap();
+            engine.manager().deploy(&workflow).unwrap();
+
+            let count = Arc::new(Mutex::new(0));
+            let chan = engine.channel();
+            chan.on_complete(move |_| {
+                let mut count = count.lock().unwrap();
+                *count += 1;
+                if *count >= iters {
+                    s.close();
+                }
+            });
+
+            let start = Instant::now();
+            for _ in 0..iters {
+                let _ = engine.executor().start(&workflow.id, &Vars::new()).unwrap();
+            }
+            sig.recv().await;
+            start.elapsed()
+        })
+    });
+}
+
+criterion_group!(benches, load, deploy, start, act, script);
 criterion_main!(benches);
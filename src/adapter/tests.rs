@@ -1,6 +1,6 @@
 use crate::{
     store::{data, DbSet, Query, StoreAdapter, StoreKind},
-    Builder, Result,
+    Builder, Result, Workflow,
 };
 use std::sync::Arc;
 use tokio::sync::OnceCell;
@@ -24,6 +24,48 @@ async fn adapter_set_extern_store_test() {
     store.reset();
 }
 
+#[tokio::test]
+async fn adapter_optional_histories_test() {
+    let store = MinimalStore(TestStore::new());
+    assert!(store.proc_histories().find("p1").is_err());
+    assert!(store.task_histories().query(&Query::new()).is_err());
+
+    let engine = Builder::new().store(&store).build();
+    assert_eq!(engine.runtime().cache().store().kind(), StoreKind::Extern);
+}
+
+#[tokio::test]
+async fn adapter_optional_model_versions_test() {
+    let store = MinimalStore(TestStore::new());
+    assert!(store
+        .model_versions()
+        .query(&Query::new())
+        .unwrap()
+        .is_empty());
+
+    let engine = Builder::new().store(&store).build();
+    let workflow = Workflow::new().with_id("m1");
+    assert!(engine.manager().deploy(&workflow).is_ok());
+    assert!(engine.manager().model_versions("m1").unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn adapter_optional_triggers_test() {
+    let store = MinimalStore(TestStore::new());
+    assert!(store.triggers().query(&Query::new()).unwrap().is_empty());
+
+    let engine = Builder::new().store(&store).build();
+    let workflow = Workflow::new()
+        .with_id("m1")
+        .with_trigger(|t| t.with_id("t1").with_cron("0 9 * * *"));
+    assert!(engine.manager().deploy(&workflow).is_ok());
+    assert!(engine
+        .runtime()
+        .fire_triggers(i64::MAX, false)
+        .unwrap()
+        .is_empty());
+}
+
 #[derive(Debug, Clone)]
 pub struct TestStore {
     models: Collect<data::Model>,
@@ -31,6 +73,10 @@ pub struct TestStore {
     tasks: Collect<data::Task>,
     packages: Collect<data::Package>,
     messages: Collect<data::Message>,
+    proc_histories: Collect<data::Proc>,
+    task_histories: Collect<data::Task>,
+    model_versions: Collect<data::ModelVersion>,
+    triggers: Collect<data::Trigger>,
 }
 
 impl TestStore {
@@ -41,6 +87,10 @@ impl TestStore {
             tasks: Collect::new(),
             packages: Collect::new(),
             messages: Collect::new(),
+            proc_histories: Collect::new(),
+            task_histories: Collect::new(),
+            model_versions: Collect::new(),
+            triggers: Collect::new(),
         }
     }
 }
@@ -68,6 +118,22 @@ impl StoreAdapter for TestStore {
     fn messages(&self) -> Arc<dyn DbSet<Item = data::Message>> {
         Arc::new(self.messages.clone())
     }
+
+    fn proc_histories(&self) -> Arc<dyn DbSet<Item = data::Proc>> {
+        Arc::new(self.proc_histories.clone())
+    }
+
+    fn task_histories(&self) -> Arc<dyn DbSet<Item = data::Task>> {
+        Arc::new(self.task_histories.clone())
+    }
+
+    fn model_versions(&self) -> Arc<dyn DbSet<Item = data::ModelVersion>> {
+        Arc::new(self.model_versions.clone())
+    }
+
+    fn triggers(&self) -> Arc<dyn DbSet<Item = data::Trigger>> {
+        Arc::new(self.triggers.clone())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -111,3 +177,32 @@ where
         Ok(false)
     }
 }
+
+/// the store without the optional sets
+#[derive(Debug, Clone)]
+pub struct MinimalStore(TestStore);
+
+impl StoreAdapter for MinimalStore {
+    fn init(&self) {}
+    fn close(&self) {}
+
+    fn models(&self) -> Arc<dyn DbSet<Item = data::Model>> {
+        self.0.models()
+    }
+
+    fn procs(&self) -> Arc<dyn DbSet<Item = data::Proc>> {
+        self.0.procs()
+    }
+
+    fn tasks(&self) -> Arc<dyn DbSet<Item = data::Task>> {
+        self.0.tasks()
+    }
+
+    fn packages(&self) -> Arc<dyn DbSet<Item = data::Package>> {
+        self.0.packages()
+    }
+
+    fn messages(&self) -> Arc<dyn DbSet<Item = data::Message>> {
+        self.0.messages()
+    }
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::{Config, Engine, StoreAdapter};
+use crate::{Config, Engine, HistoryMode, Severity, StoreAdapter};
 
 pub struct Builder {
     config: Config,
@@ -54,6 +54,31 @@ impl Builder {
         self
     }
 
+    pub fn history_mode(mut self, mode: HistoryMode) -> Self {
+        self.config.history_mode = mode;
+        self
+    }
+
+    pub fn worker_count(mut self, count: usize) -> Self {
+        self.config.worker_count = count;
+        self
+    }
+
+    pub fn deploy_severity(mut self, severity: Severity) -> Self {
+        self.config.deploy_severity = Some(severity);
+        self
+    }
+
+    pub fn script_timeout(mut self, millis: i64) -> Self {
+        self.config.script_timeout = Some(millis);
+        self
+    }
+
+    pub fn script_memory(mut self, bytes: usize) -> Self {
+        self.config.script_memory = Some(bytes);
+        self
+    }
+
     pub fn store<STORE: StoreAdapter + Clone + 'static>(mut self, store: &STORE) -> Self {
         self.store = Some(Arc::new(store.clone()));
         self
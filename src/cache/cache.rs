@@ -49,11 +49,21 @@ impl Cache {
 
     pub fn init(&self, engine: &Engine) {
         debug!("cache::init");
-        #[cfg(feature = "store")]
+        #[cfg(any(feature = "store", feature = "sqlite"))]
         {
-            let config = engine.config();
-            *self.store.write().unwrap() =
-                Arc::new(Store::local(&config.data_dir, &config.db_name));
+            #[cfg(not(test))]
+            let (data_dir, db_name) = {
+                let config = engine.config();
+                (config.data_dir.clone(), config.db_name.clone())
+            };
+            #[cfg(test)]
+            let (data_dir, db_name) = crate::utils::test_store_path();
+
+            #[cfg(feature = "store")]
+            let store = Store::local(&data_dir, &db_name);
+            #[cfg(all(feature = "sqlite", not(feature = "store")))]
+            let store = Store::sqlite(&data_dir, &db_name);
+            *self.store.write().unwrap() = Arc::new(store);
         }
         if let Some(store) = engine.adapter().store() {
             *self.store.write().unwrap() = Arc::new(Store::create(store));
@@ -112,6 +122,31 @@ impl Cache {
         Ok(true)
     }
 
+    /// keep the completed proc in store and release it from cache
+    #[instrument]
+    pub fn keep(&self, proc: &Arc<Proc>) -> Result<bool> {
+        debug!("keep pid={}", proc.id());
+        let ret = self.store.read().unwrap().upsert_proc(proc);
+        // release the proc even if the store fails, or it would stay in cache forever
+        self.procs.remove(proc.id());
+        ret?;
+        Ok(true)
+    }
+
+    /// move the completed proc to the store histories and release it from cache
+    #[instrument]
+    pub fn archive(&self, proc: &Arc<Proc>) -> Result<bool> {
+        debug!("archive pid={}", proc.id());
+        let store = self.store.read().unwrap();
+        let ret = store
+            .upsert_proc(proc)
+            .and_then(|_| store.archive_proc(proc.id()));
+        // release the proc even if the store fails, or it would stay in cache forever
+        self.procs.remove(proc.id());
+        ret?;
+        Ok(true)
+    }
+
     #[instrument(skip(on_load))]
     pub fn restore<F: Fn(&Arc<Proc>)>(&self, rt: &Arc<Runtime>, on_load: F) -> Result<()> {
         debug!("restore");
@@ -1,7 +1,7 @@
 use crate::{
     data::{self, MessageStatus},
     sch::{self, Node, Runtime, StatementBatch, TaskLifeCycle, TaskState},
-    store::{Cond, Expr, Query, Store},
+    store::{Cond, DbSet, Expr, Query, Store},
     utils::{self, Id},
     ActError, Error, Message, Result, StoreAdapter, Workflow,
 };
@@ -41,7 +41,7 @@ impl Store {
                     proc.set_pure_err(&err)
                 }
 
-                self.load_tasks(&proc, rt)?;
+                self.load_tasks(&self.tasks(), &proc, rt)?;
                 ret.push(proc);
             }
         }
@@ -63,7 +63,7 @@ impl Store {
                 proc.set_pure_state(p.state.into());
                 proc.set_start_time(p.start_time);
                 proc.set_env_local(&env_local.into());
-                self.load_tasks(&proc, rt)?;
+                self.load_tasks(&self.tasks(), &proc, rt)?;
                 if let Some(err) = p.err {
                     let err: Error = serde_json::from_str(&err)
                         .map_err(|err| ActError::Store(err.to_string()))?;
@@ -75,6 +75,32 @@ impl Store {
         }
     }
 
+    pub fn load_history(&self, pid: &str, rt: &Arc<Runtime>) -> Result<Option<Arc<sch::Proc>>> {
+        debug!("load history pid={}", pid);
+        match self.proc_histories().find(pid) {
+            Ok(p) => {
+                let model = Workflow::from_json(&p.model)?;
+                let proc = sch::Proc::new_with_timestamp(pid, p.timestamp, rt);
+                let env_local: serde_json::Value = serde_json::from_str(&p.env_local)
+                    .map_err(|err| ActError::Store(err.to_string()))?;
+
+                proc.load(&model)?;
+                proc.set_pure_state(p.state.into());
+                proc.set_start_time(p.start_time);
+                proc.set_end_time(p.end_time);
+                proc.set_env_local(&env_local.into());
+                self.load_tasks(&self.task_histories(), &proc, rt)?;
+                if let Some(err) = p.err {
+                    let err: Error = serde_json::from_str(&err)
+                        .map_err(|err| ActError::Store(err.to_string()))?;
+                    proc.set_pure_err(&err)
+                }
+                Ok(Some(proc))
+            }
+            Err(_) => Ok(None),
+        }
+    }
+
     // pub fn save_proc(&self, proc: &Arc<Proc>) -> Result<()> {
     //     for task in &proc.tasks() {
     //         self.upsert_task(task)?;
@@ -95,6 +121,30 @@ impl Store {
         Ok(true)
     }
 
+    /// move the proc and its tasks to the history collections
+    pub fn archive_proc(&self, pid: &str) -> Result<bool> {
+        debug!("archive_proc pid={}", pid);
+        let proc = self.procs().find(pid)?;
+        let q = Query::new().push(Cond::and().push(Expr::eq("pid", pid.to_string())));
+        let tasks = self.tasks().query(&q)?;
+        for task in tasks {
+            let histories = self.task_histories();
+            match histories.exists(&task.id)? {
+                true => histories.update(&task)?,
+                false => histories.create(&task)?,
+            };
+            self.tasks().delete(&task.id)?;
+        }
+
+        let histories = self.proc_histories();
+        match histories.exists(&proc.id)? {
+            true => histories.update(&proc)?,
+            false => histories.create(&proc)?,
+        };
+        self.procs().delete(pid)?;
+        Ok(true)
+    }
+
     pub fn set_message(&self, id: &str, status: MessageStatus) -> Result<bool> {
         if let Ok(mut message) = self.messages().find(id) {
             message.status = status;
@@ -131,8 +181,11 @@ impl Store {
         Ok(true)
     }
 
+    /// re-send the messages which are not acked in `timeout_millis` before the tick time `now`
+    /// the re-sent messages are stamped with `now`, so they are checked again after the same timeout
     pub fn with_no_response_messages<F: Fn(&Message)>(
         &self,
+        now: i64,
         timeout_millis: u64,
         max_message_retry_times: i32,
         f: F,
@@ -140,15 +193,12 @@ impl Store {
         let q = Query::new().set_limit(300).push(
             Cond::and()
                 .push(Expr::eq("status", MessageStatus::Created))
-                .push(Expr::lt(
-                    "update_time",
-                    utils::time::time_millis() as u64 - timeout_millis,
-                )),
+                .push(Expr::le("update_time", now as u64 - timeout_millis)),
         );
         if let Ok(messages) = self.messages().query(&q) {
             for m in messages.iter() {
                 let mut message = m.clone();
-                message.update_time = utils::time::time_millis();
+                message.update_time = now;
                 if message.retry_times < max_message_retry_times {
                     message.retry_times += 1;
                     let _ = self.messages().update(&message);
@@ -220,11 +270,16 @@ impl Store {
         Ok(())
     }
 
-    fn load_tasks(&self, proc: &Arc<sch::Proc>, rt: &Arc<Runtime>) -> Result<()> {
+    fn load_tasks(
+        &self,
+        set: &Arc<dyn DbSet<Item = data::Task>>,
+        proc: &Arc<sch::Proc>,
+        rt: &Arc<Runtime>,
+    ) -> Result<()> {
         debug!("load_tasks pid={}", proc.id());
         let tree = &proc.tree();
         let query = Query::new().push(Cond::and().push(Expr::eq("pid", proc.id())));
-        let tasks = self.tasks().query(&query)?;
+        let tasks = set.query(&query)?;
         for t in tasks {
             let state: TaskState = t.state.into();
             let node = Node::from_str(&t.node_data, tree);
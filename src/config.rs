@@ -1,15 +1,49 @@
+use crate::Severity;
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub cache_cap: usize,
     pub log_dir: String,
     pub log_level: String,
     pub data_dir: String,
+
+    // the local store is opened in memory by the name `:memory:`
     pub db_name: String,
     pub tick_interval_secs: u64,
 
     // will delete message after the max retries
     // cancel the settings by setting to 0
     pub max_message_retry_times: i32,
+
+    // what to do with the proc and its tasks after the proc is completed
+    pub history_mode: HistoryMode,
+
+    // the count of the scheduler workers to run tasks concurrently
+    // the tasks in the same proc are always run in order by one worker
+    pub worker_count: usize,
+
+    // reject the model in deploy when the lint has a diagnostic at or above the severity
+    // no lint check when it is None
+    pub deploy_severity: Option<Severity>,
+
+    // the max millis to evaluate a script, no limit when it is None
+    pub script_timeout: Option<i64>,
+
+    // the max bytes a script can allocate, no limit when it is None
+    pub script_memory: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum HistoryMode {
+    /// delete the proc and tasks from store
+    #[default]
+    Delete,
+
+    /// keep the proc and tasks in the procs and tasks collections
+    Keep,
+
+    /// move the proc and tasks to the proc_histories and task_histories collections
+    Archive,
 }
 
 impl Default for Config {
@@ -24,6 +58,15 @@ impl Default for Config {
             // default to 15s
             tick_interval_secs: 15,
             max_message_retry_times: 20,
+            history_mode: HistoryMode::Delete,
+
+            // default to the available parallelism
+            worker_count: std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+            deploy_severity: None,
+            script_timeout: None,
+            script_memory: None,
         }
     }
 }
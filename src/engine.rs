@@ -129,6 +129,16 @@ impl Engine {
         self.runtime.is_running()
     }
 
+    /// the count of the scheduler workers
+    pub fn workers(&self) -> usize {
+        self.runtime.scher().workers()
+    }
+
+    /// the count of the tasks waiting in the scheduler queues
+    pub fn queue_depth(&self) -> usize {
+        self.runtime.scher().queue_depth()
+    }
+
     fn init(&self) {
         info!("init");
         plugin::init(self);
@@ -1,15 +1,19 @@
 mod moudle;
+mod pool;
 #[cfg(test)]
 mod tests;
 mod value;
 
-use crate::{ActError, Result, ShareLock, Vars};
+use crate::{
+    utils::consts::{ERR_SCRIPT_MEMORY, ERR_SCRIPT_TIMEOUT},
+    ActError, Limits, Result, ShareLock, Vars,
+};
 use core::fmt;
 use rquickjs::{Context as JsContext, Ctx as JsCtx, FromJs, Runtime as JsRuntime};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::sync::{Arc, RwLock};
 
-use self::value::ActValue;
+use self::{pool::JsPool, value::ActValue};
 
 /// ActModule to extend the js features
 ///
@@ -31,6 +35,8 @@ pub trait ActModule: Send + Sync {
 pub struct Enviroment {
     vars: ShareLock<Vars>,
     modules: ShareLock<Vec<Box<dyn ActModule>>>,
+    pool: JsPool,
+    limits: RwLock<Limits>,
 }
 
 impl fmt::Debug for Enviroment {
@@ -49,6 +55,8 @@ impl Enviroment {
         let mut env = Enviroment {
             modules: Arc::new(RwLock::new(Vec::new())),
             vars: Arc::new(RwLock::new(Vars::new())),
+            pool: JsPool::new(),
+            limits: RwLock::new(Limits::default()),
         };
         env.init();
         env
@@ -62,6 +70,14 @@ impl Enviroment {
     pub fn register_module<T: ActModule + Clone + 'static>(&self, module: &T) {
         let mut modules = self.modules.write().unwrap();
         modules.push(Box::new(module.clone()));
+
+        // the idle runtimes are initialized without the new module
+        self.pool.clear();
+    }
+
+    /// set the default limits for the scripts without their own limits
+    pub fn set_limits(&self, limits: Limits) {
+        *self.limits.write().unwrap() = limits;
     }
 
     pub fn get<T>(&self, name: &str) -> Option<T>
@@ -83,31 +99,107 @@ impl Enviroment {
         f(&mut vars);
     }
 
+    /// compile the script without running it to check the syntax
+    /// the error message contains the name and the source position
+    pub fn check(&self, name: &str, script: &str) -> Result<()> {
+        let runtime = JsRuntime::new().unwrap();
+        let ctx = JsContext::full(&runtime).unwrap();
+        ctx.with(|ctx| {
+            let result = pool::compile(&ctx, name, script);
+            if let Err(rquickjs::Error::Exception) = result {
+                let exception = rquickjs::Exception::from_js(&ctx, ctx.catch()).unwrap();
+                return Err(ActError::Script(format!(
+                    "{name}:{}:{} {}",
+                    exception.line().unwrap_or_default(),
+                    exception.column().unwrap_or_default(),
+                    exception.message().unwrap_or_default()
+                )));
+            }
+
+            result.map(|_| ()).map_err(ActError::from)
+        })
+    }
+
+    /// evaluate the script in a pooled runtime
+    /// the runtime is dropped instead of reusing when the script changes the globals,
+    /// so that the procs can not affect each other
     pub fn eval<T>(&self, expr: &str) -> Result<T>
     where
         T: DeserializeOwned,
     {
-        let runtime = JsRuntime::new().unwrap();
-        let ctx = JsContext::full(&runtime).unwrap();
-        ctx.with(|ctx| {
+        self.eval_with(expr, &Limits::default())
+    }
+
+    /// evaluate the script with the limits, the unset ones fall back to the default limits
+    /// the runtime exceeding the limits is not reused
+    pub fn eval_with<T>(&self, expr: &str, limits: &Limits) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let limits = limits.or(&self.limits.read().unwrap());
+        let mut entry = {
             let modules = self.modules.read().unwrap();
-            for m in modules.iter() {
-                m.init(&ctx)?;
-            }
+            self.pool.take(&modules)?
+        };
 
-            let result = ctx.eval::<ActValue, &str>(expr);
-            if let Err(rquickjs::Error::Exception) = result {
-                let exception = rquickjs::Exception::from_js(&ctx, ctx.catch()).unwrap();
-                eprintln!("error: {exception:?}");
-                return Err(ActError::Exception {
+        entry.limit(&limits);
+        let (result, is_clean) = entry.with(|ctx, entry| {
+            let result = entry.eval(ctx, expr);
+            let result = match result {
+                Err(rquickjs::Error::Exception) => {
+                    match rquickjs::Exception::from_js(ctx, ctx.catch()) {
+                        Ok(exception) => {
+                            eprintln!("error: {exception:?}");
+                            Err(ActError::Exception {
+                                ecode: "".to_string(),
+                                message: exception.message().unwrap_or_default(),
+                            })
+                        }
+                        // the out of memory error may be thrown without an exception object
+                        Err(_) => Err(ActError::Exception {
+                            ecode: "".to_string(),
+                            message: "out of memory".to_string(),
+                        }),
+                    }
+                }
+                Err(rquickjs::Error::Allocation) => Err(ActError::Exception {
                     ecode: "".to_string(),
-                    message: exception.message().unwrap_or_default(),
-                });
+                    message: "out of memory".to_string(),
+                }),
+                Err(err) => Err(ActError::from(err)),
+                Ok(value) => ActValue::from_js(ctx, value).map_err(ActError::from),
+            };
+            (result, entry.is_clean(ctx))
+        });
+        let interrupted = entry.unlimit();
+
+        let result = match result {
+            Err(_) if interrupted => Err(ActError::Limit {
+                ecode: ERR_SCRIPT_TIMEOUT.to_string(),
+                message: format!(
+                    "the script exceeds the time limit of {}",
+                    limits.timeout.as_ref().unwrap()
+                ),
+            }),
+            Err(ActError::Exception { message, .. })
+                if limits.memory.is_some() && message == "out of memory" =>
+            {
+                Err(ActError::Limit {
+                    ecode: ERR_SCRIPT_MEMORY.to_string(),
+                    message: format!(
+                        "the script exceeds the memory limit of {} bytes",
+                        limits.memory.unwrap()
+                    ),
+                })
             }
+            result => result,
+        };
+        if is_clean && !matches!(result, Err(ActError::Limit { .. })) {
+            self.pool.put(entry);
+        }
 
-            let value = result.map_err(ActError::from)?;
-            let ret = serde_json::from_value::<T>(value.into()).map_err(ActError::from)?;
-            Ok(ret)
-        })
+        let value = result?;
+        let ret = serde_json::from_value::<T>(value.into()).map_err(ActError::from)?;
+        Ok(ret)
     }
 }
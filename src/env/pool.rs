@@ -0,0 +1,265 @@
+use super::ActModule;
+use crate::{utils, Limits};
+use rquickjs::{
+    qjs, Context as JsContext, Ctx as JsCtx, Function, Persistent, Runtime as JsRuntime, Value,
+};
+use std::{
+    collections::HashMap,
+    ffi::CString,
+    sync::{
+        atomic::{AtomicBool, AtomicI64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// the max idle runtimes kept in the pool
+const MAX_POOL_SIZE: usize = 32;
+
+/// the max compiled scripts cached in one runtime
+const MAX_SCRIPT_CACHE: usize = 512;
+
+/// freeze the objects reachable from the globals after the modules are initialized,
+/// such as the prototypes and the hidden iterator and generator intrinsics,
+/// so a script can not pollute them for the next procs using the pooled runtime
+///
+/// the global object itself is kept writable, and it is checked by the `GLOBALS_GUARD`
+const FREEZE_INTRINSICS: &str = r#"
+(() => {
+    const roots = [
+        Object.getPrototypeOf(function* () {}),
+        Object.getPrototypeOf(async function () {}),
+        Object.getPrototypeOf(async function* () {}),
+        Object.getPrototypeOf([][Symbol.iterator]()),
+        Object.getPrototypeOf(new Map().entries()),
+        Object.getPrototypeOf(new Set().entries()),
+        Object.getPrototypeOf(""[Symbol.iterator]()),
+        Object.getPrototypeOf("".matchAll(/./g)),
+        Object.getPrototypeOf(Int8Array),
+    ];
+    for (const name of Reflect.ownKeys(globalThis)) {
+        const desc = Object.getOwnPropertyDescriptor(globalThis, name);
+        roots.push(desc.value, desc.get, desc.set);
+    }
+    const visited = new Set([globalThis]);
+    while (roots.length > 0) {
+        const value = roots.pop();
+        if ((typeof value !== "object" && typeof value !== "function") || value === null || visited.has(value)) {
+            continue;
+        }
+        visited.add(value);
+        Object.freeze(value);
+        roots.push(Object.getPrototypeOf(value));
+        for (const key of Reflect.ownKeys(value)) {
+            const desc = Object.getOwnPropertyDescriptor(value, key);
+            roots.push(desc.value, desc.get, desc.set);
+        }
+    }
+})()
+"#;
+
+/// snapshot the globals after the modules are initialized
+/// the returned function checks whether a script has changed the globals
+const GLOBALS_GUARD: &str = r#"
+(() => {
+    const names = Object.getOwnPropertyNames(globalThis);
+    const values = names.map((name) => globalThis[name]);
+    return () => {
+        const current = Object.getOwnPropertyNames(globalThis);
+        return current.length === names.length
+            && names.every((name, index) => Object.is(globalThis[name], values[index]));
+    };
+})()
+"#;
+
+/// The js runtime with the registered modules initialized
+pub struct JsEntry {
+    // drop the persistent values before the context and runtime
+    cache: JsCache,
+    context: JsContext,
+    runtime: JsRuntime,
+    deadline: Arc<Deadline>,
+}
+
+/// The deadline checked by the runtime interrupt handler
+#[derive(Default)]
+struct Deadline {
+    /// the time in millis to interrupt the script, `0` for no deadline
+    time: AtomicI64,
+    interrupted: AtomicBool,
+}
+
+/// The compiled scripts cached by the source text and the globals guard
+///
+/// the runtime is only returned to the pool when the globals are not changed by the scripts
+pub struct JsCache {
+    scripts: HashMap<String, Persistent<Value<'static>>>,
+    guard: Persistent<Function<'static>>,
+}
+
+/// The pool of the pre-initialized js runtimes
+#[derive(Default)]
+pub struct JsPool {
+    entries: Mutex<Vec<JsEntry>>,
+}
+
+impl JsPool {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// take an idle runtime or create a new one with the modules
+    pub fn take(&self, modules: &[Box<dyn ActModule>]) -> crate::Result<JsEntry> {
+        if let Some(entry) = self.entries.lock().unwrap().pop() {
+            return Ok(entry);
+        }
+        JsEntry::new(modules)
+    }
+
+    pub fn put(&self, entry: JsEntry) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() < MAX_POOL_SIZE {
+            entries.push(entry);
+        }
+    }
+
+    /// remove the idle runtimes, such as the modules are changed
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+}
+
+impl JsEntry {
+    fn new(modules: &[Box<dyn ActModule>]) -> crate::Result<Self> {
+        let runtime = JsRuntime::new()?;
+        let context = JsContext::full(&runtime)?;
+        let guard = context.with(|ctx| {
+            for m in modules.iter() {
+                m.init(&ctx)?;
+            }
+            ctx.eval::<(), &str>(FREEZE_INTRINSICS)?;
+            let guard = ctx.eval::<Function, &str>(GLOBALS_GUARD)?;
+            Ok::<_, crate::ActError>(Persistent::save(&ctx, guard))
+        })?;
+
+        let deadline = Arc::new(Deadline::default());
+        let handler = deadline.clone();
+        runtime.set_interrupt_handler(Some(Box::new(move || {
+            let time = handler.time.load(Ordering::Relaxed);
+            if time > 0 && utils::time::time_millis() >= time {
+                handler.interrupted.store(true, Ordering::Relaxed);
+                return true;
+            }
+            false
+        })));
+
+        Ok(Self {
+            cache: JsCache {
+                scripts: HashMap::new(),
+                guard,
+            },
+            context,
+            runtime,
+            deadline,
+        })
+    }
+
+    /// apply the limits before evaluating a script
+    /// the memory limit is counted from the current usage of the runtime
+    pub fn limit(&self, limits: &Limits) {
+        let time = match &limits.timeout {
+            Some(timeout) => utils::time::time_millis() + timeout.as_millis(),
+            None => 0,
+        };
+        self.deadline.time.store(time, Ordering::Relaxed);
+        self.deadline.interrupted.store(false, Ordering::Relaxed);
+
+        let memory = match limits.memory {
+            Some(memory) => {
+                let usage = self.runtime.memory_usage().malloc_size.max(0) as usize;
+                usage.saturating_add(memory)
+            }
+            None => usize::MAX,
+        };
+        self.runtime.set_memory_limit(memory);
+    }
+
+    /// remove the limits and return whether the script was interrupted by the timeout
+    pub fn unlimit(&self) -> bool {
+        self.deadline.time.store(0, Ordering::Relaxed);
+        self.runtime.set_memory_limit(usize::MAX);
+        self.deadline.interrupted.swap(false, Ordering::Relaxed)
+    }
+
+    pub fn with<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&JsCtx<'_>, &mut JsCache) -> R,
+    {
+        let Self { cache, context, .. } = self;
+        context.with(|ctx| f(&ctx, cache))
+    }
+}
+
+impl JsCache {
+    /// run the script by the cached compiled code
+    /// the script is wrapped in a block to keep the `let` and `const` declarations in it
+    pub fn eval<'js>(&mut self, ctx: &JsCtx<'js>, script: &str) -> rquickjs::Result<Value<'js>> {
+        let code = match self.scripts.get(script) {
+            Some(code) => code.clone().restore(ctx)?,
+            None => {
+                let code = compile(ctx, "eval_script", &format!("{{\n{script}\n}}"))?;
+                if self.scripts.len() >= MAX_SCRIPT_CACHE {
+                    self.scripts.clear();
+                }
+                self.scripts
+                    .insert(script.to_string(), Persistent::save(ctx, code.clone()));
+                code
+            }
+        };
+
+        run(ctx, &code)
+    }
+
+    /// check if the globals are the same as the ones after initializing
+    pub fn is_clean(&self, ctx: &JsCtx<'_>) -> bool {
+        self.guard
+            .clone()
+            .restore(ctx)
+            .and_then(|guard| guard.call::<_, bool>(()))
+            .unwrap_or(false)
+    }
+}
+
+/// compile the script as a global script without running it
+pub fn compile<'js>(ctx: &JsCtx<'js>, name: &str, script: &str) -> rquickjs::Result<Value<'js>> {
+    let source = CString::new(script)?;
+    let name = CString::new(name)?;
+    unsafe {
+        let value = qjs::JS_Eval(
+            ctx.as_raw().as_ptr(),
+            source.as_ptr(),
+            script.len() as _,
+            name.as_ptr(),
+            (qjs::JS_EVAL_TYPE_GLOBAL | qjs::JS_EVAL_FLAG_COMPILE_ONLY) as _,
+        );
+        if qjs::JS_IsException(value) {
+            return Err(rquickjs::Error::Exception);
+        }
+        Ok(Value::from_raw(ctx.clone(), value))
+    }
+}
+
+fn run<'js>(ctx: &JsCtx<'js>, code: &Value<'js>) -> rquickjs::Result<Value<'js>> {
+    unsafe {
+        // the compiled code is consumed by JS_EvalFunction, so dup it to keep the cached one
+        let value = qjs::JS_EvalFunction(ctx.as_raw().as_ptr(), qjs::JS_DupValue(code.as_raw()));
+        if qjs::JS_IsException(value) {
+            return Err(rquickjs::Error::Exception);
+        }
+        Ok(Value::from_raw(ctx.clone(), value))
+    }
+}
@@ -1,5 +1,7 @@
 use crate::{
-    env::Enviroment, Act, ActError, Context, Engine, Event, Message, Signal, Vars, Workflow,
+    env::Enviroment,
+    utils::consts::{ERR_SCRIPT_MEMORY, ERR_SCRIPT_TIMEOUT},
+    Act, ActError, Context, Engine, Event, Limits, Message, Signal, Vars, Workflow,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -95,6 +97,118 @@ fn env_eval_object() {
     );
 }
 
+#[test]
+fn env_eval_reuse_runtime() {
+    let env = Enviroment::new();
+    let script = r#"
+    let v = 5;
+    v
+    "#;
+
+    // the let declarations are not kept in the pooled runtime
+    assert_eq!(env.eval::<i64>(script).unwrap(), 5);
+    assert_eq!(env.eval::<i64>(script).unwrap(), 5);
+    assert_eq!(env.pool.len(), 1);
+}
+
+#[test]
+fn env_eval_globals_isolation() {
+    let env = Enviroment::new();
+    assert_eq!(env.eval::<i64>("var a = 1; globalThis.b = 2;").unwrap(), 2);
+    env.eval::<()>("console = null;").unwrap();
+
+    // the runtime is dropped when the globals are changed
+    assert_eq!(env.pool.len(), 0);
+    assert_eq!(
+        env.eval::<String>("typeof a + typeof b").unwrap(),
+        "undefinedundefined"
+    );
+    assert!(env.eval::<()>(r#"console.log("ok")"#).is_ok());
+    assert_eq!(env.pool.len(), 1);
+}
+
+#[test]
+fn env_eval_intrinsics_isolation() {
+    let env = Enviroment::new();
+    env.eval::<i64>(
+        r#"
+        Object.prototype.x = 1;
+        Array.prototype.f = () => 1;
+        Math.k = 1;
+        act.k = 1;
+        "#,
+    )
+    .unwrap();
+    assert!(env
+        .eval::<()>(r#""use strict"; String.prototype.s = 1;"#)
+        .is_err());
+
+    // the intrinsics are frozen, so the pooled runtime is kept without the changes
+    assert_eq!(env.pool.len(), 1);
+    assert!(env
+        .eval::<bool>(
+            r#"({}).x === undefined && [].f === undefined && Math.k === undefined
+            && act.k === undefined && "".s === undefined"#
+        )
+        .unwrap());
+    assert_eq!(
+        env.eval::<Vec<String>>(r#"["a"].union(["b"])"#).unwrap(),
+        ["a", "b"]
+    );
+}
+
+#[test]
+fn env_eval_error_keep_runtime() {
+    let env = Enviroment::new();
+    assert!(env.eval::<()>(r#"throw new Error("err1")"#).is_err());
+    assert!(env.eval::<()>("let v = ;").is_err());
+    assert_eq!(env.eval::<i64>("1 + 1").unwrap(), 2);
+    assert_eq!(env.pool.len(), 1);
+}
+
+#[test]
+fn env_eval_timeout_limit() {
+    let env = Enviroment::new();
+    let limits = Limits::new().with_timeout("100ms");
+    let result = env.eval_with::<()>("while(true) {}", &limits);
+    assert!(matches!(result, Err(ActError::Limit { ecode, .. }) if ecode == ERR_SCRIPT_TIMEOUT));
+
+    // the interrupted runtime is not reused
+    assert_eq!(env.pool.len(), 0);
+    assert_eq!(env.eval::<i64>("1 + 1").unwrap(), 2);
+}
+
+#[test]
+fn env_eval_memory_limit() {
+    let env = Enviroment::new();
+    let limits = Limits::new().with_memory(1024 * 1024).with_timeout("5s");
+    let script = r#"
+    let items = [];
+    while(true) {
+        items.push(new Array(1000).fill("item"));
+    }
+    "#;
+    let result = env.eval_with::<()>(script, &limits);
+    assert!(matches!(result, Err(ActError::Limit { ecode, .. }) if ecode == ERR_SCRIPT_MEMORY));
+    assert_eq!(env.pool.len(), 0);
+    assert_eq!(env.eval::<i64>("1 + 1").unwrap(), 2);
+}
+
+#[test]
+fn env_eval_default_limits() {
+    let env = Enviroment::new();
+    env.set_limits(Limits::new().with_timeout("100ms"));
+    let result = env.eval::<()>("while(true) {}");
+    assert!(matches!(result, Err(ActError::Limit { ecode, .. }) if ecode == ERR_SCRIPT_TIMEOUT));
+
+    // the own limits go before the default ones
+    let result = env.eval_with::<i64>(
+        "let i = 0; while(i < 1000) { i++; } i",
+        &Limits::new().with_timeout("10s"),
+    );
+    assert_eq!(result.unwrap(), 1000);
+}
+
 #[tokio::test]
 async fn env_console_module() {
     let env = Enviroment::new();
@@ -529,3 +643,27 @@ async fn run_test<T: Clone + Send + 'static + Default>(
     context.eval::<()>(&script).unwrap();
     sig2.recv().await
 }
+
+#[test]
+fn env_check_ok() {
+    let env = Enviroment::new();
+    let result = env.check("step1.run", "let a = 5;\nact.back(\"step1\");");
+    assert!(result.is_ok());
+}
+
+#[test]
+fn env_check_syntax_error() {
+    let env = Enviroment::new();
+    let result = env.check("step1.run", "let a = 5;\nlet b = (a + ;");
+    assert!(result.is_err());
+}
+
+#[test]
+fn env_check_global_script() {
+    let env = Enviroment::new();
+    // the scripts run as global scripts instead of modules
+    assert!(env.check("step1.run", "with ({ a: 5 }) { a + 1 }").is_ok());
+    let result = env.check("step1.run", "import { a } from \"b\";");
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("step1.run:1:"));
+}
@@ -16,6 +16,10 @@ pub enum ActError {
     #[error("ecode: {ecode}, message: {message}")]
     Exception { ecode: String, message: String },
 
+    /// the script exceeds the time or memory limit
+    #[error("ecode: {ecode}, message: {message}")]
+    Limit { ecode: String, message: String },
+
     #[error("{0}")]
     Model(String),
 
@@ -73,6 +77,7 @@ impl Into<Error> for ActError {
     fn into(self) -> Error {
         match self {
             ActError::Exception { ecode, message } => Error { ecode, message },
+            ActError::Limit { ecode, message } => Error { ecode, message },
             err => Error {
                 ecode: "".to_string(),
                 message: err.to_string(),
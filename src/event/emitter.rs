@@ -1,14 +1,20 @@
 use crate::{
     event::Message,
     sch::{Proc, Runtime, Task},
-    utils, Event, Result, ShareLock,
+    ActError, Event, Result, ShareLock,
 };
 use std::{
     collections::HashMap,
     sync::{Arc, RwLock},
 };
-use tokio::runtime::Handle;
-use tracing::debug;
+use tokio::{
+    runtime::Handle,
+    sync::{
+        mpsc::{self, error::TrySendError},
+        watch,
+    },
+};
+use tracing::{debug, warn};
 
 use super::TaskExtra;
 macro_rules! dispatch_event {
@@ -40,6 +46,16 @@ pub type ProcHandle = Arc<dyn Fn(&Event<Arc<Proc>>) + Send + Sync>;
 pub type TaskHandle = Arc<dyn Fn(&Event<Arc<Task>, TaskExtra>) + Send + Sync>;
 pub type TickHandle = Arc<dyn Fn(&i64) + Send + Sync>;
 
+/// the max messages waiting in one proc watcher
+const WATCHER_CAPACITY: usize = 1024;
+
+/// watch the messages and the final result of one proc
+/// it is notified in the emitting order without the channel dispatching
+struct ProcWatcher {
+    messages: mpsc::Sender<Event<Message>>,
+    result: watch::Sender<Option<Event<Message>>>,
+}
+
 pub struct Emitter {
     starts: ShareLock<HashMap<String, ActWorkflowMessageHandle>>,
     completes: ShareLock<HashMap<String, ActWorkflowMessageHandle>>,
@@ -52,6 +68,12 @@ pub struct Emitter {
 
     ticks: ShareLock<Vec<TickHandle>>,
 
+    watchers: ShareLock<HashMap<String, ProcWatcher>>,
+
+    /// the message subscribers by subscription id with the channel key
+    /// they are notified in the emitting order without the channel dispatching
+    subscribers: ShareLock<HashMap<String, (String, ActWorkflowMessageHandle)>>,
+
     runtime: ShareLock<Option<Arc<Runtime>>>,
 }
 
@@ -71,6 +93,8 @@ impl Emitter {
             procs: Arc::new(RwLock::new(Vec::new())),
             tasks: Arc::new(RwLock::new(Vec::new())),
             ticks: Arc::new(RwLock::new(Vec::new())),
+            watchers: Arc::new(RwLock::new(HashMap::new())),
+            subscribers: Arc::new(RwLock::new(HashMap::new())),
 
             runtime: Arc::new(RwLock::new(None)),
         }
@@ -98,6 +122,23 @@ impl Emitter {
             .or_insert(f);
     }
 
+    /// subscribe the messages with the subscription id, which is removed with the channel key
+    pub fn subscribe(
+        &self,
+        key: &str,
+        id: &str,
+        f: impl Fn(&Event<Message>) + Send + Sync + 'static,
+    ) {
+        self.subscribers
+            .write()
+            .unwrap()
+            .insert(id.to_string(), (key.to_string(), Arc::new(f)));
+    }
+
+    pub fn unsubscribe(&self, id: &str) {
+        self.subscribers.write().unwrap().remove(id);
+    }
+
     pub fn on_start(&self, key: &str, f: impl Fn(&Event<Message>) + Send + Sync + 'static) {
         let f = Arc::new(f);
         self.starts
@@ -140,6 +181,32 @@ impl Emitter {
         self.ticks.write().unwrap().push(Arc::new(f));
     }
 
+    /// watch the proc messages and the final result by pid
+    /// the messages are dropped when the receiver is full, the result is always kept
+    /// one proc can only be watched by one handle, it returns error if the pid is already watched
+    pub fn watch_proc(
+        &self,
+        pid: &str,
+    ) -> Result<(
+        mpsc::Receiver<Event<Message>>,
+        watch::Receiver<Option<Event<Message>>>,
+    )> {
+        let mut watchers = self.watchers.write().unwrap();
+        if watchers.contains_key(pid) {
+            return Err(ActError::Action(format!(
+                "proc_id({pid}) is already watched by another handle"
+            )));
+        }
+        let (messages, rx) = mpsc::channel(WATCHER_CAPACITY);
+        let (result, result_rx) = watch::channel(None);
+        watchers.insert(pid.to_string(), ProcWatcher { messages, result });
+        Ok((rx, result_rx))
+    }
+
+    pub fn unwatch_proc(&self, pid: &str) {
+        self.watchers.write().unwrap().remove(pid);
+    }
+
     pub fn emit_proc_event(&self, proc: &Arc<Proc>) {
         debug!("emit_proc_event: {}", proc.id());
         let handlers = self.procs.read().unwrap();
@@ -189,23 +256,28 @@ impl Emitter {
     pub fn emit_complete_event(&self, state: &Message) {
         debug!("emit_complete_event: {:?}", state);
         let e = Event::new(&*self.runtime.read().unwrap(), state);
+        self.notify_watcher(&e, true);
         dispatch_key_event!(self, completes, &e);
     }
 
     pub fn emit_message(&self, msg: &Message) {
         debug!("emit_message: {:?}", msg);
         let e = Event::new(&*self.runtime.read().unwrap(), msg);
+        self.notify_watcher(&e, false);
+        for (_, handle) in self.subscribers.read().unwrap().values() {
+            (handle)(&e);
+        }
         dispatch_key_event!(self, messages, &e);
     }
 
     pub fn emit_error(&self, state: &Message) {
         debug!("emit_error: {:?}", state);
         let e = Event::new(&*self.runtime.read().unwrap(), state);
+        self.notify_watcher(&e, true);
         dispatch_key_event!(self, errors, &e);
     }
 
-    pub fn emit_tick(&self) {
-        let time_millis = utils::time::time_millis();
+    pub fn emit_tick(&self, time_millis: i64) {
         debug!("emit_tick {time_millis}");
         dispatch_event!(self, ticks, &time_millis);
     }
@@ -230,5 +302,32 @@ impl Emitter {
         if messages.contains_key(key) {
             messages.remove(key);
         }
+
+        self.subscribers
+            .write()
+            .unwrap()
+            .retain(|_, (chan, _)| chan != key);
+    }
+
+    fn notify_watcher(&self, e: &Event<Message>, is_end: bool) {
+        // the re-sent messages have been notified before
+        if e.retry_times > 0 {
+            return;
+        }
+
+        if is_end {
+            // remove the watcher to close the messages receiver
+            if let Some(watcher) = self.watchers.write().unwrap().remove(&e.pid) {
+                let _ = watcher.result.send(Some(e.clone()));
+            }
+            return;
+        }
+
+        if let Some(watcher) = self.watchers.read().unwrap().get(&e.pid) {
+            // the handle keeps the recent messages only if it is not read
+            if let Err(TrySendError::Full(e)) = watcher.messages.try_send(e.clone()) {
+                warn!("emitter: the proc watcher is full, drop message {}", e.id);
+            }
+        }
     }
 }
@@ -1,6 +1,15 @@
-use crate::{sch::Runtime, utils, Event, Message};
-use std::sync::Arc;
-use tracing::error;
+use crate::{sch::Runtime, utils, ActionResult, Event, Message, Result};
+use futures::Stream;
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::sync::mpsc;
+use tracing::{error, warn};
+
+/// the max messages waiting in one [`MessageStream`]
+const STREAM_CAPACITY: usize = 1024;
 
 #[derive(Clone)]
 pub struct ChannelOptions {
@@ -151,6 +160,73 @@ impl Channel {
         });
     }
 
+    /// Subscribe the channel messages as a stream
+    ///
+    /// the messages are filtered by the [`ChannelOptions`] and stored if `ack` is set,
+    /// the stored messages will be re-sent until they are acked by [`MessageStream::ack`]
+    ///
+    /// each stream receives the messages in the emitting order, it holds at most 1024 messages,
+    /// and the others are queued without blocking the emitter until the stream is read
+    ///
+    /// Example
+    /// ```rust,no_run
+    /// use acts::{Engine, ChannelOptions};
+    /// use futures::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let engine = Engine::new();
+    ///     let chan = engine.channel_with_options(&ChannelOptions {
+    ///         id: "chan1".to_string(),
+    ///         ack: true,
+    ///         ..Default::default()
+    ///     });
+    ///
+    ///     let mut stream = chan.subscribe();
+    ///     while let Some(e) = stream.next().await {
+    ///         println!("message: {:?}", e.inner());
+    ///         stream.ack(&e.id).unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub fn subscribe(self: &Arc<Self>) -> MessageStream {
+        let (tx, rx) = mpsc::channel(STREAM_CAPACITY);
+        // the emitter never waits for the stream, the messages are queued and forwarded
+        // to the bounded stream when the receiver has room
+        let (queue_tx, mut queue_rx) = mpsc::unbounded_channel::<Event<Message>>();
+        tokio::spawn(async move {
+            while let Some(e) = queue_rx.recv().await {
+                if tx.send(e).await.is_err() {
+                    // the stream is closed
+                    break;
+                }
+            }
+        });
+
+        let id = utils::shortid();
+        let chan = self.clone();
+        self.runtime
+            .emitter()
+            .subscribe(&self.emit_id, &id, move |e| {
+                if !chan.matches(e) {
+                    return;
+                }
+                chan.store_if(e);
+                if queue_tx.send(e.clone()).is_err() {
+                    warn!(
+                        "channel.subscribe: the stream is closed, drop message {}",
+                        e.id
+                    );
+                }
+            });
+
+        MessageStream {
+            id,
+            chan: self.clone(),
+            receiver: rx,
+        }
+    }
+
     pub fn close(&self) {
         self.runtime.emitter().remove(&self.emit_id);
     }
@@ -186,3 +262,42 @@ impl Drop for Channel {
         self.close();
     }
 }
+
+/// The message stream created by [`Channel::subscribe`]
+pub struct MessageStream {
+    id: String,
+    chan: Arc<Channel>,
+    receiver: mpsc::Receiver<Event<Message>>,
+}
+
+impl MessageStream {
+    /// receive the next message
+    pub async fn recv(&mut self) -> Option<Event<Message>> {
+        self.receiver.recv().await
+    }
+
+    /// ack the message by message id
+    pub fn ack(&self, id: &str) -> Result<ActionResult> {
+        self.chan.runtime.ack(id)
+    }
+
+    /// stop receiving the messages, the other streams and handlers of the channel are kept
+    pub fn close(&mut self) {
+        self.chan.runtime.emitter().unsubscribe(&self.id);
+        self.receiver.close();
+    }
+}
+
+impl Drop for MessageStream {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl Stream for MessageStream {
+    type Item = Event<Message>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
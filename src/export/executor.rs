@@ -1,8 +1,10 @@
 use std::sync::Arc;
 
 use crate::{
-    event::Action, sch::Runtime, store::StoreAdapter, utils::consts, ActionResult, ModelInfo,
-    Result, Vars,
+    event::Action,
+    sch::Runtime,
+    utils::{self, consts},
+    ActError, ActionResult, ProcHandle, Result, Vars,
 };
 
 #[derive(Clone)]
@@ -17,18 +19,59 @@ impl Executor {
         }
     }
 
-    pub fn start(&self, mid: &str, options: &Vars) -> Result<ActionResult> {
+    /// start a proc by model id
+    /// set `ver` in the options to start with a deployed model version instead of the current model
+    /// the returned [`ProcHandle`] can be used to wait for the proc result
+    pub fn start(&self, mid: &str, options: &Vars) -> Result<ProcHandle> {
         let state = ActionResult::begin();
-        let model: ModelInfo = self.runtime.cache().store().models().find(mid)?.into();
-        let workflow = model.workflow()?;
+        let ver = options.get::<u32>(consts::MODEL_VER);
+        let workflow = self.runtime.cache().store().workflow(mid, ver)?;
 
         let mut vars = options.clone();
+        workflow.schema.check_inputs(&mut vars)?;
+
         // set the workflow initiator
         if let Some(uid) = options.get_value(consts::FOR_ACT_KEY_UID) {
             vars.insert(consts::INITIATOR.to_string(), uid.clone());
         }
+
+        // watch the proc before starting to receive all of the messages
+        let pid = options.get::<String>("pid").unwrap_or_else(utils::longid);
+        vars.insert("pid".to_string(), pid.clone().into());
+        // check the running proc before watching, not to replace the watcher of it
+        if self.runtime.proc(&pid).is_some() {
+            return Err(ActError::Action(format!(
+                "proc_id({pid}) is duplicated in running proc list"
+            )));
+        }
+        let mut handle = ProcHandle::new(&self.runtime, &pid)?;
+
         let proc = self.runtime.start(&workflow, &vars)?;
-        state.end_with_data("pid", proc.id())
+        handle.set_result(state.end_with_data("pid", proc.id())?);
+        Ok(handle)
+    }
+
+    /// send a signal to resume all of the `!wait` tasks with the same name and correlation key
+    /// the `options` are set to the resumed tasks
+    ///
+    /// set `start` to a model id to start a new proc with the options
+    /// when there is no waiting task
+    pub fn signal(
+        &self,
+        name: &str,
+        key: &str,
+        start: Option<&str>,
+        options: &Vars,
+    ) -> Result<ActionResult> {
+        let state = ActionResult::begin();
+        let count = self.runtime.signal(name, key, options)?;
+        if count == 0 {
+            if let Some(mid) = start {
+                let handle = self.start(mid, options)?;
+                return state.end_with_data("pid", handle.pid());
+            }
+        }
+        state.end_with_data("count", count)
     }
 
     pub fn ack(&self, id: &str) -> Result<ActionResult> {
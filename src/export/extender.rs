@@ -1,7 +1,7 @@
 use core::fmt;
 use std::sync::{Arc, Mutex};
 
-use crate::{sch::Runtime, ActModule, ActPlugin};
+use crate::{sch::Runtime, ActHandler, ActModule, ActPlugin, Calendar, HandlerOptions, Result};
 
 #[derive(Clone)]
 pub struct Extender {
@@ -76,6 +76,62 @@ impl Extender {
         plugins.push(Box::new(plugin.clone()));
     }
 
+    /// register business calendar
+    /// the `Timeout` and `!delay` refer to it by name to count the durations in working time
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use acts::{Calendar, Engine};
+    ///
+    /// let engine = Engine::new();
+    /// let calendar = Calendar::new()
+    ///     .with_name("office")
+    ///     .with_timezone("Asia/Shanghai")
+    ///     .with_hours(&["09:00-12:00", "13:00-18:00"])
+    ///     .with_holiday("2026-10-01");
+    /// engine.extender().register_calendar(&calendar).unwrap();
+    /// ```
+    pub fn register_calendar(&self, calendar: &Calendar) -> Result<()> {
+        self.runtime.add_calendar(calendar)
+    }
+
+    /// register act handler
+    /// the `!req` acts matched by the key and tag patterns in options are handled in process,
+    /// including the pending ones restored from the store
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use acts::{ActHandler, ActOutcome, Engine, HandlerOptions, Message, Result, Vars};
+    ///
+    /// #[derive(Clone)]
+    /// struct TestHandler;
+    ///
+    /// #[async_trait::async_trait]
+    /// impl ActHandler for TestHandler {
+    ///     async fn handle(&self, _message: &Message) -> Result<ActOutcome> {
+    ///         Ok(ActOutcome::Complete(Vars::new()))
+    ///     }
+    /// }
+    ///
+    /// let engine = Engine::new();
+    /// let options = HandlerOptions::new().with_key("act*").with_concurrency(4);
+    /// engine.extender().register_handler(&options, &TestHandler).unwrap();
+    /// ```
+    pub fn register_handler<T: ActHandler + Clone + 'static>(
+        &self,
+        options: &HandlerOptions,
+        handler: &T,
+    ) -> Result<()> {
+        self.runtime
+            .handlers()
+            .register(options, Arc::new(handler.clone()))?;
+
+        // the pending `req` acts saved before the registration are handled by it too
+        self.runtime.restore_handlers()
+    }
+
     pub fn plugins(&self) -> Arc<Mutex<Vec<Box<dyn ActPlugin>>>> {
         self.plugins.clone()
     }
@@ -0,0 +1,130 @@
+use crate::{
+    sch::Runtime, utils::consts, ActError, ActionResult, Error, Event, Message, MessageState,
+    Result, Vars,
+};
+use std::sync::Arc;
+use tokio::sync::{mpsc, watch};
+
+/// The final result of a proc
+#[derive(Debug, Clone)]
+pub struct ProcResult {
+    pub pid: String,
+
+    /// the proc final state
+    /// such as completed, cancelled, aborted or error
+    pub state: MessageState,
+
+    /// the workflow outputs
+    pub outputs: Vars,
+
+    /// the error if the proc is failed
+    pub err: Option<Error>,
+
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+/// The handle of a started proc
+///
+/// it holds at most 1024 unread proc messages and drops the later ones until they are read,
+/// use [`crate::Channel::subscribe`] to receive all of the messages
+///
+/// Example
+/// ```rust,no_run
+/// use acts::{Engine, Act, Workflow, Vars};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let engine = Engine::new();
+///     let workflow = Workflow::new().with_id("m1").with_step(|step| {
+///             step.with_id("step1").with_act(Act::req(|act| act.with_id("act1")))
+///     });
+///     engine.manager().deploy(&workflow).expect("fail to deploy workflow");
+///
+///     let executor = engine.executor();
+///     let mut handle = executor.start(&workflow.id, &Vars::new()).unwrap();
+///     let e = handle
+///         .next_message(|m| m.is_key("act1") && m.is_state("created"))
+///         .await
+///         .unwrap();
+///     executor.complete(&e.pid, &e.tid, &Vars::new()).unwrap();
+///
+///     let result = handle.wait().await.unwrap();
+///     println!("state={:?} outputs={:?}", result.state, result.outputs);
+/// }
+/// ```
+pub struct ProcHandle {
+    pid: String,
+    runtime: Arc<Runtime>,
+    result: ActionResult,
+    messages: mpsc::Receiver<Event<Message>>,
+    end: watch::Receiver<Option<Event<Message>>>,
+}
+
+impl ProcHandle {
+    pub(crate) fn new(rt: &Arc<Runtime>, pid: &str) -> Result<Self> {
+        let (messages, end) = rt.emitter().watch_proc(pid)?;
+        Ok(Self {
+            pid: pid.to_string(),
+            runtime: rt.clone(),
+            result: ActionResult::begin(),
+            messages,
+            end,
+        })
+    }
+
+    pub(crate) fn set_result(&mut self, result: ActionResult) {
+        self.result = result;
+    }
+
+    /// the proc id
+    pub fn pid(&self) -> &str {
+        &self.pid
+    }
+
+    /// the outputs of the start action
+    pub fn outputs(&self) -> &Vars {
+        self.result.outputs()
+    }
+
+    /// wait until the proc is completed and return the final result
+    pub async fn wait(&self) -> Result<ProcResult> {
+        let mut end = self.end.clone();
+        let e = end
+            .wait_for(|v| v.is_some())
+            .await
+            .map_err(|_| ActError::Runtime(format!("proc '{}' is not completed", self.pid)))?
+            .clone()
+            .unwrap();
+
+        Ok(ProcResult {
+            pid: e.pid.clone(),
+            state: e.state.clone(),
+            outputs: e.outputs.clone(),
+            err: e.inputs.get::<Error>(consts::ACT_ERR_KEY),
+            start_time: e.start_time,
+            end_time: e.end_time,
+        })
+    }
+
+    /// receive the next proc message matched by the filter
+    /// return None if the proc is completed and there are no more messages
+    pub async fn next_message(
+        &mut self,
+        filter: impl Fn(&Message) -> bool,
+    ) -> Option<Event<Message>> {
+        while let Some(e) = self.messages.recv().await {
+            if filter(&e) {
+                return Some(e);
+            }
+        }
+
+        None
+    }
+}
+
+impl Drop for ProcHandle {
+    fn drop(&mut self) {
+        self.runtime.emitter().unwatch_proc(&self.pid);
+    }
+}
@@ -1,11 +1,12 @@
 use crate::{
     data::Package,
-    sch::Runtime,
+    sch::{self, NodeContent, Runtime, StatementBatch, TaskLifeCycle, TaskState},
     store::{Cond, Expr, StoreAdapter},
-    utils::Id,
-    ActionResult, ModelInfo, PackageInfo, ProcInfo, Query, Result, TaskInfo, Workflow,
+    utils::{self, consts, Id},
+    Act, ActError, ActionResult, DueInfo, HistoryMode, MigrateReport, ModelInfo,
+    PackageInfo, ProcInfo, Query, Result, TaskInfo, Trigger, TriggerInfo, Workflow,
 };
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use tracing::instrument;
 
 #[derive(Clone)]
@@ -23,6 +24,8 @@ impl Manager {
     #[instrument(skip(self))]
     pub fn publish(&self, pack: &Package) -> Result<ActionResult> {
         let state = ActionResult::begin();
+        let script = String::from_utf8(pack.file_data.clone()).map_err(ActError::from)?;
+        self.runtime.env().check(&pack.id, &script)?;
         self.runtime.cache().store().publish(pack)?;
         state.end()
     }
@@ -56,11 +59,28 @@ impl Manager {
         }
     }
 
+    /// deploy the model
+    /// the fragment includes are expanded before deploying
+    /// the model is rejected by the lint if the `deploy_severity` of the config is set
     #[instrument(skip(self))]
     pub fn deploy(&self, model: &Workflow) -> Result<ActionResult> {
         let state = ActionResult::begin();
+        let mut model = model.clone();
+        sch::NodeTree::expand(&mut model, &|source, ver| self.fragments(source, ver))?;
         model.valid()?;
-        self.runtime.cache().store().deploy(model)?;
+        self.check_scripts(&model)?;
+        if let Some(level) = self.runtime.config().deploy_severity {
+            let diagnostics = model
+                .lint()
+                .iter()
+                .filter(|d| d.severity >= level)
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>();
+            if !diagnostics.is_empty() {
+                return Err(ActError::Model(diagnostics.join("\n")));
+            }
+        }
+        self.runtime.cache().store().deploy(&model)?;
         state.end()
     }
 
@@ -80,9 +100,16 @@ impl Manager {
         }
     }
 
+    /// get the model by id
+    /// the current model is returned if the ver is None
     #[instrument(skip(self))]
-    pub fn model(&self, id: &str, fmt: &str) -> Result<ModelInfo> {
-        match self.runtime.cache().store().models().find(id) {
+    pub fn model(&self, id: &str, ver: Option<u32>, fmt: &str) -> Result<ModelInfo> {
+        let store = self.runtime.cache().store();
+        let m = match ver {
+            Some(ver) => store.model_version(id, ver),
+            None => store.models().find(id),
+        };
+        match m {
             Ok(m) => {
                 let mut model: ModelInfo = m.into();
                 if fmt == "tree" {
@@ -95,9 +122,71 @@ impl Manager {
         }
     }
 
+    /// get all of the deployed versions of the model
+    #[instrument(skip(self))]
+    pub fn model_versions(&self, id: &str) -> Result<Vec<ModelInfo>> {
+        let versions = self.runtime.cache().store().model_versions(id)?;
+        Ok(versions.into_iter().map(|m| m.into()).collect())
+    }
+
+    /// get the cron triggers of the model with the next `count` fire times
+    #[instrument(skip(self))]
+    pub fn triggers(&self, mid: &str, count: usize) -> Result<Vec<TriggerInfo>> {
+        let now = utils::time::time_millis();
+        let mut ret = Vec::new();
+        for data in self.runtime.cache().store().model_triggers(mid)? {
+            let trigger: Trigger = serde_json::from_str(&data.data).map_err(ActError::from)?;
+            ret.push(TriggerInfo {
+                next_times: trigger.next_times(now, count)?,
+                id: data.id,
+                mid: data.mid,
+                cron: trigger.cron,
+                timezone: trigger.timezone,
+                catch_up: trigger.catch_up,
+                last_time: data.last_time,
+            });
+        }
+        ret.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(ret)
+    }
+
+    /// set the deployed version as the current model
+    /// the new procs will start with the version
+    #[instrument(skip(self))]
+    pub fn rollback(&self, id: &str, ver: u32) -> Result<ActionResult> {
+        let state = ActionResult::begin();
+        self.runtime.cache().store().rollback(id, ver)?;
+        state.end()
+    }
+
+    /// migrate the running proc to the deployed model version
+    /// the mapping is used to map the old node id to the new node id
+    /// if the node is not in the new model
+    /// it returns error when the proc has queued tasks, which can be migrated later
+    #[instrument(skip(self))]
+    pub fn migrate(
+        &self,
+        pid: &str,
+        ver: u32,
+        mapping: &HashMap<String, String>,
+    ) -> Result<MigrateReport> {
+        self.migrate_with(pid, ver, mapping, false)
+    }
+
+    /// report the changes of the migration without applying them
+    #[instrument(skip(self))]
+    pub fn migrate_dry_run(
+        &self,
+        pid: &str,
+        ver: u32,
+        mapping: &HashMap<String, String>,
+    ) -> Result<MigrateReport> {
+        self.migrate_with(pid, ver, mapping, true)
+    }
+
     #[instrument(skip(self))]
     pub fn remove(&self, model_id: &str) -> Result<bool> {
-        self.runtime.cache().store().models().delete(model_id)
+        self.runtime.cache().store().remove_model(model_id)
     }
 
     #[instrument(skip(self))]
@@ -124,18 +213,7 @@ impl Manager {
                 let mut info: ProcInfo = proc.into();
 
                 if let Some(proc) = self.runtime.cache().proc(pid, &self.runtime) {
-                    if fmt == "tree" {
-                        info.tasks = proc.tree_output();
-                    } else if fmt == "json" {
-                        let mut tasks: Vec<TaskInfo> = Vec::new();
-                        for task in proc.tasks().iter() {
-                            tasks.push(task.into());
-                        }
-
-                        tasks.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
-                        info.tasks = serde_json::to_string_pretty(&tasks)
-                            .unwrap_or_else(|err| err.to_string());
-                    }
+                    info.tasks = self.tasks_output(&proc, fmt);
                 }
 
                 Ok(info)
@@ -144,6 +222,69 @@ impl Manager {
         }
     }
 
+    /// list the completed procs
+    /// the histories depend on the `history_mode` of the config
+    #[instrument(skip(self))]
+    pub fn histories(&self, cap: usize) -> Result<Vec<ProcInfo>> {
+        let query = Query::new().set_limit(cap);
+        self.query_histories(&query)
+    }
+
+    /// query the completed procs by the query conditions
+    #[instrument(skip(self))]
+    pub fn query_histories(&self, query: &Query) -> Result<Vec<ProcInfo>> {
+        let store = self.runtime.cache().store();
+        let result = match self.runtime.config().history_mode {
+            HistoryMode::Archive => store.proc_histories().query(query),
+            HistoryMode::Keep | HistoryMode::Delete => {
+                let query = query.clone().push(
+                    Cond::or()
+                        .push(Expr::eq("state", TaskState::Completed.to_string()))
+                        .push(Expr::eq("state", TaskState::Cancelled.to_string()))
+                        .push(Expr::eq("state", TaskState::Error.to_string()))
+                        .push(Expr::eq("state", TaskState::Aborted.to_string())),
+                );
+                store.procs().query(&query)
+            }
+        };
+        match result {
+            Ok(mut procs) => {
+                procs.sort_by_key(|p| p.timestamp);
+                let mut ret = Vec::new();
+                for t in &procs {
+                    ret.push(t.into());
+                }
+
+                Ok(ret)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// get the completed proc with tasks in json or tree format
+    #[instrument(skip(self))]
+    pub fn history(&self, pid: &str, fmt: &str) -> Result<ProcInfo> {
+        let store = self.runtime.cache().store();
+        let is_archive = self.runtime.config().history_mode == HistoryMode::Archive;
+        let data = match is_archive {
+            true => store.proc_histories().find(pid)?,
+            false => store.procs().find(pid)?,
+        };
+        if !TaskState::from(data.state.clone()).is_completed() {
+            return Err(ActError::Runtime(format!("proc '{pid}' is not completed")));
+        }
+
+        let mut info: ProcInfo = (&data).into();
+        let proc = match is_archive {
+            true => store.load_history(pid, &self.runtime)?,
+            false => store.load_proc(pid, &self.runtime)?,
+        };
+        if let Some(proc) = proc {
+            info.tasks = self.tasks_output(&proc, fmt);
+        }
+        Ok(info)
+    }
+
     #[instrument(skip(self))]
     pub fn tasks(&self, pid: &str, count: usize) -> Result<Vec<TaskInfo>> {
         let query = Query::new()
@@ -152,7 +293,7 @@ impl Manager {
         match self.runtime.cache().store().tasks().query(&query) {
             Ok(mut tasks) => {
                 let mut ret = Vec::new();
-                tasks.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+                tasks.sort_by_key(|t| t.timestamp);
                 for t in tasks.into_iter().take(count) {
                     ret.push(t.into());
                 }
@@ -173,7 +314,7 @@ impl Manager {
         match self.runtime.cache().store().tasks().query(&query) {
             Ok(mut tasks) => {
                 let mut ret = Vec::new();
-                tasks.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+                tasks.sort_by_key(|t| t.timestamp);
                 for t in tasks {
                     ret.push(t.into());
                 }
@@ -192,4 +333,145 @@ impl Manager {
             Err(err) => Err(err),
         }
     }
+
+    /// get the computed due times of the waiting tasks in the proc
+    /// the timeouts and delays with a calendar are counted in its working time,
+    /// so is the remaining time to the due
+    #[instrument(skip(self))]
+    pub fn dues(&self, pid: &str) -> Result<Vec<DueInfo>> {
+        let proc = self
+            .runtime
+            .cache()
+            .proc(pid, &self.runtime)
+            .ok_or(ActError::Runtime(format!("cannot find proc '{pid}'")))?;
+
+        let now = utils::time::time_millis();
+        let mut ret = Vec::new();
+        for task in proc.tasks() {
+            let info = |source: &str, calendar: &Option<String>, due: i64| -> Result<DueInfo> {
+                let remaining = match calendar {
+                    Some(name) => self.runtime.calendar(name)?.elapsed(now, due)?,
+                    None => (due - now).max(0),
+                };
+                Ok(DueInfo {
+                    pid: task.pid.clone(),
+                    tid: task.id.clone(),
+                    node_id: task.node().id().to_string(),
+                    name: task.node().name(),
+                    r#type: task.node().typ(),
+                    source: source.to_string(),
+                    calendar: calendar.clone(),
+                    due,
+                    remaining,
+                })
+            };
+            if let NodeContent::Act(Act::Delay(delay)) = &task.node().content {
+                if task.state().is_interrupted() {
+                    if let Some(due) = task.data().get::<i64>(consts::DELAY_UNTIL) {
+                        ret.push(info("delay", &delay.calendar, due)?);
+                    }
+                }
+            }
+
+            let ctx = task.create_context();
+            let hooks = task.hooks();
+            for batch in hooks.get(&TaskLifeCycle::Timeout).into_iter().flatten() {
+                if let StatementBatch::Timeout(timeout) = batch {
+                    if let Some(due) = timeout.next_due(&ctx)? {
+                        ret.push(info("timeout", &timeout.calendar, due)?);
+                    }
+                }
+            }
+        }
+        ret.sort_by_key(|d| d.due);
+        Ok(ret)
+    }
+
+    /// load the fragments source by a deployed model or a package
+    /// the package script is evaluated to an object with the `fragments`
+    fn fragments(&self, source: &str, ver: Option<u32>) -> Result<Workflow> {
+        let store = self.runtime.cache().store();
+        let model = match ver {
+            Some(ver) => store.model_version(source, ver),
+            None => store.models().find(source),
+        };
+        match model {
+            Ok(m) => Workflow::from_yml(&m.data),
+            Err(_) if ver.is_none() => {
+                let pack = store.packages().find(source).map_err(|_| {
+                    ActError::Model(format!("cannot find the fragments source '{source}'"))
+                })?;
+                let script = String::from_utf8(pack.file_data).map_err(ActError::from)?;
+                let mut workflow = self.runtime.env().eval::<Workflow>(&script)?;
+                workflow.id = pack.id;
+                Ok(workflow)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// compile the model scripts to find the syntax errors before the proc runs
+    fn check_scripts(&self, model: &Workflow) -> Result<()> {
+        let errors = model
+            .scripts()
+            .iter()
+            .filter_map(|(path, script)| self.runtime.env().check(path, script).err())
+            .map(|err| err.to_string())
+            .collect::<Vec<_>>();
+        if !errors.is_empty() {
+            return Err(ActError::Script(errors.join("\n")));
+        }
+
+        Ok(())
+    }
+
+    fn migrate_with(
+        &self,
+        pid: &str,
+        ver: u32,
+        mapping: &HashMap<String, String>,
+        dry_run: bool,
+    ) -> Result<MigrateReport> {
+        let proc = self
+            .runtime
+            .proc(pid)
+            .ok_or(ActError::Runtime(format!("cannot find proc '{pid}'")))?;
+        if proc.state().is_completed() {
+            return Err(ActError::Runtime(format!(
+                "proc '{pid}' is already completed"
+            )));
+        }
+
+        let store = self.runtime.cache().store();
+        let workflow = store.workflow(&proc.model().id, Some(ver))?;
+
+        // keep the proc locked until the migrated tasks are saved
+        proc.sync(|| {
+            let report = proc.migrate(&workflow, mapping, dry_run)?;
+            if !dry_run {
+                store.upsert_proc(&proc)?;
+                for task in proc.tasks() {
+                    store.upsert_task(&task)?;
+                }
+            }
+
+            Ok(report)
+        })
+    }
+
+    fn tasks_output(&self, proc: &Arc<sch::Proc>, fmt: &str) -> String {
+        if fmt == "tree" {
+            return proc.tree_output();
+        } else if fmt == "json" {
+            let mut tasks: Vec<TaskInfo> = Vec::new();
+            for task in proc.tasks().iter() {
+                tasks.push(task.into());
+            }
+
+            tasks.sort_by_key(|t| t.timestamp);
+            return serde_json::to_string_pretty(&tasks).unwrap_or_else(|err| err.to_string());
+        }
+
+        String::new()
+    }
 }
@@ -1,12 +1,14 @@
 mod channel;
 mod executor;
 mod extender;
+mod handle;
 mod manager;
 
 #[cfg(test)]
 mod tests;
 
-pub use channel::{Channel, ChannelOptions};
+pub use channel::{Channel, ChannelOptions, MessageStream};
 pub use executor::Executor;
 pub use extender::Extender;
+pub use handle::{ProcHandle, ProcResult};
 pub use manager::Manager;
@@ -2,10 +2,16 @@ use crate::{
     data,
     event::{MessageState, Model},
     sch::TaskState,
-    utils, Act, ActPlugin, ChannelOptions, Engine, Message, StoreAdapter, Vars, Workflow,
+    utils, Act, ActError, ActPlugin, Builder, Calendar, CatchUp, ChannelOptions, Cond, Engine,
+    Expr, HistoryMode, Message, MigrateKind, ParamType, Query, Severity, StmtBuild, StoreAdapter,
+    TaskInfo, Vars, Workflow,
 };
+use futures::StreamExt;
 use serde_json::json;
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 #[tokio::test]
 async fn export_manager_publish_ok() {
@@ -14,7 +20,7 @@ async fn export_manager_publish_ok() {
     let pack = data::Package {
         id: "pack1".to_string(),
         name: "package 1".to_string(),
-        file_data: vec![0x01, 0x02],
+        file_data: br#"act.msg({ key: "msg1" })"#.to_vec(),
         ..Default::default()
     };
 
@@ -24,6 +30,21 @@ async fn export_manager_publish_ok() {
     assert_eq!(manager.publish(&pack).is_ok(), true);
 }
 
+#[tokio::test]
+async fn export_manager_publish_syntax_error() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let pack = data::Package {
+        id: "pack1".to_string(),
+        name: "package 1".to_string(),
+        file_data: b"act.msg({ key: ".to_vec(),
+        ..Default::default()
+    };
+
+    let result = manager.publish(&pack);
+    assert!(result.unwrap_err().to_string().starts_with("pack1:1:"));
+}
+
 #[tokio::test]
 async fn export_manager_deploy_ok() {
     let engine = Engine::new();
@@ -35,7 +56,7 @@ async fn export_manager_deploy_ok() {
     let result = manager.deploy(&model);
 
     assert_eq!(result.is_ok(), true);
-    assert_eq!(manager.model(&model.id, "text").is_ok(), true);
+    assert_eq!(manager.model(&model.id, None, "text").is_ok(), true);
 }
 
 #[tokio::test]
@@ -77,6 +98,88 @@ async fn export_manager_deploy_dup_id_error() {
     assert_eq!(result.is_err(), true);
 }
 
+#[tokio::test]
+async fn export_manager_deploy_lint_error() {
+    let engine = Builder::new().deploy_severity(Severity::Error).build();
+    let manager = engine.manager();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_id("step1").with_next("step3"))
+        .with_step(|step| step.with_id("step2"));
+
+    let result = manager.deploy(&model);
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn export_manager_deploy_lint_below_severity() {
+    let engine = Builder::new().deploy_severity(Severity::Error).build();
+    let manager = engine.manager();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_id("step1").with_next("step3"))
+        .with_step(|step| step.with_id("step2"))
+        .with_step(|step| step.with_id("step3"));
+
+    // step2 is unreachable but it is only a warning
+    let result = manager.deploy(&model);
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn export_manager_deploy_lint_warning() {
+    let engine = Builder::new().deploy_severity(Severity::Warning).build();
+    let manager = engine.manager();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_id("step1").with_next("step3"))
+        .with_step(|step| step.with_id("step2"))
+        .with_step(|step| step.with_id("step3"));
+
+    let result = manager.deploy(&model);
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn export_manager_deploy_syntax_error() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          if: a >
+        - id: step2
+          run: |
+            let a = 1;
+            let b = (a + ;
+          acts:
+            - !each
+              in: '["u1", "u2"'
+              run:
+                - !msg
+                  id: msg1
+    "#;
+    let model = Workflow::from_yml(text).unwrap();
+
+    let err = manager.deploy(&model).unwrap_err().to_string();
+    assert!(err.contains("step1.if:1:"));
+    assert!(err.contains("step2.run:2:"));
+    assert!(err.contains("step2.acts[0].in:1:"));
+}
+
+#[tokio::test]
+async fn export_manager_deploy_expr_syntax_error() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_id("step1").with_input("a", json!("${ 1 + }")));
+
+    let err = manager.deploy(&model).unwrap_err().to_string();
+    assert!(err.contains("step1.inputs.a:1:"));
+}
+
 #[tokio::test]
 async fn engine_executor_start_no_pid() {
     let engine = Engine::new();
@@ -189,7 +292,7 @@ async fn export_manager_model_get_text() {
     model.set_id(&utils::longid());
     manager.deploy(&model).unwrap();
 
-    let result = manager.model(&model.id, "text").unwrap();
+    let result = manager.model(&model.id, None, "text").unwrap();
     assert_eq!(result.id, model.id);
     assert_eq!(result.model.is_empty(), false);
 }
@@ -203,11 +306,64 @@ async fn export_manager_model_get_tree() {
     model.set_id(&utils::longid());
     manager.deploy(&model).unwrap();
 
-    let result = manager.model(&model.id, "tree").unwrap();
+    let result = manager.model(&model.id, None, "tree").unwrap();
     assert_eq!(result.id, model.id);
     assert_eq!(result.model.is_empty(), false);
 }
 
+#[tokio::test]
+async fn export_manager_model_get_tree_with_fragments() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let fragments = Workflow::new().with_id("fragments").with_fragment(|f| {
+        f.with_id("approval").with_step(|step| {
+            step.with_id("approval")
+                .with_act(Act::req(|act| act.with_id("approve")))
+        })
+    });
+    manager.deploy(&fragments).unwrap();
+
+    let model = Workflow::new().with_id("m1").with_step(|step| {
+        step.with_id("step1")
+            .with_include(|i| i.with_uses("fragments/approval@1"))
+    });
+    manager.deploy(&model).unwrap();
+
+    let result = manager.model("m1", None, "tree").unwrap();
+    assert!(result.model.contains("step1_approve"));
+
+    // the fragments source does not have the version 2
+    let model = Workflow::new().with_id("m2").with_step(|step| {
+        step.with_id("step1")
+            .with_include(|i| i.with_uses("fragments/approval@2"))
+    });
+    assert!(manager.deploy(&model).is_err());
+}
+
+#[tokio::test]
+async fn export_manager_model_fragments_from_package() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let pack = data::Package {
+        id: "fragments".to_string(),
+        name: "fragments".to_string(),
+        file_data: br#"({ fragments: [{ id: "notify", acts: [{ msg: { id: "notify" } }] }] })"#
+            .to_vec(),
+        ..Default::default()
+    };
+    manager.publish(&pack).unwrap();
+
+    let model = Workflow::new().with_id("m1").with_step(|step| {
+        step.with_id("step1").with_act(Act::include(|i| {
+            i.with_id("notify1").with_uses("fragments/notify")
+        }))
+    });
+    manager.deploy(&model).unwrap();
+
+    let result = manager.model("m1", None, "tree").unwrap();
+    assert!(result.model.contains("notify1"));
+}
+
 #[tokio::test]
 async fn export_manager_model_remove() {
     let engine = Engine::new();
@@ -217,188 +373,1260 @@ async fn export_manager_model_remove() {
     model.set_id(&utils::longid());
     manager.deploy(&model).unwrap();
 
-    manager.remove(&model.id).unwrap();
-    assert_eq!(manager.models(10).unwrap().len(), 0);
+    manager.remove(&model.id).unwrap();
+    assert_eq!(manager.models(10).unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn export_manager_model_remove_versions() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_id("step1"));
+    manager.deploy(&model).unwrap();
+    manager.deploy(&model).unwrap();
+
+    manager.remove(&model.id).unwrap();
+    assert_eq!(manager.model_versions(&model.id).unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn export_manager_model_versions() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let mut model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_id("step1"));
+    manager.deploy(&model).unwrap();
+    model.name = "v2".to_string();
+    manager.deploy(&model).unwrap();
+
+    let versions = manager.model_versions(&model.id).unwrap();
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[0].id, model.id);
+    assert_eq!(versions[0].ver, 1);
+    assert_eq!(versions[1].ver, 2);
+    assert_eq!(versions[1].name, "v2");
+}
+
+#[tokio::test]
+async fn export_manager_model_get_by_ver() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let mut model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_id("step1"));
+    manager.deploy(&model).unwrap();
+    model.name = "v2".to_string();
+    manager.deploy(&model).unwrap();
+
+    let result = manager.model(&model.id, Some(1), "text").unwrap();
+    assert_eq!(result.ver, 1);
+    assert_eq!(result.name, "");
+
+    let result = manager.model(&model.id, None, "text").unwrap();
+    assert_eq!(result.ver, 2);
+    assert_eq!(result.name, "v2");
+}
+
+#[tokio::test]
+async fn export_manager_model_get_by_ver_not_found() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_id("step1"));
+    manager.deploy(&model).unwrap();
+
+    assert!(manager.model(&model.id, Some(5), "text").is_err());
+}
+
+#[tokio::test]
+async fn export_manager_model_rollback() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let mut model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_id("step1"));
+    manager.deploy(&model).unwrap();
+    model.name = "v2".to_string();
+    manager.deploy(&model).unwrap();
+
+    manager.rollback(&model.id, 1).unwrap();
+    let result = manager.model(&model.id, None, "text").unwrap();
+    assert_eq!(result.ver, 1);
+    assert_eq!(result.name, "");
+
+    // deploy after rollback still creates a new version
+    manager.deploy(&model).unwrap();
+    let result = manager.model(&model.id, None, "text").unwrap();
+    assert_eq!(result.ver, 3);
+    assert_eq!(manager.model_versions(&model.id).unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn export_manager_model_rollback_not_found() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_id("step1"));
+    manager.deploy(&model).unwrap();
+
+    assert!(manager.rollback(&model.id, 2).is_err());
+}
+
+#[tokio::test]
+async fn export_manager_triggers() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_trigger(|t| {
+            t.with_id("t1")
+                .with_cron("0 9 * * *")
+                .with_timezone("Asia/Shanghai")
+        })
+        .with_step(|step| step.with_id("step1"));
+    manager.deploy(&model).unwrap();
+
+    let triggers = manager.triggers(&model.id, 3).unwrap();
+    assert_eq!(triggers.len(), 1);
+    assert_eq!(triggers[0].cron, "0 9 * * *");
+    assert_eq!(triggers[0].timezone, Some("Asia/Shanghai".to_string()));
+    assert_eq!(triggers[0].next_times.len(), 3);
+    assert_eq!(
+        triggers[0].next_times[1] - triggers[0].next_times[0],
+        24 * 3600 * 1000
+    );
+
+    manager.remove(&model.id).unwrap();
+    assert_eq!(manager.triggers(&model.id, 3).unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn export_manager_deploy_trigger_invalid_cron() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_trigger(|t| t.with_cron("every day"))
+        .with_step(|step| step.with_id("step1"));
+    assert!(manager.deploy(&model).is_err());
+}
+
+#[tokio::test]
+async fn export_manager_dues() {
+    let engine = Engine::new();
+    let calendar = Calendar::new()
+        .with_name("office")
+        .with_timezone("Asia/Shanghai");
+    engine.extender().register_calendar(&calendar).unwrap();
+
+    let model = Workflow::new().with_id(&utils::longid()).with_step(|step| {
+        step.with_id("step1")
+            .with_timeout(|t| t.with_on("8h").with_calendar("office"))
+            .with_act(Act::delay(|act| {
+                act.with_id("delay1")
+                    .with_duration("1d")
+                    .with_calendar("office")
+            }))
+            .with_act(Act::req(|act| {
+                act.with_id("act1").with_timeout(|t| t.with_on("2h"))
+            }))
+    });
+    engine.manager().deploy(&model).unwrap();
+    let mut handle = engine.executor().start(&model.id, &Vars::new()).unwrap();
+    handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await
+        .unwrap();
+
+    let proc = engine.runtime().proc(handle.pid()).unwrap();
+    let start_time = |nid: &str| proc.task_by_nid(nid).first().unwrap().start_time();
+    let dues = engine.manager().dues(handle.pid()).unwrap();
+    assert_eq!(dues.len(), 3);
+
+    let act1 = dues.iter().find(|d| d.node_id == "act1").unwrap();
+    assert_eq!(act1.source, "timeout");
+    assert_eq!(act1.calendar, None);
+    assert_eq!(act1.due, start_time("act1") + 2 * 3600 * 1000);
+    assert!(act1.remaining > 0 && act1.remaining <= 2 * 3600 * 1000);
+
+    let step1 = dues.iter().find(|d| d.node_id == "step1").unwrap();
+    assert_eq!(step1.calendar, Some("office".to_string()));
+    assert_eq!(
+        step1.due,
+        calendar.add(start_time("step1"), 8 * 3600 * 1000).unwrap()
+    );
+
+    // the remaining time is counted in the working time
+    assert!(step1.remaining > 0 && step1.remaining <= 8 * 3600 * 1000);
+
+    let delay1 = dues.iter().find(|d| d.node_id == "delay1").unwrap();
+    assert_eq!(delay1.source, "delay");
+    assert_eq!(delay1.r#type, "delay");
+    assert!(
+        delay1.due
+            >= calendar
+                .add(start_time("delay1"), 24 * 3600 * 1000)
+                .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn export_manager_dues_not_found() {
+    let engine = Engine::new();
+    assert!(engine.manager().dues("no_exists").is_err());
+}
+
+#[tokio::test]
+async fn export_extender_register_calendar_invalid() {
+    let engine = Engine::new();
+    let calendar = Calendar::new().with_name("c1").with_hours(&["9-5"]);
+    assert!(engine.extender().register_calendar(&calendar).is_err());
+}
+
+#[tokio::test]
+async fn export_runtime_fire_triggers() {
+    let (engine, mid) = deploy_trigger_model(CatchUp::Skip);
+    let rt = engine.runtime();
+    let procs = rt.fire_triggers(TRIGGER_START + 3 * HOUR, false).unwrap();
+    assert_eq!(procs.len(), 3);
+    assert_eq!(procs[0].model().id, mid);
+    assert_eq!(
+        procs[0].model().inputs.get::<String>("kind").unwrap(),
+        "hourly"
+    );
+
+    // the trigger is moved to the next time
+    let trigger = rt.cache().store().model_triggers(&mid).unwrap().remove(0);
+    assert_eq!(trigger.last_time, TRIGGER_START + 3 * HOUR);
+    assert_eq!(trigger.next_time, TRIGGER_START + 4 * HOUR);
+    assert_eq!(
+        rt.fire_triggers(TRIGGER_START + 3 * HOUR, false)
+            .unwrap()
+            .len(),
+        0
+    );
+}
+
+#[tokio::test]
+async fn export_runtime_fire_triggers_catch_up_skip() {
+    let (engine, _) = deploy_trigger_model(CatchUp::Skip);
+    let procs = engine
+        .runtime()
+        .fire_triggers(TRIGGER_START + 3 * HOUR, true)
+        .unwrap();
+    assert_eq!(procs.len(), 0);
+}
+
+#[tokio::test]
+async fn export_runtime_fire_triggers_catch_up_once() {
+    let (engine, _) = deploy_trigger_model(CatchUp::Once);
+    let procs = engine
+        .runtime()
+        .fire_triggers(TRIGGER_START + 3 * HOUR, true)
+        .unwrap();
+    assert_eq!(procs.len(), 1);
+}
+
+#[tokio::test]
+async fn export_runtime_fire_triggers_catch_up_all() {
+    let (engine, _) = deploy_trigger_model(CatchUp::All);
+    let procs = engine
+        .runtime()
+        .fire_triggers(TRIGGER_START + 3 * HOUR, true)
+        .unwrap();
+    assert_eq!(procs.len(), 3);
+}
+
+#[tokio::test]
+async fn export_manager_migrate() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let mid = utils::longid();
+    let model = Workflow::new()
+        .with_id(&mid)
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_act(Act::req(|act| act.with_id("act1")))
+        })
+        .with_step(|step| step.with_id("step2"));
+    manager.deploy(&model).unwrap();
+
+    let mut handle = engine.executor().start(&mid, &Vars::new()).unwrap();
+    let e = handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await
+        .unwrap();
+
+    let model = Workflow::new()
+        .with_id(&mid)
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_act(Act::req(|act| act.with_id("act1")))
+        })
+        .with_step(|step| step.with_id("step2").with_name("step 2"))
+        .with_step(|step| {
+            step.with_id("step3")
+                .with_act(Act::req(|act| act.with_id("act3")))
+        });
+    manager.deploy(&model).unwrap();
+
+    let report = manager.migrate(handle.pid(), 2, &HashMap::new()).unwrap();
+    assert_eq!(report.from_ver, 1);
+    assert_eq!(report.to_ver, 2);
+    assert!(!report.dry_run);
+    assert!(report
+        .changes
+        .iter()
+        .any(|c| c.from == "act1" && c.kind == MigrateKind::Kept));
+    assert_eq!(report.added, ["act3", "step3"]);
+
+    let proc = engine.runtime().proc(handle.pid()).unwrap();
+    assert_eq!(proc.info().ver, 2);
+
+    // the proc continues with the new model
+    engine
+        .executor()
+        .complete(&e.pid, &e.tid, &Vars::new())
+        .unwrap();
+    let e = handle
+        .next_message(|m| m.is_key("act3") && m.is_state("created"))
+        .await;
+    assert!(e.is_some());
+}
+
+#[tokio::test]
+async fn export_manager_migrate_dry_run() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let mid = utils::longid();
+    let mut model = Workflow::new().with_id(&mid).with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+    manager.deploy(&model).unwrap();
+
+    let mut handle = engine.executor().start(&mid, &Vars::new()).unwrap();
+    handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await
+        .unwrap();
+
+    model.name = "v2".to_string();
+    manager.deploy(&model).unwrap();
+
+    let report = manager
+        .migrate_dry_run(handle.pid(), 2, &HashMap::new())
+        .unwrap();
+    assert!(report.dry_run);
+    assert!(report
+        .changes
+        .iter()
+        .any(|c| c.from == mid && c.kind == MigrateKind::Changed));
+
+    let proc = engine.runtime().proc(handle.pid()).unwrap();
+    assert_eq!(proc.info().ver, 1);
+}
+
+#[tokio::test]
+async fn export_manager_migrate_active_node_removed_error() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let mid = utils::longid();
+    let model = Workflow::new().with_id(&mid).with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+    manager.deploy(&model).unwrap();
+
+    let mut handle = engine.executor().start(&mid, &Vars::new()).unwrap();
+    handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await
+        .unwrap();
+
+    let model = Workflow::new().with_id(&mid).with_step(|step| {
+        step.with_id("step2")
+            .with_act(Act::req(|act| act.with_id("act2")))
+    });
+    manager.deploy(&model).unwrap();
+
+    let ret = manager.migrate(handle.pid(), 2, &HashMap::new());
+    assert!(ret.is_err());
+
+    let proc = engine.runtime().proc(handle.pid()).unwrap();
+    assert_eq!(proc.info().ver, 1);
+}
+
+#[tokio::test]
+async fn export_manager_migrate_completed_node_removed() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let mid = utils::longid();
+    let model = Workflow::new()
+        .with_id(&mid)
+        .with_step(|step| step.with_id("step0"))
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_act(Act::req(|act| act.with_id("act1")))
+        });
+    manager.deploy(&model).unwrap();
+
+    let mut handle = engine.executor().start(&mid, &Vars::new()).unwrap();
+    handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await
+        .unwrap();
+    let proc = engine.runtime().proc(handle.pid()).unwrap();
+    let tid = proc.find_tasks(|t| t.node().id() == "step0")[0].id.clone();
+
+    let model = Workflow::new().with_id(&mid).with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+    manager.deploy(&model).unwrap();
+
+    let report = manager.migrate(handle.pid(), 2, &HashMap::new()).unwrap();
+    assert!(report
+        .changes
+        .iter()
+        .any(|c| c.tid == tid && c.kind == MigrateKind::Removed));
+
+    // the completed task of the removed node is kept for the history
+    let task = proc.task(&tid).unwrap();
+    assert_eq!(task.node().id(), "step0");
+    assert_eq!(task.state(), TaskState::Completed);
+    assert!(manager.task(handle.pid(), &tid).is_ok());
+}
+
+#[tokio::test]
+async fn export_manager_migrate_queued_tasks_error() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let mid = utils::longid();
+    let model = Workflow::new()
+        .with_id(&mid)
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_act(Act::req(|act| act.with_id("act1")))
+        })
+        .with_step(|step| step.with_id("step2"));
+    manager.deploy(&model).unwrap();
+
+    let mut handle = engine.executor().start(&mid, &Vars::new()).unwrap();
+    let e = handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await
+        .unwrap();
+    manager.deploy(&model).unwrap();
+
+    let proc = engine.runtime().proc(handle.pid()).unwrap();
+    proc.sync(|| {
+        // the next tasks are queued and wait for the proc lock to run
+        engine
+            .executor()
+            .complete(&e.pid, &e.tid, &Vars::new())
+            .unwrap();
+        assert!(proc.queued() > 0);
+        assert!(manager.migrate(handle.pid(), 2, &HashMap::new()).is_err());
+
+        // the dry run does not change the proc
+        assert!(manager
+            .migrate_dry_run(handle.pid(), 2, &HashMap::new())
+            .is_ok());
+    });
+    assert_eq!(proc.info().ver, 1);
+}
+
+#[tokio::test]
+async fn export_manager_migrate_with_mapping() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let mid = utils::longid();
+    let model = Workflow::new().with_id(&mid).with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+    manager.deploy(&model).unwrap();
+
+    let mut handle = engine.executor().start(&mid, &Vars::new()).unwrap();
+    let e = handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await
+        .unwrap();
+
+    let model = Workflow::new().with_id(&mid).with_step(|step| {
+        step.with_id("step2")
+            .with_act(Act::req(|act| act.with_id("act2")))
+    });
+    manager.deploy(&model).unwrap();
+
+    let mapping = HashMap::from([
+        ("step1".to_string(), "step2".to_string()),
+        ("act1".to_string(), "act2".to_string()),
+    ]);
+    let report = manager.migrate(handle.pid(), 2, &mapping).unwrap();
+    assert!(report.changes.iter().any(|c| c.from == "act1"
+        && c.to == Some("act2".to_string())
+        && c.kind == MigrateKind::Mapped));
+
+    let proc = engine.runtime().proc(handle.pid()).unwrap();
+    let task = proc.task(&e.tid).unwrap();
+    assert_eq!(task.node().id(), "act2");
+    assert_eq!(task.state(), TaskState::Interrupt);
+}
+
+#[tokio::test]
+async fn export_manager_migrate_kind_mismatch_error() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let mid = utils::longid();
+    let model = Workflow::new().with_id(&mid).with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+    manager.deploy(&model).unwrap();
+
+    let mut handle = engine.executor().start(&mid, &Vars::new()).unwrap();
+    handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await
+        .unwrap();
+    manager.deploy(&model).unwrap();
+
+    let mapping = HashMap::from([("act1".to_string(), "step1".to_string())]);
+    assert!(manager.migrate(handle.pid(), 2, &mapping).is_err());
+}
+
+#[tokio::test]
+async fn export_manager_migrate_proc_not_found() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    assert!(manager.migrate("not_exists", 1, &HashMap::new()).is_err());
+}
+
+#[tokio::test]
+async fn export_manager_procs_get_one() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let model = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+
+    let rt = engine.runtime();
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    let proc = rt.create_proc(&utils::longid(), &model);
+    engine.channel().on_start(move |_| s1.close());
+    rt.launch(&proc);
+    sig.recv().await;
+
+    assert_eq!(manager.procs(10).unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn export_manager_procs_get_many() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let model = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+
+    let rt = engine.runtime();
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    let count = Arc::new(Mutex::new(0));
+    engine.channel().on_start(move |_e| {
+        println!("message:{_e:?}");
+        let mut count = count.lock().unwrap();
+        *count += 1;
+
+        if *count == 5 {
+            s1.close();
+        }
+    });
+    for _ in 0..5 {
+        let proc = rt.create_proc(&utils::longid(), &model);
+        rt.launch(&proc);
+    }
+    sig.recv().await;
+    assert_eq!(manager.procs(10).unwrap().len(), 5);
+}
+
+#[tokio::test]
+async fn export_manager_proc_get_json() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let model = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+
+    let rt = engine.runtime();
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    engine.channel().on_start(move |_| s1.close());
+    let pid = utils::longid();
+    let proc = rt.create_proc(&pid, &model);
+    rt.launch(&proc);
+    sig.recv().await;
+
+    let info = manager.proc(&pid, "json").unwrap();
+    assert_eq!(info.id, pid);
+    assert_eq!(info.tasks.is_empty(), false);
+}
+
+#[tokio::test]
+async fn export_manager_proc_get_tree() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let model = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+
+    let rt = engine.runtime();
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    engine.channel().on_start(move |_| s1.close());
+    let pid = utils::longid();
+    let proc = rt.create_proc(&pid, &model);
+    rt.launch(&proc);
+    sig.recv().await;
+
+    let info = manager.proc(&pid, "tree").unwrap();
+    assert_eq!(info.id, pid);
+    assert_eq!(info.tasks.is_empty(), false);
+}
+
+#[tokio::test]
+async fn export_manager_tasks() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let model = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+
+    let rt = engine.runtime();
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    engine.channel().on_message(move |e| {
+        if e.is_key("act1") {
+            s1.close()
+        }
+    });
+    let pid = utils::longid();
+    let mut vars = Vars::new();
+    vars.insert("uid".to_string(), json!("u1"));
+    vars.insert("pid".to_string(), json!(pid));
+
+    rt.start(&model, &vars).unwrap();
+    sig.recv().await;
+
+    let tasks = manager.tasks(&pid, 10).unwrap();
+    assert_eq!(tasks.len(), 3); // 3 means the tasks with workflow step act
+}
+
+#[tokio::test]
+async fn export_manager_task_get() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let model = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+
+    let rt = engine.runtime();
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    engine.channel().on_message(move |e| {
+        if e.is_key("act1") {
+            s1.close()
+        }
+    });
+    let pid = utils::longid();
+    let mut vars = Vars::new();
+    vars.insert("uid".to_string(), json!("u1"));
+    vars.insert("pid".to_string(), json!(pid));
+
+    rt.start(&model, &vars).unwrap();
+    sig.recv().await;
+    let tasks = manager.tasks(&pid, 10).unwrap();
+    let mut result = true;
+    for task in tasks {
+        result &= manager.task(&pid, &task.id).is_ok();
+    }
+    assert_eq!(result, true);
+}
+
+#[tokio::test]
+async fn export_manager_histories_delete() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let model = Workflow::new().with_step(|step| step.with_id("step1"));
+
+    let rt = engine.runtime();
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    engine.channel().on_complete(move |_| s1.close());
+    let pid = utils::longid();
+    let mut vars = Vars::new();
+    vars.insert("pid".to_string(), json!(pid));
+    rt.start(&model, &vars).unwrap();
+    sig.recv().await;
+    sig.timeout(100).await;
+
+    assert_eq!(manager.histories(10).unwrap().len(), 0);
+    assert!(manager.history(&pid, "json").is_err());
+}
+
+#[tokio::test]
+async fn export_manager_histories_keep() {
+    let engine = Builder::new().history_mode(HistoryMode::Keep).build();
+    let manager = engine.manager();
+    let model = Workflow::new().with_step(|step| step.with_id("step1"));
+
+    let rt = engine.runtime();
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    engine.channel().on_complete(move |_| s1.close());
+    let pid = utils::longid();
+    let mut vars = Vars::new();
+    vars.insert("pid".to_string(), json!(pid));
+    rt.start(&model, &vars).unwrap();
+    sig.recv().await;
+    sig.timeout(100).await;
+
+    let histories = manager.histories(10).unwrap();
+    assert_eq!(histories.len(), 1);
+    assert_eq!(histories[0].id, pid);
+    assert_eq!(histories[0].state, TaskState::Completed.to_string());
+    assert_eq!(manager.procs(10).unwrap().len(), 1);
+    assert_eq!(manager.tasks(&pid, 10).unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn export_manager_histories_archive() {
+    let engine = Builder::new().history_mode(HistoryMode::Archive).build();
+    let manager = engine.manager();
+    let model = Workflow::new().with_step(|step| step.with_id("step1"));
+
+    let rt = engine.runtime();
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    engine.channel().on_complete(move |_| s1.close());
+    let pid = utils::longid();
+    let mut vars = Vars::new();
+    vars.insert("pid".to_string(), json!(pid));
+    rt.start(&model, &vars).unwrap();
+    sig.recv().await;
+    sig.timeout(100).await;
+
+    let histories = manager.histories(10).unwrap();
+    assert_eq!(histories.len(), 1);
+    assert_eq!(histories[0].id, pid);
+    assert_eq!(manager.procs(10).unwrap().len(), 0);
+    assert_eq!(manager.tasks(&pid, 10).unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn export_manager_history_get_json() {
+    let engine = Builder::new().history_mode(HistoryMode::Archive).build();
+    let manager = engine.manager();
+    let model = Workflow::new().with_step(|step| step.with_id("step1"));
+
+    let rt = engine.runtime();
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    engine.channel().on_complete(move |_| s1.close());
+    let pid = utils::longid();
+    let mut vars = Vars::new();
+    vars.insert("pid".to_string(), json!(pid));
+    rt.start(&model, &vars).unwrap();
+    sig.recv().await;
+    sig.timeout(100).await;
+
+    let info = manager.history(&pid, "json").unwrap();
+    assert_eq!(info.id, pid);
+    let tasks: Vec<TaskInfo> = serde_json::from_str(&info.tasks).unwrap();
+    assert_eq!(tasks.len(), 2);
+}
+
+#[tokio::test]
+async fn export_manager_history_get_tree() {
+    let engine = Builder::new().history_mode(HistoryMode::Keep).build();
+    let manager = engine.manager();
+    let model = Workflow::new().with_step(|step| step.with_id("step1"));
+
+    let rt = engine.runtime();
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    engine.channel().on_complete(move |_| s1.close());
+    let pid = utils::longid();
+    let mut vars = Vars::new();
+    vars.insert("pid".to_string(), json!(pid));
+    rt.start(&model, &vars).unwrap();
+    sig.recv().await;
+    sig.timeout(100).await;
+
+    let info = manager.history(&pid, "tree").unwrap();
+    assert_eq!(info.id, pid);
+    assert!(!info.tasks.is_empty());
+}
+
+#[tokio::test]
+async fn export_manager_history_not_completed_error() {
+    let engine = Builder::new().history_mode(HistoryMode::Keep).build();
+    let manager = engine.manager();
+    let model = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+
+    let rt = engine.runtime();
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    engine.channel().on_start(move |_| s1.close());
+    let pid = utils::longid();
+    let mut vars = Vars::new();
+    vars.insert("pid".to_string(), json!(pid));
+    rt.start(&model, &vars).unwrap();
+    sig.recv().await;
+
+    assert!(manager.history(&pid, "json").is_err());
+    assert_eq!(manager.histories(10).unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn export_manager_query_histories() {
+    let engine = Builder::new().history_mode(HistoryMode::Archive).build();
+    let manager = engine.manager();
+    let model = Workflow::new()
+        .with_id("m1")
+        .with_step(|step| step.with_id("step1"));
+
+    let rt = engine.runtime();
+    let sig = engine.signal(0);
+    let s1 = sig.clone();
+    engine.channel().on_complete(move |_| {
+        s1.update(|count| *count += 1);
+        if s1.data() == 3 {
+            s1.close();
+        }
+    });
+    for _ in 0..3 {
+        rt.start(&model, &Vars::new()).unwrap();
+    }
+    sig.recv().await;
+    sig.timeout(100).await;
+
+    let q = Query::new().push(Cond::and().push(Expr::eq("mid", "m1")));
+    assert_eq!(manager.query_histories(&q).unwrap().len(), 3);
+
+    let q = Query::new().push(Cond::and().push(Expr::eq("mid", "m1")));
+    let q = q.set_limit(2);
+    assert_eq!(manager.query_histories(&q).unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn export_executeor_start() {
+    let engine = Engine::new();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_id("step1"));
+
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    engine.channel().on_complete(move |_| s1.close());
+
+    engine.manager().deploy(&model).unwrap();
+
+    let pid = utils::longid();
+    let mut vars = Vars::new();
+    vars.insert("uid".to_string(), json!("u1"));
+    vars.insert("pid".to_string(), json!(pid));
+
+    let result = engine.executor().start(&model.id, &vars);
+    sig.recv().await;
+    assert_eq!(result.is_ok(), true);
+}
+
+#[tokio::test]
+async fn export_executor_start_handle_pid() {
+    let engine = Engine::new();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_id("step1"));
+    engine.manager().deploy(&model).unwrap();
+
+    let mut vars = Vars::new();
+    vars.insert("pid".to_string(), json!("pid1"));
+    let handle = engine.executor().start(&model.id, &vars).unwrap();
+    assert_eq!(handle.pid(), "pid1");
+    assert_eq!(handle.outputs().get::<String>("pid").unwrap(), "pid1");
+}
+
+#[tokio::test]
+async fn export_executor_start_handle_pid_duplicated() {
+    let engine = Engine::new();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_act(Act::req(|act| act.with_id("act1"))));
+    engine.manager().deploy(&model).unwrap();
+
+    let pid = utils::longid();
+    let vars = Vars::new().with("pid", pid.clone());
+    let mut handle = engine.executor().start(&model.id, &vars).unwrap();
+    let e = handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await
+        .unwrap();
+
+    // the second start is rejected and the first handle still watches the proc
+    assert!(engine.executor().start(&model.id, &vars).is_err());
+    engine
+        .executor()
+        .complete(&e.pid, &e.tid, &Vars::new())
+        .unwrap();
+    let ret = handle.wait().await.unwrap();
+    assert_eq!(ret.pid, pid);
+    assert_eq!(ret.state, MessageState::Completed);
+}
+
+#[tokio::test]
+async fn export_executor_start_with_schema() {
+    let engine = Engine::new();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_input_schema("days", |p| {
+            p.with_type(ParamType::Integer).with_required(true)
+        })
+        .with_step(|step| step.with_id("step1"));
+    engine.manager().deploy(&model).unwrap();
+
+    let result = engine
+        .executor()
+        .start(&model.id, &Vars::new().with("days", 3));
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn export_executor_start_with_schema_error() {
+    let engine = Engine::new();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_input_schema("days", |p| {
+            p.with_type(ParamType::Integer).with_required(true)
+        })
+        .with_step(|step| step.with_id("step1"));
+    engine.manager().deploy(&model).unwrap();
+
+    let err = engine.executor().start(&model.id, &Vars::new()).err();
+    assert_eq!(
+        err,
+        Some(ActError::Action("the input 'days' is required".to_string()))
+    );
+
+    let err = engine
+        .executor()
+        .start(&model.id, &Vars::new().with("days", "3"))
+        .err();
+    assert_eq!(
+        err,
+        Some(ActError::Action(
+            "the input 'days' expects type 'integer' but got '\"3\"'".to_string()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn export_executor_complete_with_outputs_schema() {
+    let engine = Engine::new();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_output("approved", json!(null))
+        .with_output_schema("approved", |p| {
+            p.with_type(ParamType::Boolean).with_required(true)
+        })
+        .with_step(|step| step.with_id("step1"));
+    engine.manager().deploy(&model).unwrap();
+
+    let handle = engine
+        .executor()
+        .start(&model.id, &Vars::new().with("approved", true))
+        .unwrap();
+    let ret = handle.wait().await.unwrap();
+    assert_eq!(ret.state, MessageState::Completed);
+    assert_eq!(ret.outputs.get::<bool>("approved"), Some(true));
+}
+
+#[tokio::test]
+async fn export_executor_complete_with_outputs_schema_error() {
+    let engine = Engine::new();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_output("approved", json!(null))
+        .with_output_schema("approved", |p| {
+            p.with_type(ParamType::Boolean).with_required(true)
+        })
+        .with_step(|step| step.with_id("step1"));
+    engine.manager().deploy(&model).unwrap();
+
+    let handle = engine
+        .executor()
+        .start(&model.id, &Vars::new().with("approved", "yes"))
+        .unwrap();
+    let ret = handle.wait().await.unwrap();
+    assert_eq!(ret.state, MessageState::Error);
+    let err = ret.err.unwrap();
+    assert_eq!(err.ecode, utils::consts::ERR_OUTPUTS);
+    assert_eq!(
+        err.message,
+        "the output 'approved' expects type 'boolean' but got '\"yes\"'"
+    );
+}
+
+#[tokio::test]
+async fn export_manager_model_schema() {
+    let engine = Engine::new();
+    let manager = engine.manager();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_input_schema("days", |p| p.with_type(ParamType::Integer))
+        .with_step(|step| step.with_id("step1"));
+    manager.deploy(&model).unwrap();
+
+    let info = manager.model(&model.id, None, "text").unwrap();
+    assert_eq!(info.schema, model.schema);
 }
 
 #[tokio::test]
-async fn export_manager_procs_get_one() {
+async fn export_executor_start_with_ver() {
     let engine = Engine::new();
     let manager = engine.manager();
-    let model = Workflow::new().with_step(|step| {
+    let mut model = Workflow::new().with_id(&utils::longid()).with_step(|step| {
         step.with_id("step1")
             .with_act(Act::req(|act| act.with_id("act1")))
     });
+    manager.deploy(&model).unwrap();
+    model.name = "v2".to_string();
+    manager.deploy(&model).unwrap();
 
-    let rt = engine.runtime();
-    let sig = engine.signal(());
-    let s1 = sig.clone();
-    let proc = rt.create_proc(&utils::longid(), &model);
-    engine.channel().on_start(move |_| s1.close());
-    rt.launch(&proc);
-    sig.recv().await;
-
-    assert_eq!(manager.procs(10).unwrap().len(), 1);
+    let mut handle = engine
+        .executor()
+        .start(&model.id, &Vars::new().with("ver", 1))
+        .unwrap();
+    handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await
+        .unwrap();
+    let proc = engine.runtime().proc(handle.pid()).unwrap();
+    assert_eq!(proc.info().ver, 1);
+    assert_eq!(proc.info().name, "");
+
+    let mut handle = engine.executor().start(&model.id, &Vars::new()).unwrap();
+    handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await
+        .unwrap();
+    let proc = engine.runtime().proc(handle.pid()).unwrap();
+    assert_eq!(proc.info().ver, 2);
+    assert_eq!(proc.info().name, "v2");
 }
 
 #[tokio::test]
-async fn export_manager_procs_get_many() {
+async fn export_executor_start_with_ver_not_found() {
     let engine = Engine::new();
-    let manager = engine.manager();
-    let model = Workflow::new().with_step(|step| {
-        step.with_id("step1")
-            .with_act(Act::req(|act| act.with_id("act1")))
-    });
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_id("step1"));
+    engine.manager().deploy(&model).unwrap();
 
-    let rt = engine.runtime();
-    let sig = engine.signal(());
-    let s1 = sig.clone();
-    let count = Arc::new(Mutex::new(0));
-    engine.channel().on_start(move |_e| {
-        println!("message:{_e:?}");
-        let mut count = count.lock().unwrap();
-        *count += 1;
+    let ret = engine
+        .executor()
+        .start(&model.id, &Vars::new().with("ver", 3));
+    assert!(ret.is_err());
+}
 
-        if *count == 5 {
-            s1.close();
-        }
-    });
-    for _ in 0..5 {
-        let proc = rt.create_proc(&utils::longid(), &model);
-        rt.launch(&proc);
-    }
-    sig.recv().await;
-    assert_eq!(manager.procs(10).unwrap().len(), 5);
+#[tokio::test]
+async fn export_executor_start_handle_wait() {
+    let engine = Engine::new();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_input("a", json!(10))
+        .with_setup(|setup| setup.add(Act::expose(Vars::new().with("a", ()))));
+    engine.manager().deploy(&model).unwrap();
+
+    let handle = engine.executor().start(&model.id, &Vars::new()).unwrap();
+    let result = handle.wait().await.unwrap();
+    assert_eq!(result.pid, handle.pid());
+    assert_eq!(result.state, MessageState::Completed);
+    assert_eq!(result.outputs.get::<i64>("a").unwrap(), 10);
+    assert!(result.err.is_none());
 }
 
 #[tokio::test]
-async fn export_manager_proc_get_json() {
+async fn export_executor_start_handle_wait_error() {
     let engine = Engine::new();
-    let manager = engine.manager();
-    let model = Workflow::new().with_step(|step| {
+    let model = Workflow::new().with_id(&utils::longid()).with_step(|step| {
         step.with_id("step1")
             .with_act(Act::req(|act| act.with_id("act1")))
     });
+    engine.manager().deploy(&model).unwrap();
 
-    let rt = engine.runtime();
-    let sig = engine.signal(());
-    let s1 = sig.clone();
-    engine.channel().on_start(move |_| s1.close());
-    let pid = utils::longid();
-    let proc = rt.create_proc(&pid, &model);
-    rt.launch(&proc);
-    sig.recv().await;
+    let executor = engine.executor();
+    let mut handle = executor.start(&model.id, &Vars::new()).unwrap();
+    let e = handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await
+        .unwrap();
+    let mut vars = Vars::new();
+    vars.insert("error".to_string(), json!({ "ecode": "code_1"}));
+    executor.error(&e.pid, &e.tid, &vars).unwrap();
 
-    let info = manager.proc(&pid, "json").unwrap();
-    assert_eq!(info.id, pid);
-    assert_eq!(info.tasks.is_empty(), false);
+    let result = handle.wait().await.unwrap();
+    assert_eq!(result.state, MessageState::Error);
+    assert_eq!(result.err.unwrap().ecode, "code_1");
 }
 
 #[tokio::test]
-async fn export_manager_proc_get_tree() {
+async fn export_executor_start_handle_next_message() {
     let engine = Engine::new();
-    let manager = engine.manager();
-    let model = Workflow::new().with_step(|step| {
+    let model = Workflow::new().with_id(&utils::longid()).with_step(|step| {
         step.with_id("step1")
             .with_act(Act::req(|act| act.with_id("act1")))
+            .with_act(Act::req(|act| act.with_id("act2")))
     });
+    engine.manager().deploy(&model).unwrap();
 
-    let rt = engine.runtime();
-    let sig = engine.signal(());
-    let s1 = sig.clone();
-    engine.channel().on_start(move |_| s1.close());
-    let pid = utils::longid();
-    let proc = rt.create_proc(&pid, &model);
-    rt.launch(&proc);
-    sig.recv().await;
+    let executor = engine.executor();
+    let mut handle = executor.start(&model.id, &Vars::new()).unwrap();
+    let mut keys = Vec::new();
+    while let Some(e) = handle.next_message(|m| m.is_state("created")).await {
+        if e.is_type("req") {
+            keys.push(e.key.clone());
+            executor.complete(&e.pid, &e.tid, &Vars::new()).unwrap();
+        }
+    }
+    assert_eq!(keys, ["act1", "act2"]);
 
-    let info = manager.proc(&pid, "tree").unwrap();
-    assert_eq!(info.id, pid);
-    assert_eq!(info.tasks.is_empty(), false);
+    let result = handle.wait().await.unwrap();
+    assert_eq!(result.state, MessageState::Completed);
 }
 
 #[tokio::test]
-async fn export_manager_tasks() {
+async fn export_executor_signal() {
     let engine = Engine::new();
-    let manager = engine.manager();
-    let model = Workflow::new().with_step(|step| {
-        step.with_id("step1")
-            .with_act(Act::req(|act| act.with_id("act1")))
-    });
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_input("order_id", json!(null))
+        .with_step(|step| {
+            step.with_id("step1").with_act(Act::wait(|act| {
+                act.with_id("wait1")
+                    .with_signal("order_paid")
+                    .with_key(r#"order:${ $("order_id") }"#)
+            }))
+        });
+    engine.manager().deploy(&model).unwrap();
 
-    let rt = engine.runtime();
-    let sig = engine.signal(());
-    let s1 = sig.clone();
-    engine.channel().on_message(move |e| {
-        if e.is_key("act1") {
-            s1.close()
-        }
-    });
-    let pid = utils::longid();
-    let mut vars = Vars::new();
-    vars.insert("uid".to_string(), json!("u1"));
-    vars.insert("pid".to_string(), json!(pid));
+    // two procs wait for the same order and one for another
+    let executor = engine.executor();
+    let mut handles = Vec::new();
+    for order_id in ["o1", "o1", "o2"] {
+        let mut handle = executor
+            .start(&model.id, &Vars::new().with("order_id", order_id))
+            .unwrap();
+        handle
+            .next_message(|m| m.is_key("wait1") && m.is_state("created"))
+            .await
+            .unwrap();
+        handles.push(handle);
+    }
 
-    rt.start(&model, &vars).unwrap();
-    sig.recv().await;
+    let result = executor
+        .signal(
+            "order_paid",
+            "order:o1",
+            None,
+            &Vars::new().with("paid", true),
+        )
+        .unwrap();
+    assert_eq!(result.outputs().get::<usize>("count").unwrap(), 2);
+    for handle in &handles[0..2] {
+        let result = handle.wait().await.unwrap();
+        assert_eq!(result.state, MessageState::Completed);
+    }
 
-    let tasks = manager.tasks(&pid, 10).unwrap();
-    assert_eq!(tasks.len(), 3); // 3 means the tasks with workflow step act
+    let proc = engine.runtime().proc(handles[2].pid()).unwrap();
+    assert!(proc.state().is_running());
 }
 
 #[tokio::test]
-async fn export_manager_task_get() {
+async fn export_executor_signal_no_waiter() {
     let engine = Engine::new();
-    let manager = engine.manager();
-    let model = Workflow::new().with_step(|step| {
-        step.with_id("step1")
-            .with_act(Act::req(|act| act.with_id("act1")))
-    });
+    let result = engine
+        .executor()
+        .signal("order_paid", "order:o1", None, &Vars::new())
+        .unwrap();
+    assert_eq!(result.outputs().get::<usize>("count").unwrap(), 0);
+}
 
-    let rt = engine.runtime();
-    let sig = engine.signal(());
-    let s1 = sig.clone();
+#[tokio::test]
+async fn export_executor_signal_start() {
+    let engine = Engine::new();
+    let model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_step(|step| step.with_act(Act::req(|act| act.with_id("act1"))));
+    engine.manager().deploy(&model).unwrap();
+
+    let sig = engine.signal(String::new());
+    let s = sig.clone();
     engine.channel().on_message(move |e| {
-        if e.is_key("act1") {
-            s1.close()
+        if e.is_key("act1") && e.is_state("created") {
+            s.send(e.pid.clone());
         }
     });
-    let pid = utils::longid();
-    let mut vars = Vars::new();
-    vars.insert("uid".to_string(), json!("u1"));
-    vars.insert("pid".to_string(), json!(pid));
 
-    rt.start(&model, &vars).unwrap();
-    sig.recv().await;
-    let tasks = manager.tasks(&pid, 10).unwrap();
-    let mut result = true;
-    for task in tasks {
-        result &= manager.task(&pid, &task.id).is_ok();
-    }
-    assert_eq!(result, true);
+    let executor = engine.executor();
+    let result = executor
+        .signal(
+            "order_paid",
+            "order:o1",
+            Some(&model.id),
+            &Vars::new().with("paid", true),
+        )
+        .unwrap();
+    let pid = sig.recv().await;
+    assert_eq!(result.outputs().get::<String>("pid").unwrap(), pid);
+    let proc = engine.runtime().proc(&pid).unwrap();
+    assert_eq!(proc.model().id, model.id);
+    assert!(proc.model().inputs.get::<bool>("paid").unwrap());
 }
 
 #[tokio::test]
-async fn export_executeor_start() {
+async fn export_executor_start_handles_many() {
     let engine = Engine::new();
     let model = Workflow::new()
         .with_id(&utils::longid())
         .with_step(|step| step.with_id("step1"));
-
-    let sig = engine.signal(());
-    let s1 = sig.clone();
-    engine.channel().on_complete(move |_| s1.close());
-
     engine.manager().deploy(&model).unwrap();
 
-    let pid = utils::longid();
-    let mut vars = Vars::new();
-    vars.insert("uid".to_string(), json!("u1"));
-    vars.insert("pid".to_string(), json!(pid));
-
-    let result = engine.executor().start(&model.id, &vars);
-    sig.recv().await;
-    assert_eq!(result.is_ok(), true);
+    let mut handles = Vec::new();
+    for _ in 0..5 {
+        handles.push(engine.executor().start(&model.id, &Vars::new()).unwrap());
+    }
+    for handle in handles {
+        let result = handle.wait().await.unwrap();
+        assert_eq!(result.pid, handle.pid());
+        assert_eq!(result.state, MessageState::Completed);
+    }
 }
 
 #[tokio::test]
@@ -1224,6 +2452,152 @@ async fn export_message_resend_error_messages() {
     assert_eq!(message.retry_times, 0);
 }
 
+#[tokio::test]
+async fn export_channel_subscribe() {
+    let engine = Engine::new();
+    let model = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+
+    let mut stream = engine.channel().subscribe();
+    engine.runtime().start(&model, &Vars::new()).unwrap();
+
+    let mut keys = Vec::new();
+    while let Some(e) = stream.next().await {
+        keys.push(e.key.clone());
+        if e.is_key("act1") {
+            break;
+        }
+    }
+    assert!(keys.contains(&"act1".to_string()));
+}
+
+#[tokio::test]
+async fn export_channel_subscribe_with_options() {
+    let engine = Engine::new();
+    let chan = engine.channel_with_options(&ChannelOptions {
+        key: "act*".to_string(),
+        ..Default::default()
+    });
+    let mut stream = chan.subscribe();
+
+    for key in ["step1", "act1"] {
+        let msg = Message {
+            id: utils::longid(),
+            key: key.to_string(),
+            ..Message::default()
+        };
+        engine.runtime().emitter().emit_message(&msg);
+    }
+
+    let e = stream.recv().await.unwrap();
+    assert_eq!(e.key, "act1");
+}
+
+#[tokio::test]
+async fn export_channel_subscribe_ack() {
+    let engine = Engine::new();
+    let chan = engine.channel_with_options(&ChannelOptions {
+        id: "my_emit_id".to_string(),
+        ack: true,
+        ..Default::default()
+    });
+    let mut stream = chan.subscribe();
+
+    let msg = Message {
+        id: utils::longid(),
+        ..Message::default()
+    };
+    engine.runtime().emitter().emit_message(&msg);
+    let e = stream.next().await.unwrap();
+    let store = engine.runtime().cache().store();
+    let message = store.messages().find(&e.id).unwrap();
+    assert_eq!(message.status, data::MessageStatus::Created);
+
+    stream.ack(&e.id).unwrap();
+    let message = store.messages().find(&e.id).unwrap();
+    assert_eq!(message.status, data::MessageStatus::Acked);
+}
+
+#[tokio::test]
+async fn export_channel_subscribe_close() {
+    let engine = Engine::new();
+    let mut stream = engine.channel().subscribe();
+    stream.close();
+
+    let msg = Message {
+        id: utils::longid(),
+        ..Message::default()
+    };
+    engine.runtime().emitter().emit_message(&msg);
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn export_channel_subscribe_order() {
+    let engine = Engine::new();
+    let mut stream = engine.channel().subscribe();
+
+    let ids = (0..100).map(|_| utils::longid()).collect::<Vec<_>>();
+    for id in &ids {
+        let msg = Message {
+            id: id.clone(),
+            ..Message::default()
+        };
+        engine.runtime().emitter().emit_message(&msg);
+    }
+
+    let mut received = Vec::new();
+    while received.len() < ids.len() {
+        received.push(stream.next().await.unwrap().id.clone());
+    }
+    assert_eq!(received, ids);
+}
+
+#[tokio::test]
+async fn export_channel_subscribe_full() {
+    let engine = Engine::new();
+    let mut stream = engine.channel().subscribe();
+
+    // emit more messages than the stream can hold before reading it
+    let ids = (0..3000).map(|_| utils::longid()).collect::<Vec<_>>();
+    for id in &ids {
+        let msg = Message {
+            id: id.clone(),
+            ..Message::default()
+        };
+        engine.runtime().emitter().emit_message(&msg);
+    }
+
+    let mut received = Vec::new();
+    while received.len() < ids.len() {
+        received.push(stream.next().await.unwrap().id.clone());
+    }
+    assert_eq!(received, ids);
+}
+
+#[tokio::test]
+async fn export_channel_subscribe_many() {
+    let engine = Engine::new();
+    let chan = engine.channel();
+    let mut stream1 = chan.subscribe();
+    let mut stream2 = chan.subscribe();
+
+    // close one stream does not affect the others
+    let mut stream3 = chan.subscribe();
+    stream3.close();
+
+    let msg = Message {
+        id: utils::longid(),
+        ..Message::default()
+    };
+    engine.runtime().emitter().emit_message(&msg);
+    assert_eq!(stream1.next().await.unwrap().id, msg.id);
+    assert_eq!(stream2.next().await.unwrap().id, msg.id);
+    assert!(stream3.next().await.is_none());
+}
+
 #[derive(Debug, Default, Clone)]
 struct TestPlugin;
 
@@ -1244,3 +2618,26 @@ mod test_module {
         }
     }
 }
+
+// 2024-01-01T00:00:00Z
+const TRIGGER_START: i64 = 1704067200000;
+const HOUR: i64 = 3600 * 1000;
+
+/// deploy a model with an hourly trigger which was last fired at `TRIGGER_START`
+fn deploy_trigger_model(catch_up: CatchUp) -> (Engine, String) {
+    let engine = Engine::new();
+    let mut model = Workflow::new()
+        .with_id(&utils::longid())
+        .with_trigger(|t| t.with_cron("0 * * * *").with_input("kind", json!("hourly")))
+        .with_step(|step| step.with_id("step1"));
+    model.triggers[0].catch_up = catch_up;
+    engine.manager().deploy(&model).unwrap();
+
+    let store = engine.runtime().cache().store();
+    let mut trigger = store.model_triggers(&model.id).unwrap().remove(0);
+    trigger.last_time = TRIGGER_START;
+    trigger.next_time = TRIGGER_START + HOUR;
+    store.triggers().update(&trigger).unwrap();
+
+    (engine, model.id)
+}
@@ -0,0 +1,301 @@
+use crate::{
+    sch::Runtime,
+    utils::consts::{self, ERR_HANDLER, ERR_HANDLER_TIMEOUT},
+    ActError, Action, Error, Message, MessageState, Result, TimeoutLimit, Vars,
+};
+use async_trait::async_trait;
+use globset::{Glob, GlobMatcher};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex, RwLock},
+    time::Duration,
+};
+use tokio::sync::Semaphore;
+use tracing::{debug, error};
+
+#[cfg(test)]
+mod tests;
+
+/// the max running handlers of one registration by default
+const DEFAULT_CONCURRENCY: usize = 16;
+
+/// The result of the [`ActHandler`] which is applied to the task
+#[derive(Debug, Clone)]
+pub enum ActOutcome {
+    /// complete the task with the outputs
+    Complete(Vars),
+
+    /// skip the task
+    Skip,
+
+    /// fail the task with the error, which can be caught by the `catches` with its ecode,
+    /// the empty ecode is set to `handler_error`
+    Error(Error),
+}
+
+/// Act handler trait to run the `!req` acts in process
+///
+/// the handler is invoked with the `created` message of the matched act,
+/// and its result is applied as complete, error or skip without subscribing the messages.
+/// the pending acts in the store are handled when the handler is registered, so they are
+/// handled again after the engine is restarted.
+///
+/// the message is still emitted to the channels, so the channel subscribers matching the
+/// same key should not also complete the act, or the later action fails since the act is
+/// already done
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use acts::{ActHandler, ActOutcome, Engine, HandlerOptions, Message, Result, Vars};
+///
+/// #[derive(Clone)]
+/// struct ApproveHandler;
+///
+/// #[async_trait::async_trait]
+/// impl ActHandler for ApproveHandler {
+///     async fn handle(&self, message: &Message) -> Result<ActOutcome> {
+///         let amount = message.inputs.get::<i64>("amount").unwrap_or_default();
+///         Ok(ActOutcome::Complete(Vars::new().with("approved", amount < 1000)))
+///     }
+/// }
+///
+/// let engine = Engine::new();
+/// let options = HandlerOptions::new().with_key("approve*").with_timeout("30s");
+/// engine
+///     .extender()
+///     .register_handler(&options, &ApproveHandler)
+///     .unwrap();
+/// ```
+#[async_trait]
+pub trait ActHandler: Send + Sync {
+    async fn handle(&self, message: &Message) -> Result<ActOutcome>;
+}
+
+/// The options to register the [`ActHandler`]
+#[derive(Debug, Clone)]
+pub struct HandlerOptions {
+    /// use the glob pattern to match the act key
+    /// eg. approve*
+    pub key: String,
+
+    /// use the glob pattern to match the act tag or model tag
+    /// eg. *tag1*
+    pub tag: String,
+
+    /// the max handlers running at the same time
+    pub concurrency: usize,
+
+    /// the max time to run the handler, the task fails with `handler_timeout` when exceeded
+    pub timeout: Option<TimeoutLimit>,
+}
+
+impl Default for HandlerOptions {
+    fn default() -> Self {
+        Self {
+            key: "*".to_string(),
+            tag: "*".to_string(),
+            concurrency: DEFAULT_CONCURRENCY,
+            timeout: None,
+        }
+    }
+}
+
+impl HandlerOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_key(mut self, key: &str) -> Self {
+        self.key = key.to_string();
+        self
+    }
+
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tag = tag.to_string();
+        self
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    pub fn with_timeout(mut self, v: &str) -> Self {
+        self.timeout = Some(
+            TimeoutLimit::parse(v)
+                .unwrap_or_else(|_| panic!("failed with error format '{v}' for 'timeout' ")),
+        );
+        self
+    }
+}
+
+struct HandlerEntry {
+    key: GlobMatcher,
+    tag: GlobMatcher,
+    timeout: Option<TimeoutLimit>,
+    permits: Arc<Semaphore>,
+    handler: Arc<dyn ActHandler>,
+}
+
+/// The registered act handlers, the first matched one handles the message
+#[derive(Default)]
+pub struct Handlers {
+    entries: RwLock<Vec<Arc<HandlerEntry>>>,
+
+    /// the tids of the running handlers to avoid handling one task twice
+    running: Mutex<HashSet<String>>,
+}
+
+impl std::fmt::Debug for Handlers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Handlers")
+            .field("count", &self.entries.read().unwrap().len())
+            .finish()
+    }
+}
+
+impl Handlers {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn register(&self, options: &HandlerOptions, handler: Arc<dyn ActHandler>) -> Result<()> {
+        if options.concurrency == 0 {
+            return Err(ActError::Runtime(
+                "the handler's 'concurrency' must be greater than 0".to_string(),
+            ));
+        }
+        let glob = |pat: &str| {
+            Glob::new(pat)
+                .map(|g| g.compile_matcher())
+                .map_err(|err| ActError::Runtime(format!("the handler pattern '{pat}': {err}")))
+        };
+        let entry = HandlerEntry {
+            key: glob(&options.key)?,
+            tag: glob(&options.tag)?,
+            timeout: options.timeout.clone(),
+            permits: Arc::new(Semaphore::new(options.concurrency)),
+            handler,
+        };
+        self.entries.write().unwrap().push(Arc::new(entry));
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    /// check if any handler matches the act key and the act tag or model tag
+    pub fn is_match(&self, key: &str, tag: &str, model_tag: &str) -> bool {
+        self.entries
+            .read()
+            .unwrap()
+            .iter()
+            .any(|entry| entry.is_match(key, tag, model_tag))
+    }
+
+    /// run the matched handler for the created `req` message in background
+    /// return true if the message is handled or it is still being handled
+    pub fn dispatch(&self, rt: &Arc<Runtime>, message: &Message) -> bool {
+        if message.r#type != consts::ACT_TYPE_REQ || message.state != MessageState::Created {
+            return false;
+        }
+        let entry = match self
+            .entries
+            .read()
+            .unwrap()
+            .iter()
+            .find(|entry| entry.matches(message))
+        {
+            Some(entry) => entry.clone(),
+            None => return false,
+        };
+        if !self.running.lock().unwrap().insert(message.tid.clone()) {
+            return true;
+        }
+
+        debug!("handlers.dispatch: key={} tid={}", message.key, message.tid);
+        let rt = rt.clone();
+        let message = message.clone();
+        tokio::spawn(async move {
+            let outcome = entry.run(&message).await;
+            let action = match outcome {
+                ActOutcome::Complete(outputs) => {
+                    Action::new(&message.pid, &message.tid, consts::EVT_NEXT, &outputs)
+                }
+                ActOutcome::Skip => {
+                    Action::new(&message.pid, &message.tid, consts::EVT_SKIP, &Vars::new())
+                }
+                ActOutcome::Error(mut err) => {
+                    // the error action requires an ecode
+                    if err.ecode.is_empty() {
+                        err.ecode = ERR_HANDLER.to_string();
+                    }
+                    Action::new(
+                        &message.pid,
+                        &message.tid,
+                        consts::EVT_ERR,
+                        &Vars::new().with(consts::ACT_ERR_KEY, err),
+                    )
+                }
+            };
+            // the action takes the proc lock, so do it in the blocking thread
+            let ret = {
+                let rt = rt.clone();
+                tokio::task::spawn_blocking(move || rt.do_action(&action)).await
+            };
+            match ret {
+                Ok(Err(err)) => error!("handlers.dispatch: tid={} {err}", message.tid),
+                Err(err) => error!("handlers.dispatch: tid={} {err}", message.tid),
+                _ => {}
+            }
+            rt.handlers().running.lock().unwrap().remove(&message.tid);
+        });
+
+        true
+    }
+}
+
+impl HandlerEntry {
+    fn matches(&self, message: &Message) -> bool {
+        self.is_match(&message.key, &message.tag, &message.model.tag)
+    }
+
+    fn is_match(&self, key: &str, tag: &str, model_tag: &str) -> bool {
+        self.key.is_match(key) && (self.tag.is_match(tag) || self.tag.is_match(model_tag))
+    }
+
+    async fn run(&self, message: &Message) -> ActOutcome {
+        // the semaphore is never closed
+        let _permit = self.permits.acquire().await.unwrap();
+
+        // run in another task to catch the panic and abort it when timeout
+        let handler = self.handler.clone();
+        let msg = message.clone();
+        let mut task = tokio::spawn(async move { handler.handle(&msg).await });
+        let result = match &self.timeout {
+            Some(timeout) => {
+                let duration = Duration::from_millis(timeout.as_millis().max(0) as u64);
+                match tokio::time::timeout(duration, &mut task).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        task.abort();
+                        return ActOutcome::Error(Error::new(
+                            &format!("the handler exceeds the time limit of {timeout}"),
+                            ERR_HANDLER_TIMEOUT,
+                        ));
+                    }
+                }
+            }
+            None => task.await,
+        };
+
+        match result {
+            Ok(Ok(outcome)) => outcome,
+            Ok(Err(err)) => ActOutcome::Error(err.into()),
+            Err(err) => ActOutcome::Error(Error::new(&format!("the handler panics: {err}"), "")),
+        }
+    }
+}
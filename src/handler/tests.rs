@@ -0,0 +1,362 @@
+use crate::{
+    store::db::MemStore, utils::consts, Act, ActError, ActHandler, ActOutcome, Builder, Engine,
+    Error, HandlerOptions, HistoryMode, Message, MessageState, ProcHandle, Req, Result, StmtBuild,
+    Vars, Workflow,
+};
+use serde_json::json;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+#[tokio::test]
+async fn handler_register() {
+    let engine = Engine::new();
+    let extender = engine.extender();
+    extender
+        .register_handler(&HandlerOptions::new(), &TestHandler::complete())
+        .unwrap();
+    assert_eq!(engine.runtime().handlers().len(), 1);
+}
+
+#[tokio::test]
+async fn handler_register_zero_concurrency() {
+    let engine = Engine::new();
+    let ret = engine.extender().register_handler(
+        &HandlerOptions::new().with_concurrency(0),
+        &TestHandler::complete(),
+    );
+    assert!(ret.is_err());
+}
+
+#[tokio::test]
+async fn handler_complete() {
+    let engine = Engine::new();
+    let handler = TestHandler::complete();
+    engine
+        .extender()
+        .register_handler(&HandlerOptions::new().with_key("act*"), &handler)
+        .unwrap();
+
+    let mut handle = start(&engine, &["act1"]);
+    let e = handle
+        .next_message(|m| m.is_key("act1") && m.is_state("completed"))
+        .await
+        .unwrap();
+    assert_eq!(e.outputs.get::<bool>("approved"), Some(true));
+
+    let ret = handle.wait().await.unwrap();
+    assert_eq!(ret.state, MessageState::Completed);
+    assert_eq!(*handler.keys.lock().unwrap(), ["act1"]);
+}
+
+#[tokio::test]
+async fn handler_message_emitted() {
+    let engine = Builder::new().history_mode(HistoryMode::Keep).build();
+    engine
+        .extender()
+        .register_handler(&HandlerOptions::new(), &TestHandler::complete())
+        .unwrap();
+
+    let mut handle = start(&engine, &["act1"]);
+    let e = handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await;
+    assert!(e.is_some());
+    handle.wait().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // the workflow, step and act tasks are kept in history
+    let tasks = engine.manager().tasks(handle.pid(), 10).unwrap();
+    assert_eq!(tasks.len(), 3);
+    assert!(tasks.iter().all(|t| t.state == "completed"));
+}
+
+#[tokio::test]
+async fn handler_error() {
+    let engine = Engine::new();
+    engine
+        .extender()
+        .register_handler(&HandlerOptions::new(), &TestHandler::error("err1"))
+        .unwrap();
+
+    let handle = start(&engine, &["act1"]);
+    let ret = handle.wait().await.unwrap();
+    assert_eq!(ret.state, MessageState::Error);
+    assert_eq!(ret.err.unwrap().ecode, "err1");
+}
+
+#[tokio::test]
+async fn handler_error_catch() {
+    let engine = Engine::new();
+    engine
+        .extender()
+        .register_handler(&HandlerOptions::new(), &TestHandler::error("err1"))
+        .unwrap();
+
+    let workflow = Workflow::new().with_id("m1").with_step(|step| {
+        step.with_id("step1")
+            .with_catch(|c| {
+                c.with_err("err1")
+                    .with_then(|stmts| stmts.add(Act::msg(|act| act.with_id("msg1"))))
+            })
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+    engine.manager().deploy(&workflow).unwrap();
+    let mut handle = engine.executor().start("m1", &Vars::new()).unwrap();
+    let e = handle.next_message(|m| m.is_key("msg1")).await;
+    assert!(e.is_some());
+
+    let ret = handle.wait().await.unwrap();
+    assert_eq!(ret.state, MessageState::Completed);
+}
+
+#[tokio::test]
+async fn handler_return_err() {
+    let engine = Engine::new();
+    engine
+        .extender()
+        .register_handler(&HandlerOptions::new(), &TestHandler::complete())
+        .unwrap();
+
+    let workflow = Workflow::new().with_id("m1").with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::req(|act| act.with_id("act1").with_input("fail", true)))
+    });
+    engine.manager().deploy(&workflow).unwrap();
+    let handle = engine.executor().start("m1", &Vars::new()).unwrap();
+    let ret = handle.wait().await.unwrap();
+    assert_eq!(ret.state, MessageState::Error);
+    let err = ret.err.unwrap();
+    assert_eq!(err.ecode, consts::ERR_HANDLER);
+    assert_eq!(err.message, "failed");
+}
+
+#[tokio::test]
+async fn handler_skip() {
+    let engine = Engine::new();
+    engine
+        .extender()
+        .register_handler(&HandlerOptions::new(), &TestHandler::skip())
+        .unwrap();
+
+    let mut handle = start(&engine, &["act1"]);
+    let e = handle
+        .next_message(|m| m.is_key("act1") && m.is_state("skipped"))
+        .await;
+    assert!(e.is_some());
+    handle.wait().await.unwrap();
+}
+
+#[tokio::test]
+async fn handler_timeout() {
+    let engine = Engine::new();
+    let handler = TestHandler::complete().with_delay(1000);
+    engine
+        .extender()
+        .register_handler(&HandlerOptions::new().with_timeout("100ms"), &handler)
+        .unwrap();
+
+    let handle = start(&engine, &["act1"]);
+    let ret = handle.wait().await.unwrap();
+    assert_eq!(ret.state, MessageState::Error);
+    assert_eq!(ret.err.unwrap().ecode, consts::ERR_HANDLER_TIMEOUT);
+}
+
+#[tokio::test]
+async fn handler_concurrency() {
+    let engine = Engine::new();
+    let handler = TestHandler::complete().with_delay(50);
+    engine
+        .extender()
+        .register_handler(&HandlerOptions::new().with_concurrency(1), &handler)
+        .unwrap();
+
+    let handle = start(&engine, &["act1", "act2", "act3"]);
+    let ret = handle.wait().await.unwrap();
+    assert_eq!(ret.state, MessageState::Completed);
+    assert_eq!(handler.keys.lock().unwrap().len(), 3);
+    assert_eq!(handler.max_running.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn handler_not_matched() {
+    let engine = Engine::new();
+    let handler = TestHandler::complete();
+    engine
+        .extender()
+        .register_handler(&HandlerOptions::new().with_tag("tag1"), &handler)
+        .unwrap();
+
+    let mut handle = start(&engine, &["act1"]);
+    let e = handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await
+        .unwrap();
+    engine
+        .executor()
+        .complete(&e.pid, &e.tid, &Vars::new())
+        .unwrap();
+    handle.wait().await.unwrap();
+    assert!(handler.keys.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn handler_match_tag() {
+    let engine = Engine::new();
+    let handler = TestHandler::complete();
+    engine
+        .extender()
+        .register_handler(&HandlerOptions::new().with_tag("auto"), &handler)
+        .unwrap();
+
+    let workflow = Workflow::new().with_id("m1").with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::req(|act| act.with_id("act1").with_tag("auto")))
+            .with_act(Act::req(|act| act.with_id("act2")))
+    });
+    engine.manager().deploy(&workflow).unwrap();
+    let mut handle = engine.executor().start("m1", &Vars::new()).unwrap();
+    let e = handle
+        .next_message(|m| m.is_key("act2") && m.is_state("created"))
+        .await
+        .unwrap();
+    engine
+        .executor()
+        .complete(&e.pid, &e.tid, &Vars::new())
+        .unwrap();
+    handle.wait().await.unwrap();
+    assert_eq!(*handler.keys.lock().unwrap(), ["act1"]);
+}
+
+#[tokio::test]
+async fn handler_restore() {
+    let store = MemStore::new();
+    let engine = Builder::new().store(&store).build();
+    let mut handle = start(&engine, &["act1"]);
+    let e = handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await
+        .unwrap();
+    engine.close();
+
+    // rebuild the engine with the same store and register the handler after that
+    let engine = Builder::new().store(&store).build();
+    let sig = engine.signal(String::new());
+    let s = sig.clone();
+    engine.channel().on_complete(move |e| s.send(e.pid.clone()));
+    let handler = TestHandler::complete().with_delay(200);
+    engine
+        .extender()
+        .register_handler(&HandlerOptions::new(), &handler)
+        .unwrap();
+    // the task is still being handled, so it is not handled twice
+    engine
+        .extender()
+        .register_handler(&HandlerOptions::new(), &handler)
+        .unwrap();
+    let pid = sig.recv().await;
+    assert_eq!(pid, e.pid);
+    assert_eq!(*handler.keys.lock().unwrap(), ["act1"]);
+}
+
+#[tokio::test]
+async fn handler_restore_not_matched() {
+    let store = MemStore::new();
+    let engine = Builder::new().store(&store).build();
+    let mut handle = start(&engine, &["act1"]);
+    let e = handle
+        .next_message(|m| m.is_key("act1") && m.is_state("created"))
+        .await
+        .unwrap();
+    engine.close();
+
+    let engine = Builder::new().store(&store).build();
+    let handler = TestHandler::complete();
+    engine
+        .extender()
+        .register_handler(&HandlerOptions::new().with_key("act2"), &handler)
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    // the proc of the not matched task is not loaded
+    assert_eq!(engine.runtime().cache().count(), 0);
+
+    let task = engine.manager().task(&e.pid, &e.tid).unwrap();
+    assert_eq!(task.state, "interrupted");
+    assert!(handler.keys.lock().unwrap().is_empty());
+}
+
+fn start(engine: &Engine, keys: &[&str]) -> ProcHandle {
+    let mut workflow = Workflow::new()
+        .with_id("m1")
+        .with_step(|step| step.with_id("step1"));
+    let step = workflow.steps.first_mut().unwrap();
+    for key in keys {
+        step.acts.push(Act::Req(
+            Req::new().with_id(key).with_output("approved", json!(null)),
+        ));
+    }
+    engine.manager().deploy(&workflow).unwrap();
+    engine.executor().start("m1", &Vars::new()).unwrap()
+}
+
+#[derive(Clone)]
+struct TestHandler {
+    outcome: ActOutcome,
+    delay: u64,
+    keys: Arc<Mutex<Vec<String>>>,
+    running: Arc<AtomicUsize>,
+    max_running: Arc<AtomicUsize>,
+}
+
+impl TestHandler {
+    fn new(outcome: ActOutcome) -> Self {
+        Self {
+            outcome,
+            delay: 0,
+            keys: Arc::new(Mutex::new(Vec::new())),
+            running: Arc::new(AtomicUsize::new(0)),
+            max_running: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn complete() -> Self {
+        Self::new(ActOutcome::Complete(
+            Vars::new().with("approved", json!(true)),
+        ))
+    }
+
+    fn error(ecode: &str) -> Self {
+        Self::new(ActOutcome::Error(Error::new("handler error", ecode)))
+    }
+
+    fn skip() -> Self {
+        Self::new(ActOutcome::Skip)
+    }
+
+    fn with_delay(mut self, millis: u64) -> Self {
+        self.delay = millis;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl ActHandler for TestHandler {
+    async fn handle(&self, message: &Message) -> Result<ActOutcome> {
+        let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_running.fetch_max(running, Ordering::SeqCst);
+        if self.delay > 0 {
+            tokio::time::sleep(Duration::from_millis(self.delay)).await;
+        }
+        self.keys.lock().unwrap().push(message.key.clone());
+        self.running.fetch_sub(1, Ordering::SeqCst);
+
+        if message.inputs.get::<bool>("fail") == Some(true) {
+            return Err(ActError::Runtime("failed".to_string()));
+        }
+        Ok(self.outcome.clone())
+    }
+}
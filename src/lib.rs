@@ -11,6 +11,7 @@ mod env;
 mod error;
 mod event;
 mod export;
+mod handler;
 mod model;
 mod plugin;
 mod sch;
@@ -25,16 +26,19 @@ use std::sync::Arc;
 use std::sync::RwLock;
 
 pub use builder::Builder;
-pub use config::Config;
+pub use config::{Config, HistoryMode};
 pub use engine::Engine;
 pub use env::ActModule;
 pub use error::{ActError, Error};
 pub use event::{Action, Event, Message, MessageState};
-pub use export::{Channel, ChannelOptions, Executor, Extender, Manager};
+pub use export::{
+    Channel, ChannelOptions, Executor, Extender, Manager, MessageStream, ProcHandle, ProcResult,
+};
+pub use handler::{ActHandler, ActOutcome, HandlerOptions};
 pub use model::*;
 pub use plugin::ActPlugin;
 pub use signal::Signal;
-pub use store::{data, DbSet, Query, StoreAdapter};
+pub use store::{data, Cond, DbSet, Expr, Query, StoreAdapter};
 pub type Result<T> = std::result::Result<T, ActError>;
 
 pub(crate) use sch::{Context, NodeKind};
@@ -2,23 +2,30 @@ mod block;
 mod call;
 mod chain;
 mod cmd;
+mod delay;
 mod each;
 mod r#if;
+mod items;
+mod r#loop;
 mod msg;
 mod pack;
 mod req;
+mod wait;
 
-use crate::{Catch, ModelBase, StmtBuild, Timeout, Vars};
+use crate::{Catch, Include, Limits, ModelBase, Retry, StmtBuild, Timeout, Vars};
 pub use block::Block;
 pub use call::Call;
 pub use chain::Chain;
 pub use cmd::Cmd;
-pub use each::Each;
+pub use delay::Delay;
+pub use each::{Each, EachMode};
 pub use msg::Msg;
 pub use pack::Pack;
 pub use r#if::If;
+pub use r#loop::Loop;
 pub use req::Req;
 use serde::{Deserialize, Serialize};
+pub use wait::Wait;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Act {
@@ -52,9 +59,21 @@ pub enum Act {
     #[serde(rename = "call")]
     Call(Call),
 
+    #[serde(rename = "loop")]
+    Loop(Loop),
+
     #[serde(rename = "pack")]
     Pack(Pack),
 
+    #[serde(rename = "wait")]
+    Wait(Wait),
+
+    #[serde(rename = "delay")]
+    Delay(Delay),
+
+    #[serde(rename = "include")]
+    Include(Include),
+
     #[serde(rename = "on_created")]
     OnCreated(Vec<Act>),
 
@@ -85,6 +104,10 @@ impl ModelBase for Act {
             Act::Call(r#use) => &r#use.id,
             Act::Block(b) => &b.id,
             Act::Pack(p) => &p.id,
+            Act::Loop(l) => &l.id,
+            Act::Wait(w) => &w.id,
+            Act::Delay(d) => &d.id,
+            Act::Include(i) => &i.id,
             _ => "",
         }
     }
@@ -110,7 +133,11 @@ impl Act {
             Act::Block(_) => "block",
             Act::If(_) => "if",
             Act::Call(_) => "use",
+            Act::Loop(_) => "loop",
             Act::Pack(_) => "pack",
+            Act::Wait(_) => "wait",
+            Act::Delay(_) => "delay",
+            Act::Include(_) => "include",
             Act::OnCreated(_) => "on_created",
             Act::OnTimeout(_) => "on_timeout",
             Act::OnBeforeUpdate(_) => "on_before_update",
@@ -123,8 +150,9 @@ impl Act {
 
     pub fn is_taskable(&self) -> bool {
         match self {
-            Act::Req(_) | Act::Block(_) | Act::Call(_) => true,
-            Act::Pack(_) => true,
+            Act::Req(_) | Act::Block(_) | Act::Call(_) | Act::Loop(_) => true,
+            Act::Each(each) => each.is_taskable(),
+            Act::Pack(_) | Act::Wait(_) | Act::Delay(_) => true,
             _ => false,
         }
     }
@@ -136,6 +164,10 @@ impl Act {
             Act::Call(r#use) => r#use.id = id.to_string(),
             Act::Block(b) => b.id = id.to_string(),
             Act::Pack(p) => p.id = id.to_string(),
+            Act::Loop(l) => l.id = id.to_string(),
+            Act::Wait(w) => w.id = id.to_string(),
+            Act::Delay(d) => d.id = id.to_string(),
+            Act::Include(i) => i.id = id.to_string(),
             _ => {}
         }
     }
@@ -162,6 +194,7 @@ impl Act {
             Act::Msg(msg) => &msg.name,
             Act::Cmd(cmd) => &cmd.name,
             Act::Pack(p) => &p.name,
+            Act::Wait(w) => &w.signal,
             _ => "",
         }
     }
@@ -172,6 +205,7 @@ impl Act {
             Act::Msg(msg) => msg.inputs.clone(),
             Act::Call(r#use) => r#use.inputs.clone(),
             Act::Pack(p) => p.inputs.clone(),
+            Act::Block(b) => b.inputs.clone(),
             _ => Vars::new(),
         }
     }
@@ -192,6 +226,33 @@ impl Act {
         }
     }
 
+    pub fn retry(&self) -> Option<&Retry> {
+        match self {
+            Act::Req(req) => req.retry.as_ref(),
+            Act::Pack(p) => p.retry.as_ref(),
+            Act::Call(c) => c.retry.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn limits(&self) -> Option<&Limits> {
+        match self {
+            Act::Req(req) => req.limits.as_ref(),
+            Act::Pack(p) => p.limits.as_ref(),
+            Act::Call(c) => c.limits.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn compensate(&self) -> &[Act] {
+        match self {
+            Act::Req(req) => &req.compensate,
+            Act::Pack(p) => &p.compensate,
+            Act::Call(c) => &c.compensate,
+            _ => &[],
+        }
+    }
+
     pub fn set(var: Vars) -> Self {
         Act::Set(var)
     }
@@ -240,11 +301,31 @@ impl Act {
         Act::Block(build(block))
     }
 
+    pub fn r#loop(build: fn(Loop) -> Loop) -> Self {
+        let l = Loop::default();
+        Act::Loop(build(l))
+    }
+
     pub fn pack(build: fn(Pack) -> Pack) -> Self {
         let pack = Pack::default();
         Act::Pack(build(pack))
     }
 
+    pub fn wait(build: fn(Wait) -> Wait) -> Self {
+        let w = Wait::default();
+        Act::Wait(build(w))
+    }
+
+    pub fn delay(build: fn(Delay) -> Delay) -> Self {
+        let d = Delay::default();
+        Act::Delay(build(d))
+    }
+
+    pub fn include(build: fn(Include) -> Include) -> Self {
+        let i = Include::default();
+        Act::Include(build(i))
+    }
+
     pub fn on_created(build: fn(Vec<Act>) -> Vec<Act>) -> Self {
         let stmts = Vec::new();
         Act::OnCreated(build(stmts))
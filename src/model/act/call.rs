@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::Vars;
+use crate::{Act, Limits, Retry, Vars};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Call {
@@ -16,6 +16,16 @@ pub struct Call {
 
     #[serde(default)]
     pub outputs: Vars,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<Retry>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<Limits>,
+
+    /// the acts to undo the side effects when the proc is aborted or failed
+    #[serde(default)]
+    pub compensate: Vec<Act>,
 }
 
 impl Call {
@@ -48,4 +58,20 @@ impl Call {
         self.outputs.set(name, value);
         self
     }
+
+    pub fn with_retry(mut self, build: fn(Retry) -> Retry) -> Self {
+        self.retry = Some(build(Retry::default()));
+        self
+    }
+
+    pub fn with_limits(mut self, build: fn(Limits) -> Limits) -> Self {
+        self.limits = Some(build(Limits::default()));
+        self
+    }
+
+    pub fn with_compensate(mut self, build: fn(Vec<Act>) -> Vec<Act>) -> Self {
+        let stmts = Vec::new();
+        self.compensate = build(stmts);
+        self
+    }
 }
@@ -1,26 +1,48 @@
-use crate::{Act, ActError, Context, Result};
+use super::items;
+use crate::{utils::consts, Act, Context, Result, Vars};
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Chain {
-    #[serde(default)]
+    /// the expression of the array or object to iterate
+    #[serde(default, deserialize_with = "items::deserialize_in")]
     pub r#in: String,
     pub run: Vec<Act>,
+
+    /// the var name of the item, default to `act_value`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item: Option<String>,
+
+    /// the var name of the array index or object key, default to `act_index`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<String>,
 }
 
 impl Chain {
-    pub fn parse(&self, ctx: &Context, scr: &str) -> Result<Vec<String>> {
-        if scr.is_empty() {
-            return Err(ActError::Runtime("chain's 'in' is empty".to_string()));
-        }
-        let result = ctx.eval::<Vec<String>>(scr)?;
-        if result.len() == 0 {
-            return Err(ActError::Runtime(format!(
-                "chain.in is empty in task({})",
-                ctx.task().id
-            )));
-        }
-        Ok(result)
+    /// evaluate the `in` to the `(index, item)` pairs
+    pub fn parse(&self, ctx: &Context, scr: &str) -> Result<Vec<(JsonValue, JsonValue)>> {
+        items::parse(ctx, "chain", scr)
+    }
+
+    /// the vars of the item with the index and item names
+    pub fn item_vars(&self, index: &JsonValue, item: &JsonValue) -> Vars {
+        Vars::new()
+            .with(
+                self.index.as_deref().unwrap_or(consts::ACT_INDEX),
+                index.clone(),
+            )
+            .with(
+                self.item.as_deref().unwrap_or(consts::ACT_VALUE),
+                item.clone(),
+            )
+    }
+
+    /// the acts to run for the item, the item vars are passed into the req and msg inputs
+    pub(crate) fn item_acts(&self, vars: &Vars) -> Vec<Act> {
+        let mut acts = self.run.clone();
+        items::set_inputs(&mut acts, vars);
+        acts
     }
 
     pub fn new() -> Self {
@@ -32,6 +54,16 @@ impl Chain {
         self
     }
 
+    pub fn with_item(mut self, name: &str) -> Self {
+        self.item = Some(name.to_string());
+        self
+    }
+
+    pub fn with_index(mut self, name: &str) -> Self {
+        self.index = Some(name.to_string());
+        self
+    }
+
     pub fn with_run(mut self, build: fn(Vec<Act>) -> Vec<Act>) -> Self {
         let stmts = Vec::new();
         self.run = build(stmts);
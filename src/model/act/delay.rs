@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+/// Pause the step for a while
+///
+/// set `duration` to pause for a duration such as `30s`, `10m`, `2h` or `1d`,
+/// or set `until` to pause until an absolute time, which is a millis timestamp,
+/// an RFC 3339 date or a `${ }` expression that returns one of them.
+/// the `duration` counts in the working time when the `calendar` is set
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Delay {
+    #[serde(default)]
+    pub id: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<JsonValue>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calendar: Option<String>,
+}
+
+impl Delay {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    pub fn with_duration(mut self, duration: &str) -> Self {
+        self.duration = Some(duration.to_string());
+        self
+    }
+
+    pub fn with_until<T: Into<JsonValue>>(mut self, until: T) -> Self {
+        self.until = Some(until.into());
+        self
+    }
+
+    pub fn with_calendar(mut self, name: &str) -> Self {
+        self.calendar = Some(name.to_string());
+        self
+    }
+}
@@ -1,27 +1,70 @@
-use crate::{Act, ActError, Context, Result};
+use super::items;
+use crate::{utils::consts, Act, Context, Result, Vars};
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+/// The mode to run the each instances
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EachMode {
+    /// create all of the instances at once
+    #[default]
+    Parallel,
+
+    /// create the next instance after the previous one is finished
+    Sequential,
+}
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Each {
-    #[serde(default)]
+    /// the expression of the array or object to iterate
+    #[serde(default, deserialize_with = "items::deserialize_in")]
     pub r#in: String,
     pub run: Vec<Act>,
+
+    /// the var name of the item, default to `act_value`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item: Option<String>,
+
+    /// the var name of the array index or object key, default to `act_index`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<String>,
+
+    /// the completion condition, which is checked when any instance is finished
+    /// the `total`, `completed`, `skipped`, `errored` and `finished` variables are the counters of the instances
+    /// the remaining instances are cancelled when it is true
+    /// the errored instances fail the each only when all of the instances are finished without meeting it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
+
+    #[serde(default)]
+    pub mode: EachMode,
 }
 
 impl Each {
-    pub fn parse(&self, ctx: &Context, scr: &str) -> Result<Vec<String>> {
-        if scr.is_empty() {
-            return Err(ActError::Runtime("each's 'in' is empty".to_string()));
-        }
+    /// evaluate the `in` to the `(index, item)` pairs
+    pub fn parse(&self, ctx: &Context, scr: &str) -> Result<Vec<(JsonValue, JsonValue)>> {
+        items::parse(ctx, "each", scr)
+    }
 
-        let result = ctx.eval::<Vec<String>>(scr)?;
-        if result.len() == 0 {
-            return Err(ActError::Runtime(format!(
-                "each.in is empty in task({})",
-                ctx.task().id
-            )));
-        }
-        Ok(result)
+    /// the vars of the item with the index and item names
+    pub fn item_vars(&self, index: &JsonValue, item: &JsonValue) -> Vars {
+        Vars::new()
+            .with(
+                self.index.as_deref().unwrap_or(consts::ACT_INDEX),
+                index.clone(),
+            )
+            .with(
+                self.item.as_deref().unwrap_or(consts::ACT_VALUE),
+                item.clone(),
+            )
+    }
+
+    /// the acts to run for the item, the item vars are passed into the req and msg inputs
+    pub(crate) fn item_acts(&self, vars: &Vars) -> Vec<Act> {
+        let mut acts = self.run.clone();
+        items::set_inputs(&mut acts, vars);
+        acts
     }
 
     pub fn new() -> Self {
@@ -38,4 +81,29 @@ impl Each {
         self.run = build(stmts);
         self
     }
+
+    pub fn with_item(mut self, name: &str) -> Self {
+        self.item = Some(name.to_string());
+        self
+    }
+
+    pub fn with_index(mut self, name: &str) -> Self {
+        self.index = Some(name.to_string());
+        self
+    }
+
+    pub fn with_until(mut self, expr: &str) -> Self {
+        self.until = Some(expr.to_string());
+        self
+    }
+
+    pub fn with_mode(mut self, mode: EachMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// the instances are run in an each task to check the completion condition or the sequential mode
+    pub fn is_taskable(&self) -> bool {
+        self.until.is_some() || self.mode == EachMode::Sequential
+    }
 }
@@ -0,0 +1,84 @@
+use crate::{Act, ActError, Context, Result, Vars};
+use serde::{Deserialize, Deserializer};
+use serde_json::Value as JsonValue;
+
+/// deserialize the `in` of each and chain
+/// the string is an expression, and the other values (such as an array from js) are converted to json text
+pub(crate) fn deserialize_in<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = JsonValue::deserialize(deserializer)?;
+    Ok(match value {
+        JsonValue::String(s) => s,
+        JsonValue::Null => String::new(),
+        v => v.to_string(),
+    })
+}
+
+/// evaluate the `in` expression to the `(index, item)` pairs
+/// the index is the position of an array or the key of an object
+pub(crate) fn parse(ctx: &Context, kind: &str, scr: &str) -> Result<Vec<(JsonValue, JsonValue)>> {
+    if scr.is_empty() {
+        return Err(ActError::Runtime(format!("{kind}'s 'in' is empty")));
+    }
+
+    let items: Vec<(JsonValue, JsonValue)> = match ctx.eval::<JsonValue>(scr)? {
+        JsonValue::Array(items) => items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| (index.into(), item))
+            .collect(),
+        JsonValue::Object(items) => items
+            .into_iter()
+            .map(|(key, item)| (key.into(), item))
+            .collect(),
+        value => {
+            return Err(ActError::Runtime(format!(
+                "{kind}.in expects an array or object but got '{value}' in task({})",
+                ctx.task().id
+            )))
+        }
+    };
+
+    if items.is_empty() {
+        return Err(ActError::Runtime(format!(
+            "{kind}.in is empty in task({})",
+            ctx.task().id
+        )));
+    }
+    Ok(items)
+}
+
+/// set the item vars to the inputs of the req and msg acts
+pub(crate) fn set_inputs(acts: &mut [Act], vars: &Vars) {
+    for act in acts.iter_mut() {
+        match act {
+            Act::Req(req) => {
+                for (key, value) in vars {
+                    req.inputs.set(key, value.clone());
+                }
+            }
+            Act::Msg(msg) => {
+                for (key, value) in vars {
+                    msg.inputs.set(key, value.clone());
+                }
+            }
+            Act::If(cond) => {
+                set_inputs(&mut cond.then, vars);
+                set_inputs(&mut cond.r#else, vars);
+            }
+            Act::Block(block) => {
+                let mut block = Some(block);
+                while let Some(b) = block {
+                    set_inputs(&mut b.acts, vars);
+                    block = b.next.as_deref_mut();
+                }
+            }
+            Act::Each(each) => set_inputs(&mut each.run, vars),
+            Act::Chain(chain) => set_inputs(&mut chain.run, vars),
+            Act::Loop(l) => set_inputs(&mut l.run, vars),
+            _ => {}
+        }
+    }
+}
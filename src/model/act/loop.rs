@@ -0,0 +1,70 @@
+use crate::Act;
+use serde::{Deserialize, Serialize};
+
+/// Repeat the acts until the condition holds
+///
+/// every iteration runs as a block task with the iteration index in the `index` var,
+/// the loop fails with the `loop_max` ecode if the condition still holds after `max` iterations
+///
+/// it is an act only, to repeat the steps, put their acts in the `run` of the loop
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Loop {
+    #[serde(default)]
+    pub id: String,
+
+    /// check before each iteration, the loop goes on when it is true
+    #[serde(default)]
+    pub r#while: Option<String>,
+
+    /// check after each iteration, the loop stops when it is true
+    #[serde(default)]
+    pub until: Option<String>,
+
+    /// the max iterations to guard the loop, it is required and must be greater than 0
+    /// it is the repeat count when there is neither `while` nor `until`
+    pub max: u32,
+
+    /// the var name of the iteration index, default to `loop_index`
+    #[serde(default)]
+    pub index: Option<String>,
+
+    #[serde(default)]
+    pub run: Vec<Act>,
+}
+
+impl Loop {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    pub fn with_while(mut self, expr: &str) -> Self {
+        self.r#while = Some(expr.to_string());
+        self
+    }
+
+    pub fn with_until(mut self, expr: &str) -> Self {
+        self.until = Some(expr.to_string());
+        self
+    }
+
+    pub fn with_max(mut self, max: u32) -> Self {
+        self.max = max;
+        self
+    }
+
+    pub fn with_index(mut self, name: &str) -> Self {
+        self.index = Some(name.to_string());
+        self
+    }
+
+    pub fn with_run(mut self, build: fn(Vec<Act>) -> Vec<Act>) -> Self {
+        let stmts = Vec::new();
+        self.run = build(stmts);
+        self
+    }
+}
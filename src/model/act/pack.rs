@@ -1,4 +1,4 @@
-use crate::Vars;
+use crate::{Act, Limits, Retry, Vars};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -17,6 +17,16 @@ pub struct Pack {
 
     #[serde(default)]
     pub outputs: Vars,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<Retry>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<Limits>,
+
+    /// the acts to undo the side effects when the proc is aborted or failed
+    #[serde(default)]
+    pub compensate: Vec<Act>,
 }
 
 impl Pack {
@@ -54,4 +64,20 @@ impl Pack {
         self.uses = pack_id.to_string();
         self
     }
+
+    pub fn with_retry(mut self, build: fn(Retry) -> Retry) -> Self {
+        self.retry = Some(build(Retry::default()));
+        self
+    }
+
+    pub fn with_limits(mut self, build: fn(Limits) -> Limits) -> Self {
+        self.limits = Some(build(Limits::default()));
+        self
+    }
+
+    pub fn with_compensate(mut self, build: fn(Vec<Act>) -> Vec<Act>) -> Self {
+        let stmts = Vec::new();
+        self.compensate = build(stmts);
+        self
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{Act, Catch, Timeout, Vars};
+use crate::{Act, Catch, Limits, Retry, Timeout, Vars};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -32,6 +32,13 @@ pub struct Req {
     pub catches: Vec<Catch>,
     #[serde(default)]
     pub timeout: Vec<Timeout>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<Retry>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<Limits>,
+    #[serde(default)]
+    pub compensate: Vec<Act>,
 }
 
 impl Req {
@@ -105,4 +112,20 @@ impl Req {
         self.timeout.push(build(c));
         self
     }
+
+    pub fn with_retry(mut self, build: fn(Retry) -> Retry) -> Self {
+        self.retry = Some(build(Retry::default()));
+        self
+    }
+
+    pub fn with_limits(mut self, build: fn(Limits) -> Limits) -> Self {
+        self.limits = Some(build(Limits::default()));
+        self
+    }
+
+    pub fn with_compensate(mut self, build: fn(Vec<Act>) -> Vec<Act>) -> Self {
+        let stmts = Vec::new();
+        self.compensate = build(stmts);
+        self
+    }
 }
@@ -0,0 +1,49 @@
+use crate::Timeout;
+use serde::{Deserialize, Serialize};
+
+/// Wait for an external signal
+///
+/// the task is interrupted until `Executor::signal` is called with the same signal name
+/// and the correlation key, the `key` can be a template such as `order:${ $("order_id") }`
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Wait {
+    #[serde(default)]
+    pub id: String,
+
+    /// the signal name
+    pub signal: String,
+
+    /// the correlation key to match the signal
+    #[serde(default)]
+    pub key: String,
+
+    #[serde(default)]
+    pub timeout: Vec<Timeout>,
+}
+
+impl Wait {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    pub fn with_signal(mut self, signal: &str) -> Self {
+        self.signal = signal.to_string();
+        self
+    }
+
+    pub fn with_key(mut self, key: &str) -> Self {
+        self.key = key.to_string();
+        self
+    }
+
+    pub fn with_timeout(mut self, build: fn(Timeout) -> Timeout) -> Self {
+        let c = Timeout::default();
+        self.timeout.push(build(c));
+        self
+    }
+}
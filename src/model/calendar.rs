@@ -0,0 +1,267 @@
+use crate::{ActError, Result};
+use chrono::{Datelike, Days, NaiveDate, NaiveTime, TimeZone, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, str::FromStr};
+
+/// the max days to search the working time
+const MAX_CALENDAR_DAYS: u64 = 3660;
+
+/// The business calendar to count the timeouts and delays in working time
+///
+/// the `workdays` default to Mon-Fri and the `hours` default to 09:00-17:00
+///
+/// # Example
+/// ```yaml
+/// name: office
+/// timezone: Asia/Shanghai
+/// workdays: [Mon, Tue, Wed, Thu, Fri]
+/// hours:
+///   - 09:00-12:00
+///   - 13:00-18:00
+/// holidays:
+///   - 2026-10-01
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Calendar {
+    pub name: String,
+
+    /// the iana timezone name, default to UTC
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+
+    #[serde(default)]
+    pub workdays: Vec<String>,
+
+    /// the working hours of a workday in `HH:MM-HH:MM`, they are in order and not overlapped
+    #[serde(default)]
+    pub hours: Vec<String>,
+
+    /// the dates in `YYYY-MM-DD` which are not workdays
+    #[serde(default)]
+    pub holidays: Vec<String>,
+}
+
+struct WorkingTime {
+    tz: Tz,
+    workdays: HashSet<Weekday>,
+    hours: Vec<(NaiveTime, NaiveTime)>,
+    holidays: HashSet<NaiveDate>,
+}
+
+impl Calendar {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn from_yml(s: &str) -> Result<Self> {
+        let calendar =
+            serde_yaml::from_str::<Calendar>(s).map_err(|err| ActError::Model(format!("{err}")))?;
+        calendar.valid()?;
+        Ok(calendar)
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn with_timezone(mut self, timezone: &str) -> Self {
+        self.timezone = Some(timezone.to_string());
+        self
+    }
+
+    pub fn with_workdays(mut self, days: &[&str]) -> Self {
+        self.workdays = days.iter().map(|d| d.to_string()).collect();
+        self
+    }
+
+    pub fn with_hours(mut self, hours: &[&str]) -> Self {
+        self.hours = hours.iter().map(|h| h.to_string()).collect();
+        self
+    }
+
+    pub fn with_holiday(mut self, date: &str) -> Self {
+        self.holidays.push(date.to_string());
+        self
+    }
+
+    pub fn valid(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(ActError::Model(
+                "the calendar's 'name' is required".to_string(),
+            ));
+        }
+        let time = self.working_time()?;
+        if time.workdays.is_empty() || time.hours.is_empty() {
+            return Err(ActError::Model(format!(
+                "the calendar '{}' has no working time",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+
+    /// the time in millis after adding the working `millis` to the `start`
+    pub fn add(&self, start: i64, millis: i64) -> Result<i64> {
+        if millis <= 0 {
+            return Ok(start);
+        }
+        let time = self.working_time()?;
+        let mut remain = millis;
+        let mut date = time.date(start)?;
+        for _ in 0..MAX_CALENDAR_DAYS {
+            for (begin, end) in time.ranges(date)? {
+                let begin = begin.max(start);
+                if begin >= end {
+                    continue;
+                }
+                if remain <= end - begin {
+                    return Ok(begin + remain);
+                }
+                remain -= end - begin;
+            }
+            date = time.next_date(date)?;
+        }
+
+        Err(ActError::Runtime(format!(
+            "the calendar '{}' has no working time",
+            self.name
+        )))
+    }
+
+    /// the working time in millis between the `start` and `end`
+    pub fn elapsed(&self, start: i64, end: i64) -> Result<i64> {
+        let time = self.working_time()?;
+        let mut elapsed = 0;
+        let mut date = time.date(start)?;
+        while date <= time.date(end)? {
+            for (begin, finish) in time.ranges(date)? {
+                let begin = begin.max(start);
+                let finish = finish.min(end);
+                if begin < finish {
+                    elapsed += finish - begin;
+                }
+            }
+            date = time.next_date(date)?;
+        }
+        Ok(elapsed)
+    }
+
+    fn working_time(&self) -> Result<WorkingTime> {
+        let tz = match &self.timezone {
+            Some(name) => Tz::from_str(name).map_err(|err| {
+                ActError::Model(format!("the calendar timezone '{name}' is invalid: {err}"))
+            })?,
+            None => Tz::UTC,
+        };
+
+        let workdays = if self.workdays.is_empty() {
+            HashSet::from([
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ])
+        } else {
+            self.workdays
+                .iter()
+                .map(|day| {
+                    Weekday::from_str(day).map_err(|_| {
+                        ActError::Model(format!("the calendar workday '{day}' is invalid"))
+                    })
+                })
+                .collect::<Result<HashSet<_>>>()?
+        };
+
+        let mut hours = Vec::new();
+        let default_hours = vec!["09:00-17:00".to_string()];
+        let items = if self.hours.is_empty() {
+            &default_hours
+        } else {
+            &self.hours
+        };
+        for item in items {
+            let range = item.split_once('-').and_then(|(begin, end)| {
+                let begin = NaiveTime::parse_from_str(begin.trim(), "%H:%M").ok()?;
+                let end = NaiveTime::parse_from_str(end.trim(), "%H:%M").ok()?;
+                (begin < end).then_some((begin, end))
+            });
+            match range {
+                Some(range) => hours.push(range),
+                None => {
+                    return Err(ActError::Model(format!(
+                        "the calendar hours '{item}' is invalid"
+                    )))
+                }
+            }
+        }
+        // the hours must be in order and not overlap, or the working time is counted twice
+        for pair in hours.windows(2) {
+            if pair[1].0 < pair[0].1 {
+                return Err(ActError::Model(format!(
+                    "the calendar hours '{}-{}' is overlapped or not in order",
+                    pair[1].0.format("%H:%M"),
+                    pair[1].1.format("%H:%M")
+                )));
+            }
+        }
+
+        let holidays = self
+            .holidays
+            .iter()
+            .map(|date| {
+                NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|err| {
+                    ActError::Model(format!("the calendar holiday '{date}' is invalid: {err}"))
+                })
+            })
+            .collect::<Result<HashSet<_>>>()?;
+
+        Ok(WorkingTime {
+            tz,
+            workdays,
+            hours,
+            holidays,
+        })
+    }
+}
+
+impl WorkingTime {
+    fn date(&self, millis: i64) -> Result<NaiveDate> {
+        self.tz
+            .timestamp_millis_opt(millis)
+            .single()
+            .map(|t| t.date_naive())
+            .ok_or(ActError::Runtime(format!(
+                "the calendar time '{millis}' is invalid"
+            )))
+    }
+
+    fn next_date(&self, date: NaiveDate) -> Result<NaiveDate> {
+        date.checked_add_days(Days::new(1))
+            .ok_or(ActError::Runtime(format!(
+                "the calendar date '{date}' is out of range"
+            )))
+    }
+
+    /// the working ranges in millis of the date
+    fn ranges(&self, date: NaiveDate) -> Result<Vec<(i64, i64)>> {
+        if !self.workdays.contains(&date.weekday()) || self.holidays.contains(&date) {
+            return Ok(Vec::new());
+        }
+
+        let mut ret = Vec::new();
+        for (begin, end) in &self.hours {
+            let begin = self
+                .tz
+                .from_local_datetime(&date.and_time(*begin))
+                .earliest();
+            let end = self.tz.from_local_datetime(&date.and_time(*end)).latest();
+            if let (Some(begin), Some(end)) = (begin, end) {
+                ret.push((begin.timestamp_millis(), end.timestamp_millis()));
+            }
+        }
+        Ok(ret)
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{Act, ActError, Result, Step, Vars};
+use serde::{Deserialize, Serialize};
+
+/// The named step or acts to reuse in other models
+///
+/// the `inputs` are the parameters with the default values,
+/// which can be overridden by the `inputs` of the [`Include`]
+///
+/// # Example
+/// ```yaml
+/// id: fragments
+/// fragments:
+///   - id: approval
+///     inputs:
+///       role: admin
+///     step:
+///       name: approval
+///       acts:
+///         - !req
+///           id: approve
+///           inputs:
+///             role: ${ $("role") }
+///   - id: notify
+///     acts:
+///       - !msg
+///         id: notify
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Fragment {
+    pub id: String,
+
+    #[serde(default)]
+    pub inputs: Vars,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step: Option<Step>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub acts: Vec<Act>,
+}
+
+/// Include the fragment by `uses` which is expanded when deploying
+///
+/// the `uses` is in `source/fragment@ver` format, the `source` is a deployed model or package
+/// and the `@ver` is only for the models. the fragment in the same model is referred by its id
+///
+/// # Example
+/// ```yaml
+/// steps:
+///   - id: approve
+///     include:
+///       uses: fragments/approval@2
+///       inputs:
+///         role: manager
+///     acts:
+///       - !include
+///         uses: fragments/notify
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Include {
+    #[serde(default)]
+    pub id: String,
+
+    pub uses: String,
+
+    #[serde(default)]
+    pub inputs: Vars,
+}
+
+impl Fragment {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    pub fn with_input<T>(mut self, name: &str, value: T) -> Self
+    where
+        T: Serialize + Clone,
+    {
+        self.inputs.set(name, value);
+        self
+    }
+
+    pub fn with_step(mut self, build: fn(Step) -> Step) -> Self {
+        self.step = Some(build(Step::default()));
+        self
+    }
+
+    pub fn with_acts(mut self, build: fn(Vec<Act>) -> Vec<Act>) -> Self {
+        let stmts = Vec::new();
+        self.acts = build(stmts);
+        self
+    }
+}
+
+impl Include {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    pub fn with_uses(mut self, uses: &str) -> Self {
+        self.uses = uses.to_string();
+        self
+    }
+
+    pub fn with_input<T>(mut self, name: &str, value: T) -> Self
+    where
+        T: Serialize + Clone,
+    {
+        self.inputs.set(name, value);
+        self
+    }
+
+    /// parse the `uses` to (source, fragment id, version)
+    /// the source is `None` for the fragment in the same model
+    pub fn parse(&self) -> Result<(Option<String>, String, Option<u32>)> {
+        let (path, ver) = match self.uses.rsplit_once('@') {
+            Some((path, ver)) => {
+                let ver = ver.parse::<u32>().map_err(|_| {
+                    ActError::Model(format!("the include version in '{}' is invalid", self.uses))
+                })?;
+                (path, Some(ver))
+            }
+            None => (self.uses.as_str(), None),
+        };
+        let (source, id) = match path.rsplit_once('/') {
+            Some((source, id)) => (Some(source.to_string()), id),
+            None => (None, path),
+        };
+        if id.is_empty() || source.as_ref().is_some_and(|s| s.is_empty()) {
+            return Err(ActError::Model(format!(
+                "the include '{}' is invalid",
+                self.uses
+            )));
+        }
+        if source.is_none() && ver.is_some() {
+            return Err(ActError::Model(format!(
+                "the include '{}' in the same model cannot have version",
+                self.uses
+            )));
+        }
+
+        Ok((source, id.to_string(), ver))
+    }
+}
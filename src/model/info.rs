@@ -1,4 +1,4 @@
-use crate::{sch, store::data, ActError, Result, Workflow};
+use crate::{sch, store::data, ActError, CatchUp, Result, Schema, Workflow};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
@@ -18,6 +18,7 @@ pub struct ProcInfo {
     pub id: String,
     pub name: String,
     pub mid: String,
+    pub ver: u32,
     pub state: String,
     pub start_time: i64,
     pub end_time: i64,
@@ -40,6 +41,45 @@ pub struct TaskInfo {
     pub timestamp: i64,
 }
 
+/// The change kind of a task when migrating a proc
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub enum MigrateKind {
+    /// the node is not changed
+    Kept,
+    /// the node with the same id is changed in the new model
+    Changed,
+    /// the node is replaced by the node in mapping
+    Mapped,
+    /// the node is removed from the new model, only for the completed tasks
+    /// the task is kept with the removed node for the history
+    Removed,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MigrateChange {
+    pub tid: String,
+    pub state: String,
+    pub from: String,
+    pub to: Option<String>,
+    pub kind: MigrateKind,
+}
+
+/// The report of a proc migration
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MigrateReport {
+    pub pid: String,
+    pub mid: String,
+    pub from_ver: u32,
+    pub to_ver: u32,
+    pub dry_run: bool,
+
+    /// the task changes
+    pub changes: Vec<MigrateChange>,
+
+    /// the new node ids in the target model
+    pub added: Vec<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ModelInfo {
     pub id: String,
@@ -48,6 +88,40 @@ pub struct ModelInfo {
     pub size: u32,
     pub time: i64,
     pub model: String,
+
+    /// the inputs and outputs schema to render the start form
+    #[serde(default)]
+    pub schema: Schema,
+}
+
+/// The cron trigger of the model with the upcoming fire times
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TriggerInfo {
+    pub id: String,
+    pub mid: String,
+    pub cron: String,
+    pub timezone: Option<String>,
+    pub catch_up: CatchUp,
+    pub last_time: i64,
+    pub next_times: Vec<i64>,
+}
+
+/// The computed due time of a waiting task
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DueInfo {
+    pub pid: String,
+    pub tid: String,
+    pub node_id: String,
+    pub name: String,
+    pub r#type: String,
+
+    /// the due source, `timeout` or `delay`
+    pub source: String,
+    pub calendar: Option<String>,
+    pub due: i64,
+
+    /// the time in millis left to the due, counted in the working time if it has a calendar
+    pub remaining: i64,
 }
 
 impl From<&data::Package> for PackageInfo {
@@ -78,6 +152,9 @@ impl ModelInfo {
 
 impl From<data::Model> for ModelInfo {
     fn from(m: data::Model) -> Self {
+        let schema = Workflow::from_yml(&m.data)
+            .map(|w| w.schema)
+            .unwrap_or_default();
         Self {
             id: m.id,
             name: m.name,
@@ -85,6 +162,7 @@ impl From<data::Model> for ModelInfo {
             size: m.size,
             time: m.time,
             model: m.data,
+            schema,
         }
     }
 }
@@ -95,6 +173,9 @@ impl From<&data::Proc> for ProcInfo {
             id: p.id.clone(),
             name: p.name.clone(),
             mid: p.mid.clone(),
+            ver: Workflow::from_json(&p.model)
+                .map(|m| m.ver())
+                .unwrap_or_default(),
             state: p.state.clone(),
             start_time: p.start_time,
             end_time: p.end_time,
@@ -157,6 +238,7 @@ impl Into<serde_json::Value> for ProcInfo {
         json!({
             "id": self.id,
             "mid": self.mid,
+            "ver": self.ver,
             "name": self.name,
             "state": self.state,
             "start_time": self.start_time,
@@ -176,6 +258,7 @@ impl Into<serde_json::Value> for ModelInfo {
             "size": self.size,
             "time": self.time,
             "model": self.model,
+            "schema": self.schema,
         })
     }
 }
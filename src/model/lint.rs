@@ -0,0 +1,646 @@
+use crate::{
+    utils, Act, Branch, Join, ModelBase, Retry, Step, Timeout, TimeoutLimit, Vars, Workflow,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    sync::OnceLock,
+};
+
+/// the `act.back("step")` calls in the scripts
+static BACK: OnceLock<Regex> = OnceLock::new();
+
+/// The severity of a lint diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A diagnostic reported by [`Workflow::lint`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+
+    /// the node id which the diagnostic belongs to
+    pub node: String,
+    pub message: String,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.severity, self.node, self.message)
+    }
+}
+
+impl Workflow {
+    /// check the model and return all of the diagnostics
+    /// it reports more problems than [`Workflow::valid`] without stopping at the first one
+    pub fn lint(&self) -> Vec<Diagnostic> {
+        let mut linter = Linter::default();
+        linter.lint(self);
+        linter.diagnostics
+    }
+}
+
+impl Workflow {
+    /// collect all of the embedded scripts with the node path
+    /// including `run`, `if`, `on`, `each.in`, `chain.in` and the `${ }` expressions in vars
+    pub(crate) fn scripts(&self) -> Vec<(String, String)> {
+        let mut scripts = Vec::new();
+        collect_vars(&mut scripts, &self.id, "inputs", &self.inputs);
+        collect_vars(&mut scripts, &self.id, "outputs", &self.outputs);
+        collect_vars(&mut scripts, &self.id, "env", &self.env);
+        collect_act_scripts(&mut scripts, &format!("{}.setup", self.id), &self.setup);
+        for step in &self.steps {
+            collect_step_scripts(&mut scripts, step);
+        }
+
+        scripts
+    }
+}
+
+fn collect_vars(scripts: &mut Vec<(String, String)>, path: &str, name: &str, vars: &Vars) {
+    for (key, value) in vars {
+        if let Some(expr) = value.as_str().and_then(utils::get_expr) {
+            scripts.push((format!("{path}.{name}.{key}"), expr));
+        }
+    }
+}
+
+fn collect_step_scripts(scripts: &mut Vec<(String, String)>, step: &Step) {
+    let path = &step.id;
+    if let Some(expr) = &step.r#if {
+        scripts.push((format!("{path}.if"), expr.clone()));
+    }
+    if let Some(run) = &step.run {
+        scripts.push((format!("{path}.run"), run.clone()));
+    }
+    if let Some(Join::Expr(expr)) = &step.join {
+        scripts.push((format!("{path}.join"), expr.clone()));
+    }
+    collect_vars(scripts, path, "inputs", &step.inputs);
+    collect_vars(scripts, path, "outputs", &step.outputs);
+    collect_act_scripts(scripts, &format!("{path}.setup"), &step.setup);
+    collect_act_scripts(scripts, &format!("{path}.acts"), &step.acts);
+    for (index, catch) in step.catches.iter().enumerate() {
+        collect_act_scripts(scripts, &format!("{path}.catches[{index}]"), &catch.then);
+    }
+    collect_timeout_scripts(scripts, &format!("{path}.timeout"), &step.timeout);
+    collect_act_scripts(scripts, &format!("{path}.compensate"), &step.compensate);
+
+    for branch in &step.branches {
+        let path = &branch.id;
+        if let Some(expr) = &branch.r#if {
+            scripts.push((format!("{path}.if"), expr.clone()));
+        }
+        if let Some(run) = &branch.run {
+            scripts.push((format!("{path}.run"), run.clone()));
+        }
+        collect_vars(scripts, path, "inputs", &branch.inputs);
+        collect_vars(scripts, path, "outputs", &branch.outputs);
+        for step in &branch.steps {
+            collect_step_scripts(scripts, step);
+        }
+    }
+}
+
+fn collect_act_scripts(scripts: &mut Vec<(String, String)>, path: &str, acts: &[Act]) {
+    for (index, act) in acts.iter().enumerate() {
+        let path = format!("{path}[{index}]");
+        collect_act_scripts(scripts, &format!("{path}.compensate"), act.compensate());
+        match act {
+            Act::Set(vars) => collect_vars(scripts, &path, "set", vars),
+            Act::Expose(vars) => collect_vars(scripts, &path, "expose", vars),
+            Act::Req(req) => {
+                collect_vars(scripts, &path, "inputs", &req.inputs);
+                collect_vars(scripts, &path, "outputs", &req.outputs);
+                collect_act_scripts(scripts, &format!("{path}.on_created"), &req.on_created);
+                collect_act_scripts(scripts, &format!("{path}.on_completed"), &req.on_completed);
+                collect_timeout_scripts(scripts, &format!("{path}.timeout"), &req.timeout);
+            }
+            Act::Msg(msg) => collect_vars(scripts, &path, "inputs", &msg.inputs),
+            Act::Cmd(cmd) => collect_vars(scripts, &path, "inputs", &cmd.inputs),
+            Act::Call(call) => {
+                collect_vars(scripts, &path, "inputs", &call.inputs);
+                collect_vars(scripts, &path, "outputs", &call.outputs);
+            }
+            Act::Pack(pack) => {
+                collect_vars(scripts, &path, "inputs", &pack.inputs);
+                collect_vars(scripts, &path, "outputs", &pack.outputs);
+            }
+            Act::Each(each) => {
+                scripts.push((format!("{path}.in"), each.r#in.clone()));
+                if let Some(expr) = &each.until {
+                    scripts.push((format!("{path}.until"), expr.clone()));
+                }
+                collect_act_scripts(scripts, &format!("{path}.run"), &each.run);
+            }
+            Act::Chain(chain) => {
+                scripts.push((format!("{path}.in"), chain.r#in.clone()));
+                collect_act_scripts(scripts, &format!("{path}.run"), &chain.run);
+            }
+            Act::Loop(l) => {
+                if let Some(expr) = &l.r#while {
+                    scripts.push((format!("{path}.while"), expr.clone()));
+                }
+                if let Some(expr) = &l.until {
+                    scripts.push((format!("{path}.until"), expr.clone()));
+                }
+                collect_act_scripts(scripts, &format!("{path}.run"), &l.run);
+            }
+            Act::Block(block) => {
+                let mut block = Some(block);
+                let mut path = path.clone();
+                while let Some(b) = block {
+                    collect_vars(scripts, &path, "inputs", &b.inputs);
+                    collect_act_scripts(scripts, &format!("{path}.acts"), &b.acts);
+                    block = b.next.as_deref();
+                    path = format!("{path}.next");
+                }
+            }
+            Act::Delay(d) => {
+                if let Some(expr) = d
+                    .until
+                    .as_ref()
+                    .and_then(|v| v.as_str())
+                    .and_then(utils::get_expr)
+                {
+                    scripts.push((format!("{path}.until"), expr));
+                }
+            }
+            Act::Wait(w) => {
+                for expr in utils::get_template_exprs(&w.key) {
+                    scripts.push((format!("{path}.key"), expr));
+                }
+                collect_timeout_scripts(scripts, &format!("{path}.timeout"), &w.timeout);
+            }
+            Act::If(cond) => {
+                scripts.push((format!("{path}.on"), cond.on.clone()));
+                collect_act_scripts(scripts, &format!("{path}.then"), &cond.then);
+                collect_act_scripts(scripts, &format!("{path}.else"), &cond.r#else);
+            }
+            Act::OnCreated(acts)
+            | Act::OnUpdated(acts)
+            | Act::OnBeforeUpdate(acts)
+            | Act::OnStep(acts)
+            | Act::OnCompleted(acts) => {
+                collect_act_scripts(scripts, &format!("{path}.{}", act.kind()), acts)
+            }
+            Act::OnTimeout(timeouts) => {
+                collect_timeout_scripts(scripts, &format!("{path}.on_timeout"), timeouts)
+            }
+            Act::OnErrorCatch(catches) => {
+                for (i, catch) in catches.iter().enumerate() {
+                    collect_act_scripts(
+                        scripts,
+                        &format!("{path}.on_error_catch[{i}]"),
+                        &catch.then,
+                    );
+                }
+            }
+            Act::Include(include) => collect_vars(scripts, &path, "inputs", &include.inputs),
+        }
+    }
+}
+
+fn collect_timeout_scripts(scripts: &mut Vec<(String, String)>, path: &str, timeouts: &[Timeout]) {
+    for (index, timeout) in timeouts.iter().enumerate() {
+        let path = format!("{path}[{index}]");
+        if let Some(expr) = timeout
+            .until
+            .as_ref()
+            .and_then(|v| v.as_str())
+            .and_then(utils::get_expr)
+        {
+            scripts.push((format!("{path}.until"), expr));
+        }
+        collect_act_scripts(scripts, &path, &timeout.then);
+    }
+}
+
+#[derive(Default)]
+struct Linter {
+    ids: HashMap<String, usize>,
+    steps: Vec<String>,
+    reachable: HashSet<String>,
+    produced: HashSet<String>,
+    rets: Vec<(String, Vars)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Linter {
+    fn lint(&mut self, workflow: &Workflow) {
+        self.add_id(&workflow.id);
+        self.add_produced(&workflow.inputs);
+        self.add_produced(&workflow.env);
+        self.check_triggers(workflow);
+        self.collect_acts(&workflow.setup);
+        self.collect_steps(&workflow.steps);
+
+        for (id, count) in &self.ids {
+            if *count > 1 {
+                self.diagnostics.push(Diagnostic {
+                    severity: Severity::Error,
+                    node: id.clone(),
+                    message: format!("the id '{id}' is duplicated {count} times"),
+                });
+            }
+        }
+
+        self.check_reachable(&workflow.steps);
+        self.check_steps(&workflow.steps);
+        for id in self.steps.clone() {
+            if !self.reachable.contains(&id) {
+                self.push(
+                    Severity::Warning,
+                    &id,
+                    "the step is unreachable".to_string(),
+                );
+            }
+        }
+
+        self.check_outputs(&workflow.id, &workflow.outputs);
+        for (node, rets) in std::mem::take(&mut self.rets) {
+            self.check_rets(&node, &rets);
+        }
+
+        self.diagnostics
+            .sort_by(|a, b| b.severity.cmp(&a.severity).then(a.node.cmp(&b.node)));
+    }
+
+    fn check_triggers(&mut self, workflow: &Workflow) {
+        for trigger in &workflow.triggers {
+            let node = match trigger.id.is_empty() {
+                true => &workflow.id,
+                false => &trigger.id,
+            };
+            if trigger.schedule().is_err() {
+                self.push(
+                    Severity::Error,
+                    node,
+                    format!("the trigger cron '{}' is invalid", trigger.cron),
+                );
+            }
+            if trigger.tz().is_err() {
+                self.push(
+                    Severity::Error,
+                    node,
+                    format!(
+                        "the trigger timezone '{}' is invalid",
+                        trigger.timezone.clone().unwrap_or_default()
+                    ),
+                );
+            }
+            self.add_produced(&trigger.inputs);
+        }
+    }
+
+    fn check_timeouts(&mut self, node: &str, timeouts: &[Timeout]) {
+        for timeout in timeouts {
+            match &timeout.every {
+                Some(every) if every.as_millis() <= 0 => self.push(
+                    Severity::Error,
+                    node,
+                    format!("the timeout's 'every' '{every}' must be greater than 0"),
+                ),
+                None if timeout.max.is_some() => self.push(
+                    Severity::Warning,
+                    node,
+                    "the timeout's 'max' is ignored without 'every'".to_string(),
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    fn check_retry(&mut self, node: &str, retry: Option<&Retry>) {
+        if let Some(retry) = retry {
+            if retry.times == 0 {
+                self.push(
+                    Severity::Warning,
+                    node,
+                    "the retry's 'times' is 0 and it will never retry".to_string(),
+                );
+            }
+        }
+    }
+
+    fn push(&mut self, severity: Severity, node: &str, message: String) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            node: node.to_string(),
+            message,
+        });
+    }
+
+    fn add_id(&mut self, id: &str) {
+        if !id.is_empty() {
+            *self.ids.entry(id.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    fn add_produced(&mut self, vars: &Vars) {
+        for (key, _) in vars {
+            self.produced.insert(key.clone());
+        }
+    }
+
+    fn collect_steps(&mut self, steps: &[Step]) {
+        for step in steps {
+            self.add_id(&step.id);
+            if !step.id.is_empty() {
+                self.steps.push(step.id.clone());
+            }
+            self.add_produced(&step.inputs);
+            self.check_timeouts(&step.id, &step.timeout);
+            self.check_retry(&step.id, step.retry.as_ref());
+            self.collect_acts(&step.setup);
+            self.collect_acts(&step.acts);
+            self.collect_acts(&step.compensate);
+            for catch in &step.catches {
+                self.collect_acts(&catch.then);
+            }
+            for timeout in &step.timeout {
+                self.collect_acts(&timeout.then);
+            }
+            for branch in &step.branches {
+                self.add_id(&branch.id);
+                self.add_produced(&branch.inputs);
+                self.collect_steps(&branch.steps);
+            }
+        }
+    }
+
+    fn collect_acts(&mut self, acts: &[Act]) {
+        for act in acts {
+            self.add_id(act.id());
+            self.check_retry(act.id(), act.retry());
+            self.collect_acts(act.compensate());
+            match act {
+                Act::Set(vars) | Act::Expose(vars) => self.add_produced(vars),
+                Act::Req(req) => {
+                    self.add_produced(&req.inputs);
+                    self.add_produced(&req.outputs);
+                    self.rets.push((req.id.clone(), req.rets.clone()));
+                    self.collect_acts(&req.on_created);
+                    self.collect_acts(&req.on_completed);
+                    self.check_timeouts(&req.id, &req.timeout);
+                    for catch in &req.catches {
+                        self.collect_acts(&catch.then);
+                    }
+                    for timeout in &req.timeout {
+                        self.collect_acts(&timeout.then);
+                    }
+                }
+                Act::Block(block) => {
+                    let mut block = Some(block);
+                    while let Some(b) = block {
+                        self.collect_acts(&b.acts);
+                        block = b.next.as_deref();
+                    }
+                }
+                Act::If(cond) => {
+                    self.collect_acts(&cond.then);
+                    self.collect_acts(&cond.r#else);
+                }
+                Act::Each(each) => {
+                    self.add_produced(&each.item_vars(&JsonValue::Null, &JsonValue::Null));
+                    self.collect_acts(&each.run);
+                }
+                Act::Chain(chain) => {
+                    self.add_produced(&chain.item_vars(&JsonValue::Null, &JsonValue::Null));
+                    self.collect_acts(&chain.run);
+                }
+                Act::Loop(l) => {
+                    if l.max == 0 {
+                        self.push(
+                            Severity::Error,
+                            &l.id,
+                            "the loop's 'max' must be greater than 0".to_string(),
+                        );
+                    }
+                    self.collect_acts(&l.run);
+                }
+                Act::Delay(d) => {
+                    if d.duration.is_none() && d.until.is_none() {
+                        self.push(
+                            Severity::Error,
+                            &d.id,
+                            "the delay requires 'duration' or 'until'".to_string(),
+                        );
+                    }
+                    if let Some(duration) = &d.duration {
+                        if TimeoutLimit::parse(duration).is_err() {
+                            self.push(
+                                Severity::Error,
+                                &d.id,
+                                format!("the delay's 'duration' '{duration}' is invalid"),
+                            );
+                        }
+                    }
+                }
+                Act::Wait(w) => {
+                    if w.signal.is_empty() {
+                        self.push(
+                            Severity::Error,
+                            &w.id,
+                            "the wait's 'signal' is required".to_string(),
+                        );
+                    }
+                    self.check_timeouts(&w.id, &w.timeout);
+                    for timeout in &w.timeout {
+                        self.collect_acts(&timeout.then);
+                    }
+                }
+                Act::OnCreated(acts)
+                | Act::OnUpdated(acts)
+                | Act::OnBeforeUpdate(acts)
+                | Act::OnStep(acts)
+                | Act::OnCompleted(acts) => self.collect_acts(acts),
+                Act::OnTimeout(timeouts) => {
+                    for timeout in timeouts {
+                        self.collect_acts(&timeout.then);
+                    }
+                }
+                Act::OnErrorCatch(catches) => {
+                    for catch in catches {
+                        self.collect_acts(&catch.then);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// walk the steps from the first one, a step goes to its `next` if set,
+    /// otherwise to the following sibling and the first step of each branch
+    fn check_reachable(&mut self, steps: &[Step]) {
+        let mut edges = HashMap::new();
+        collect_edges(steps, &mut edges);
+
+        let mut queue: VecDeque<&str> = steps.first().map(|s| s.id.as_str()).into_iter().collect();
+        while let Some(id) = queue.pop_front() {
+            if !self.reachable.insert(id.to_string()) {
+                continue;
+            }
+            if let Some(nexts) = edges.get(id) {
+                queue.extend(nexts.iter().copied());
+            }
+        }
+    }
+
+    fn check_steps(&mut self, steps: &[Step]) {
+        for step in steps {
+            match &step.next {
+                Some(next) => {
+                    if !self.steps.contains(next) {
+                        self.push(
+                            Severity::Error,
+                            &step.id,
+                            format!("the next step '{next}' is not found"),
+                        );
+                    }
+                }
+                None => {
+                    if !step.branches.is_empty() {
+                        self.check_branches(step);
+                    }
+                }
+            }
+
+            self.check_outputs(&step.id, &step.outputs);
+            if let Some(run) = &step.run {
+                self.check_back(&step.id, run);
+            }
+        }
+    }
+
+    fn check_branches(&mut self, step: &Step) {
+        if step.join.is_none() && !step.branches.iter().any(|b| b.r#else) {
+            self.push(
+                Severity::Warning,
+                &step.id,
+                "the step has branches but no else branch".to_string(),
+            );
+        }
+
+        let ids: HashSet<&str> = step.branches.iter().map(|b| b.id.as_str()).collect();
+        for branch in &step.branches {
+            for need in &branch.needs {
+                if !ids.contains(need.as_str()) {
+                    self.push(
+                        Severity::Error,
+                        &branch.id,
+                        format!("the needed branch '{need}' is not found"),
+                    );
+                }
+            }
+            if is_circular(&step.branches, branch) {
+                self.push(
+                    Severity::Error,
+                    &branch.id,
+                    "the branch needs are circular".to_string(),
+                );
+            }
+
+            self.check_steps(&branch.steps);
+            self.check_outputs(&branch.id, &branch.outputs);
+            if let Some(run) = &branch.run {
+                self.check_back(&branch.id, run);
+            }
+        }
+    }
+
+    fn check_outputs(&mut self, node: &str, outputs: &Vars) {
+        for (key, value) in outputs {
+            if value.is_null() && !self.produced.contains(key) {
+                self.push(
+                    Severity::Warning,
+                    node,
+                    format!("the outputs key '{key}' is never produced"),
+                );
+            }
+        }
+    }
+
+    fn check_rets(&mut self, node: &str, rets: &Vars) {
+        for (key, value) in rets {
+            if value.is_null() && !self.produced.contains(key) {
+                self.push(
+                    Severity::Warning,
+                    node,
+                    format!("the rets key '{key}' is never produced"),
+                );
+            }
+        }
+    }
+
+    fn check_back(&mut self, node: &str, script: &str) {
+        let re =
+            BACK.get_or_init(|| Regex::new(r#"act\.back\(\s*["'`]([^"'`]+)["'`]\s*\)"#).unwrap());
+        for cap in re.captures_iter(script) {
+            let target = &cap[1];
+            if !self.steps.iter().any(|id| id == target) {
+                self.push(
+                    Severity::Error,
+                    node,
+                    format!("the back step '{target}' is not found"),
+                );
+            }
+        }
+    }
+}
+
+fn collect_edges<'a>(steps: &'a [Step], edges: &mut HashMap<&'a str, Vec<&'a str>>) {
+    for (index, step) in steps.iter().enumerate() {
+        let nexts = edges.entry(step.id.as_str()).or_default();
+        match &step.next {
+            Some(next) => nexts.push(next),
+            None => {
+                nexts.extend(steps.get(index + 1).map(|s| s.id.as_str()));
+                nexts.extend(
+                    step.branches
+                        .iter()
+                        .filter_map(|b| b.steps.first())
+                        .map(|s| s.id.as_str()),
+                );
+            }
+        }
+        for branch in &step.branches {
+            collect_edges(&branch.steps, edges);
+        }
+    }
+}
+
+fn is_circular(branches: &[Branch], start: &Branch) -> bool {
+    let mut stack: Vec<&str> = start.needs.iter().map(|n| n.as_str()).collect();
+    let mut visited = HashSet::new();
+    while let Some(id) = stack.pop() {
+        if id == start.id {
+            return true;
+        }
+        if !visited.insert(id) {
+            continue;
+        }
+        if let Some(b) = branches.iter().find(|b| b.id == id) {
+            stack.extend(b.needs.iter().map(|n| n.as_str()));
+        }
+    }
+
+    false
+}
@@ -1,19 +1,34 @@
 mod act;
 mod action_result;
 mod branch;
+mod calendar;
+mod fragment;
 mod info;
+mod lint;
+mod schema;
 mod step;
+mod trigger;
 mod vars;
 mod workflow;
 
 #[cfg(test)]
 mod tests;
 
-pub use act::{Act, Block, Call, Chain, Cmd, Each, If, Msg, Pack, Req};
+pub use act::{
+    Act, Block, Call, Chain, Cmd, Delay, Each, EachMode, If, Loop, Msg, Pack, Req, Wait,
+};
 pub use action_result::ActionResult;
 pub use branch::Branch;
-pub use info::{ModelInfo, PackageInfo, ProcInfo, TaskInfo};
-pub use step::{Catch, Step, Timeout};
+pub use calendar::Calendar;
+pub use fragment::{Fragment, Include};
+pub use info::{
+    DueInfo, MigrateChange, MigrateKind, MigrateReport, ModelInfo, PackageInfo, ProcInfo,
+    TaskInfo, TriggerInfo,
+};
+pub use lint::{Diagnostic, Severity};
+pub use schema::{Param, ParamType, Schema};
+pub use step::{Catch, Join, Limits, Retry, Step, Timeout, TimeoutLimit, TimeoutMode};
+pub use trigger::{CatchUp, Trigger};
 pub use vars::Vars;
 pub use workflow::Workflow;
 
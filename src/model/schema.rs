@@ -0,0 +1,180 @@
+use crate::{ActError, Result, Vars};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::{collections::BTreeMap, fmt};
+
+/// The typed schema of the workflow inputs and outputs
+///
+/// # Example
+/// ```yaml
+/// schema:
+///   inputs:
+///     days:
+///       type: integer
+///       required: true
+///       description: the leave days
+///     kind:
+///       type: string
+///       enum: [annual, sick]
+///       default: annual
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Schema {
+    #[serde(default)]
+    pub inputs: BTreeMap<String, Param>,
+
+    #[serde(default)]
+    pub outputs: BTreeMap<String, Param>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamType {
+    String,
+    Number,
+    Integer,
+    Boolean,
+    Object,
+    Array,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Param {
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<ParamType>,
+
+    #[serde(default)]
+    pub required: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<JsonValue>,
+
+    #[serde(default, rename = "enum", skip_serializing_if = "Vec::is_empty")]
+    pub r#enum: Vec<JsonValue>,
+
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+}
+
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParamType::String => "string",
+            ParamType::Number => "number",
+            ParamType::Integer => "integer",
+            ParamType::Boolean => "boolean",
+            ParamType::Object => "object",
+            ParamType::Array => "array",
+        })
+    }
+}
+
+impl ParamType {
+    pub fn is_match(&self, value: &JsonValue) -> bool {
+        match self {
+            ParamType::String => value.is_string(),
+            ParamType::Number => value.is_number(),
+            ParamType::Integer => value.is_i64() || value.is_u64(),
+            ParamType::Boolean => value.is_boolean(),
+            ParamType::Object => value.is_object(),
+            ParamType::Array => value.is_array(),
+        }
+    }
+}
+
+impl Schema {
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty() && self.outputs.is_empty()
+    }
+
+    /// validate the inputs and fill the default values
+    /// the keys which are not in the schema are kept as they are
+    pub fn check_inputs(&self, vars: &mut Vars) -> Result<()> {
+        for (key, param) in &self.inputs {
+            match vars.get_value(key).filter(|v| !v.is_null()) {
+                Some(value) => param.check("input", key, value)?,
+                None => {
+                    if let Some(default) = &param.default {
+                        vars.insert(key.to_string(), default.clone());
+                    } else if param.required {
+                        return Err(ActError::Action(format!("the input '{key}' is required")));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// validate the outputs when the proc completes
+    /// the default value is not used and a required output must be produced
+    pub fn check_outputs(&self, vars: &Vars) -> Result<()> {
+        for (key, param) in &self.outputs {
+            match vars.get_value(key).filter(|v| !v.is_null()) {
+                Some(value) => param.check("output", key, value)?,
+                None => {
+                    if param.required {
+                        return Err(ActError::Action(format!("the output '{key}' is required")));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Param {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_type(mut self, t: ParamType) -> Self {
+        self.r#type = Some(t);
+        self
+    }
+
+    pub fn with_required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    pub fn with_default(mut self, value: JsonValue) -> Self {
+        self.default = Some(value);
+        self
+    }
+
+    pub fn with_enum(mut self, values: &[JsonValue]) -> Self {
+        self.r#enum = values.to_vec();
+        self
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    fn check(&self, kind: &str, key: &str, value: &JsonValue) -> Result<()> {
+        if let Some(t) = &self.r#type {
+            if !t.is_match(value) {
+                return Err(ActError::Action(format!(
+                    "the {kind} '{key}' expects type '{t}' but got '{value}'"
+                )));
+            }
+        }
+
+        if !self.r#enum.is_empty() && !self.r#enum.contains(value) {
+            let values = self
+                .r#enum
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(ActError::Action(format!(
+                "the {kind} '{key}' expects one of [{values}] but got '{value}'"
+            )));
+        }
+
+        Ok(())
+    }
+}
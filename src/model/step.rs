@@ -1,12 +1,18 @@
 mod catch;
+mod join;
+mod limits;
+mod retry;
 mod timeout;
 
 #[allow(unused_imports)]
 pub use self::{
     catch::Catch,
-    timeout::{Timeout, TimeoutLimit, TimeoutUnit},
+    join::Join,
+    limits::Limits,
+    retry::Retry,
+    timeout::{Timeout, TimeoutLimit, TimeoutMode, TimeoutUnit},
 };
-use crate::{model::Branch, Act, ModelBase, Vars};
+use crate::{model::Branch, Act, Include, ModelBase, Vars};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
@@ -51,8 +57,27 @@ pub struct Step {
     #[serde(default)]
     pub timeout: Vec<Timeout>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<Retry>,
+
+    /// the limits of the scripts in the step
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<Limits>,
+
     #[serde(default)]
     pub setup: Vec<Act>,
+
+    /// the join policy to run the matched branches in parallel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub join: Option<Join>,
+
+    /// the acts to undo the side effects when the proc is aborted or failed
+    #[serde(default)]
+    pub compensate: Vec<Act>,
+
+    /// the step fragment to expand when deploying
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include: Option<Include>,
 }
 
 impl ModelBase for Step {
@@ -121,6 +146,11 @@ impl Step {
         self
     }
 
+    pub fn with_join(mut self, join: Join) -> Self {
+        self.join = Some(join);
+        self
+    }
+
     pub fn with_catch(mut self, build: fn(Catch) -> Catch) -> Self {
         let catch = Catch::default();
         self.catches.push(build(catch));
@@ -133,9 +163,30 @@ impl Step {
         self
     }
 
+    pub fn with_retry(mut self, build: fn(Retry) -> Retry) -> Self {
+        self.retry = Some(build(Retry::default()));
+        self
+    }
+
+    pub fn with_limits(mut self, build: fn(Limits) -> Limits) -> Self {
+        self.limits = Some(build(Limits::default()));
+        self
+    }
+
     pub fn with_setup(mut self, build: fn(Vec<Act>) -> Vec<Act>) -> Self {
         let stmts = Vec::new();
         self.setup = build(stmts);
         self
     }
+
+    pub fn with_compensate(mut self, build: fn(Vec<Act>) -> Vec<Act>) -> Self {
+        let stmts = Vec::new();
+        self.compensate = build(stmts);
+        self
+    }
+
+    pub fn with_include(mut self, build: fn(Include) -> Include) -> Self {
+        self.include = Some(build(Include::default()));
+        self
+    }
 }
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// The join policy of the step branches
+///
+/// all of the matched branches run in parallel, and the step goes next
+/// when the policy is satisfied, the branches still running are cancelled
+///
+/// # Example
+/// ```yaml
+/// join: any
+/// join: !count 2
+/// join: !expr joined >= total - 1
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Join {
+    /// wait for all of the branches
+    #[serde(rename = "all")]
+    All,
+
+    /// join when any of the branches is completed
+    #[serde(rename = "any")]
+    Any,
+
+    /// join when the count of the completed branches reaches the number
+    #[serde(rename = "count")]
+    Count(usize),
+
+    /// join when the expression is true
+    /// the `joined` and `total` variables are the count of the completed and running branches
+    #[serde(rename = "expr")]
+    Expr(String),
+}
@@ -0,0 +1,43 @@
+use crate::TimeoutLimit;
+use serde::{Deserialize, Serialize};
+
+/// The time and memory limits for evaluating a script
+///
+/// the unset limits fall back to the ones of the parent step, the package
+/// and the `script_timeout` and `script_memory` in config
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Limits {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<TimeoutLimit>,
+
+    /// the max bytes a script can allocate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<usize>,
+}
+
+impl Limits {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_timeout(mut self, v: &str) -> Self {
+        self.timeout = Some(
+            TimeoutLimit::parse(v)
+                .unwrap_or_else(|_| panic!("failed with error format '{v}' for 'timeout' ")),
+        );
+        self
+    }
+
+    pub fn with_memory(mut self, bytes: usize) -> Self {
+        self.memory = Some(bytes);
+        self
+    }
+
+    /// fill the unset limits by the other ones
+    pub fn or(&self, other: &Limits) -> Limits {
+        Limits {
+            timeout: self.timeout.clone().or(other.timeout.clone()),
+            memory: self.memory.or(other.memory),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::TimeoutLimit;
+use serde::{Deserialize, Serialize};
+
+/// Re-run the failed task before falling through to the `catches`
+///
+/// the n-th retry waits for `backoff * 2^(n-1)`, which is limited by `max_delay`.
+/// only the errors with the ecodes in `on` are retried, or all errors when it is empty
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Retry {
+    #[serde(default)]
+    pub times: u32,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backoff: Option<TimeoutLimit>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_delay: Option<TimeoutLimit>,
+
+    #[serde(default)]
+    pub on: Vec<String>,
+}
+
+impl Retry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_times(mut self, times: u32) -> Self {
+        self.times = times;
+        self
+    }
+
+    pub fn with_backoff(mut self, v: &str) -> Self {
+        self.backoff = Some(
+            TimeoutLimit::parse(v)
+                .unwrap_or_else(|_| panic!("failed with error format '{v}' for 'backoff' ")),
+        );
+        self
+    }
+
+    pub fn with_max_delay(mut self, v: &str) -> Self {
+        self.max_delay = Some(
+            TimeoutLimit::parse(v)
+                .unwrap_or_else(|_| panic!("failed with error format '{v}' for 'max_delay' ")),
+        );
+        self
+    }
+
+    pub fn with_on(mut self, ecode: &str) -> Self {
+        self.on.push(ecode.to_string());
+        self
+    }
+
+    /// check if the error code is allowed to retry
+    pub fn is_match(&self, ecode: &str) -> bool {
+        self.on.is_empty() || self.on.iter().any(|v| v == ecode)
+    }
+
+    /// the delay in millis before the attempt which starts from 1
+    pub fn delay(&self, attempt: u32) -> i64 {
+        let backoff = self
+            .backoff
+            .as_ref()
+            .map(|v| v.as_millis())
+            .unwrap_or_default();
+        let factor = 1_i64 << attempt.saturating_sub(1).min(30);
+        let delay = backoff.saturating_mul(factor);
+        match &self.max_delay {
+            Some(max) => delay.min(max.as_millis()),
+            None => delay,
+        }
+    }
+}
@@ -1,23 +1,59 @@
 use crate::{Act, ActError, Result};
 use regex::Regex;
 use serde::{de, Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 use std::str::FromStr;
 
+/// Check the task time and run the `then` acts when it is timeout
+///
+/// `on` is the duration since the task started, `until` is an absolute deadline
+/// which is a millis timestamp, an RFC 3339 date or a `${ }` expression that returns
+/// one of them. set `every` to repeat the timeout as a reminder at most `max` times,
+/// and set `calendar` to count the durations in the working time of the named calendar
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Timeout {
     #[serde(default)]
     pub on: TimeoutLimit,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<JsonValue>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub every: Option<TimeoutLimit>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<u32>,
+
+    #[serde(default)]
+    pub mode: TimeoutMode,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calendar: Option<String>,
+
     #[serde(default)]
     pub then: Vec<Act>,
 }
 
+/// What happens to the task after the timeout runs its `then` acts
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeoutMode {
+    /// only run the `then` acts, the task keeps waiting
+    #[default]
+    NonInterrupting,
+    /// end the task and cancel its unfinished children
+    Interrupting,
+}
+
 #[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Clone)]
 pub enum TimeoutUnit {
+    Millisecond,
     #[default]
     Second,
     Minute,
     Hour,
     Day,
+    Week,
 }
 
 #[derive(Debug, Default, PartialEq, Clone)]
@@ -40,10 +76,12 @@ impl std::fmt::Display for TimeoutLimit {
             "{}{}",
             self.value,
             match self.unit {
+                TimeoutUnit::Millisecond => "ms",
                 TimeoutUnit::Second => "s",
                 TimeoutUnit::Minute => "m",
                 TimeoutUnit::Hour => "h",
                 TimeoutUnit::Day => "d",
+                TimeoutUnit::Week => "w",
             }
         ))
     }
@@ -82,8 +120,21 @@ impl Serialize for TimeoutLimit {
 }
 
 impl TimeoutLimit {
+    pub fn from_millis(value: i64) -> Self {
+        Self {
+            value,
+            unit: TimeoutUnit::Millisecond,
+        }
+    }
+
+    /// parse the limit from `<int><unit>` with the units `ms`, `s`, `m`, `h`, `d` and `w`,
+    /// or from an ISO-8601 duration such as `PT30M` or `P1DT12H`
     pub fn parse(expr: &str) -> Result<Self> {
-        let re = Regex::new(r"^(.*)(s|m|h|d)$").unwrap();
+        if expr.starts_with('P') {
+            return Self::parse_iso(expr);
+        }
+
+        let re = Regex::new(r"^(.*?)(ms|s|m|h|d|w)$").unwrap();
         let caps = re.captures(&expr);
 
         if let Some(caps) = caps {
@@ -104,27 +155,94 @@ impl TimeoutLimit {
     }
 
     pub fn as_secs(&self) -> i64 {
-        match self.unit {
-            TimeoutUnit::Second => self.value,
-            TimeoutUnit::Minute => self.value * 60,
-            TimeoutUnit::Hour => self.value * 60 * 60,
-            TimeoutUnit::Day => self.value * 60 * 60 * 24,
+        self.as_millis() / 1000
+    }
+
+    pub fn as_millis(&self) -> i64 {
+        self.value * self.unit.millis()
+    }
+
+    /// years and months are not supported because they have no fixed length
+    fn parse_iso(expr: &str) -> Result<Self> {
+        let re = Regex::new(
+            r"^P(?:(\d+)W)?(?:(\d+)D)?(?:T(?:(\d+)H)?(?:(\d+)M)?(?:(\d+(?:\.\d{1,3})?)S)?)?$",
+        )
+        .unwrap();
+        let caps = re
+            .captures(expr)
+            .filter(|_| expr != "P" && !expr.ends_with('T'))
+            .ok_or(ActError::Model(format!(
+                "timeout parse error with '{expr}'"
+            )))?;
+
+        let units = [
+            TimeoutUnit::Week,
+            TimeoutUnit::Day,
+            TimeoutUnit::Hour,
+            TimeoutUnit::Minute,
+        ];
+        let mut millis = 0;
+        for (index, unit) in units.iter().enumerate() {
+            if let Some(m) = caps.get(index + 1) {
+                let value = m
+                    .as_str()
+                    .parse::<i64>()
+                    .map_err(|err| ActError::Model(format!("timeout parse error with '{err}'")))?;
+                millis += value * unit.millis();
+            }
+        }
+        if let Some(m) = caps.get(5) {
+            let value = m
+                .as_str()
+                .parse::<f64>()
+                .map_err(|err| ActError::Model(format!("timeout parse error with '{err}'")))?;
+            millis += (value * 1000.0).round() as i64;
         }
+
+        // keep the largest unit that still holds the exact value
+        let unit = [
+            TimeoutUnit::Week,
+            TimeoutUnit::Day,
+            TimeoutUnit::Hour,
+            TimeoutUnit::Minute,
+            TimeoutUnit::Second,
+        ]
+        .into_iter()
+        .find(|unit| millis % unit.millis() == 0)
+        .unwrap_or(TimeoutUnit::Millisecond);
+
+        Ok(Self {
+            value: millis / unit.millis(),
+            unit,
+        })
     }
 }
 
 impl TimeoutUnit {
     fn parse(expr: &str) -> Result<Self> {
         match expr {
+            "ms" => Ok(TimeoutUnit::Millisecond),
             "s" => Ok(TimeoutUnit::Second),
             "m" => Ok(TimeoutUnit::Minute),
             "h" => Ok(TimeoutUnit::Hour),
             "d" => Ok(TimeoutUnit::Day),
+            "w" => Ok(TimeoutUnit::Week),
             _ => Err(ActError::Model(format!(
                 "timeout parse error with '{expr}'"
             ))),
         }
     }
+
+    fn millis(&self) -> i64 {
+        match self {
+            TimeoutUnit::Millisecond => 1,
+            TimeoutUnit::Second => 1000,
+            TimeoutUnit::Minute => 60 * 1000,
+            TimeoutUnit::Hour => 60 * 60 * 1000,
+            TimeoutUnit::Day => 24 * 60 * 60 * 1000,
+            TimeoutUnit::Week => 7 * 24 * 60 * 60 * 1000,
+        }
+    }
 }
 
 impl Timeout {
@@ -137,6 +255,45 @@ impl Timeout {
         self
     }
 
+    pub fn with_until<T: Into<JsonValue>>(mut self, until: T) -> Self {
+        self.until = Some(until.into());
+        self
+    }
+
+    pub fn with_every(mut self, v: &str) -> Self {
+        self.every = Some(
+            TimeoutLimit::parse(v)
+                .unwrap_or_else(|_| panic!("failed with error format '{v}' for 'every' ")),
+        );
+        self
+    }
+
+    pub fn with_max(mut self, max: u32) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    pub fn with_mode(mut self, mode: TimeoutMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_calendar(mut self, name: &str) -> Self {
+        self.calendar = Some(name.to_string());
+        self
+    }
+
+    /// the key to save the fired count in the task data
+    pub(crate) fn key(&self) -> String {
+        match &self.until {
+            Some(until) => until
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or(until.to_string()),
+            None => self.on.to_string(),
+        }
+    }
+
     pub fn with_then(mut self, build: fn(Vec<Act>) -> Vec<Act>) -> Self {
         let stmts = Vec::new();
         self.then = build(stmts);
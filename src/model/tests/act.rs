@@ -1,15 +1,18 @@
 mod block;
 mod chain;
 mod cmd;
+mod delay;
 mod each;
 mod expose;
 mod hooks;
 mod r#if;
+mod r#loop;
 mod msg;
 
 mod call;
 mod req;
 mod set;
+mod wait;
 
 use crate::Act;
 
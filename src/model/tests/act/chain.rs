@@ -28,3 +28,32 @@ fn model_act_chain_run() {
     let act = Chain::new().with_run(|stmts| stmts.add(Act::set(Vars::new())));
     assert_eq!(act.run.len(), 1);
 }
+
+#[test]
+fn model_act_chain_parse_items() {
+    let text = r#"
+    !chain
+    in:
+      - uid: u1
+        role: admin
+    item: user
+    index: i
+    run:
+      - !req
+        id: act1
+    "#;
+    if let Act::Chain(stmt) = serde_yaml::from_str(text).unwrap() {
+        assert_eq!(stmt.r#in, r#"[{"role":"admin","uid":"u1"}]"#);
+        assert_eq!(stmt.item.unwrap(), "user");
+        assert_eq!(stmt.index.unwrap(), "i");
+    } else {
+        panic!("expect chain act");
+    }
+}
+
+#[test]
+fn model_act_chain_item_index() {
+    let act = Chain::new().with_item("user").with_index("i");
+    assert_eq!(act.item.as_deref(), Some("user"));
+    assert_eq!(act.index.as_deref(), Some("i"));
+}
@@ -0,0 +1,62 @@
+use crate::{Act, Delay};
+use serde_json::json;
+
+#[test]
+fn model_act_delay_parse_duration() {
+    let text = r#"
+    !delay
+    id: delay1
+    duration: 2h
+    "#;
+    if let Act::Delay(stmt) = serde_yaml::from_str(text).unwrap() {
+        assert_eq!(stmt.id, "delay1");
+        assert_eq!(stmt.duration.unwrap(), "2h");
+        assert!(stmt.until.is_none());
+    } else {
+        panic!("expect delay act");
+    }
+}
+
+#[test]
+fn model_act_delay_parse_until() {
+    let text = r#"
+    !delay
+    until: 1704067200000
+    "#;
+    if let Act::Delay(stmt) = serde_yaml::from_str(text).unwrap() {
+        assert_eq!(stmt.until.unwrap(), json!(1704067200000_i64));
+    } else {
+        panic!("expect delay act");
+    }
+}
+
+#[test]
+fn model_act_delay_parse_until_expr() {
+    let text = r#"
+    !delay
+    until: ${ $("due_time") }
+    "#;
+    if let Act::Delay(stmt) = serde_yaml::from_str(text).unwrap() {
+        assert_eq!(stmt.until.unwrap(), json!(r#"${ $("due_time") }"#));
+    } else {
+        panic!("expect delay act");
+    }
+}
+
+#[test]
+fn model_act_delay_builder() {
+    let act = Delay::new()
+        .with_id("delay1")
+        .with_duration("10m")
+        .with_until("2024-01-01T00:00:00Z");
+    assert_eq!(act.id, "delay1");
+    assert_eq!(act.duration.unwrap(), "10m");
+    assert_eq!(act.until.unwrap(), json!("2024-01-01T00:00:00Z"));
+}
+
+#[test]
+fn model_act_delay_kind() {
+    let act = Act::delay(|act| act.with_duration("1s"));
+    assert_eq!(act.kind(), "delay");
+    assert!(act.is_taskable());
+}
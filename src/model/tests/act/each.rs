@@ -1,4 +1,4 @@
-use crate::{Act, Each, StmtBuild, Vars};
+use crate::{Act, Each, EachMode, StmtBuild, Vars};
 
 #[test]
 fn model_act_each_parse() {
@@ -28,3 +28,69 @@ fn model_act_each_run() {
     let act = Each::new().with_run(|stmts| stmts.add(Act::set(Vars::new())));
     assert_eq!(act.run.len(), 1);
 }
+
+#[test]
+fn model_act_each_parse_until() {
+    let text = r#"
+    !each
+    in: "[\"a\", \"b\"]"
+    until: completed / total >= 0.6
+    mode: sequential
+    run:
+      - !req
+        id: act1
+    "#;
+    if let Act::Each(stmt) = serde_yaml::from_str(text).unwrap() {
+        assert_eq!(stmt.until.unwrap(), "completed / total >= 0.6");
+        assert_eq!(stmt.mode, EachMode::Sequential);
+    } else {
+        panic!("expect each act");
+    }
+}
+
+#[test]
+fn model_act_each_mode() {
+    let act = Each::new();
+    assert_eq!(act.mode, EachMode::Parallel);
+    assert!(!act.is_taskable());
+
+    let act = Each::new().with_mode(EachMode::Sequential);
+    assert_eq!(act.mode, EachMode::Sequential);
+    assert!(act.is_taskable());
+}
+
+#[test]
+fn model_act_each_until() {
+    let act = Each::new().with_until("completed >= 1");
+    assert_eq!(act.until.as_deref(), Some("completed >= 1"));
+    assert!(act.is_taskable());
+}
+
+#[test]
+fn model_act_each_parse_items() {
+    let text = r#"
+    !each
+    in:
+      a: 1
+      b: 2
+    item: value
+    index: key
+    run:
+      - !req
+        id: act1
+    "#;
+    if let Act::Each(stmt) = serde_yaml::from_str(text).unwrap() {
+        assert_eq!(stmt.r#in, r#"{"a":1,"b":2}"#);
+        assert_eq!(stmt.item.unwrap(), "value");
+        assert_eq!(stmt.index.unwrap(), "key");
+    } else {
+        panic!("expect each act");
+    }
+}
+
+#[test]
+fn model_act_each_item_index() {
+    let act = Each::new().with_item("user").with_index("i");
+    assert_eq!(act.item.as_deref(), Some("user"));
+    assert_eq!(act.index.as_deref(), Some("i"));
+}
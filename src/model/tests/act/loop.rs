@@ -0,0 +1,63 @@
+use crate::{Act, Loop, StmtBuild, Vars};
+
+#[test]
+fn model_act_loop_parse() {
+    let text = r#"
+    !loop
+    until: $("loop_index") >= 3
+    max: 5
+    run:
+      - !msg
+        id: msg1
+    "#;
+    if let Act::Loop(stmt) = serde_yaml::from_str(text).unwrap() {
+        assert_eq!(stmt.until.unwrap(), r#"$("loop_index") >= 3"#);
+        assert_eq!(stmt.max, 5);
+        assert_eq!(stmt.run.len(), 1);
+    } else {
+        panic!("expect loop act");
+    }
+}
+
+#[test]
+fn model_act_loop_parse_max_required() {
+    let text = r#"
+    !loop
+    while: "true"
+    run:
+      - !msg
+        id: msg1
+    "#;
+    let ret = serde_yaml::from_str::<Act>(text);
+    assert!(ret.is_err());
+}
+
+#[test]
+fn model_act_loop_while() {
+    let act = Loop::new().with_while(r#"$("a") > 0"#);
+    assert_eq!(act.r#while.unwrap(), r#"$("a") > 0"#);
+}
+
+#[test]
+fn model_act_loop_until() {
+    let act = Loop::new().with_until(r#"$("a") > 0"#);
+    assert_eq!(act.until.unwrap(), r#"$("a") > 0"#);
+}
+
+#[test]
+fn model_act_loop_max() {
+    let act = Loop::new().with_max(10);
+    assert_eq!(act.max, 10);
+}
+
+#[test]
+fn model_act_loop_index() {
+    let act = Loop::new().with_index("i");
+    assert_eq!(act.index.unwrap(), "i");
+}
+
+#[test]
+fn model_act_loop_run() {
+    let act = Loop::new().with_run(|stmts| stmts.add(Act::set(Vars::new())));
+    assert_eq!(act.run.len(), 1);
+}
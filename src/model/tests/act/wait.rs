@@ -0,0 +1,49 @@
+use crate::{Act, Wait};
+
+#[test]
+fn model_act_wait_parse() {
+    let text = r#"
+    !wait
+    id: wait1
+    signal: order_paid
+    key: order:${ $("order_id") }
+    "#;
+    if let Act::Wait(stmt) = serde_yaml::from_str(text).unwrap() {
+        assert_eq!(stmt.id, "wait1");
+        assert_eq!(stmt.signal, "order_paid");
+        assert_eq!(stmt.key, r#"order:${ $("order_id") }"#);
+    } else {
+        panic!("expect wait act");
+    }
+}
+
+#[test]
+fn model_act_wait_parse_signal_required() {
+    let text = r#"
+    !wait
+    key: order:1
+    "#;
+    let ret = serde_yaml::from_str::<Act>(text);
+    assert!(ret.is_err());
+}
+
+#[test]
+fn model_act_wait_builder() {
+    let act = Wait::new()
+        .with_id("wait1")
+        .with_signal("order_paid")
+        .with_key("order:1")
+        .with_timeout(|t| t.with_on("1d"));
+    assert_eq!(act.id, "wait1");
+    assert_eq!(act.signal, "order_paid");
+    assert_eq!(act.key, "order:1");
+    assert_eq!(act.timeout.len(), 1);
+}
+
+#[test]
+fn model_act_wait_kind() {
+    let act = Act::wait(|act| act.with_signal("order_paid"));
+    assert_eq!(act.kind(), "wait");
+    assert_eq!(act.name(), "order_paid");
+    assert!(act.is_taskable());
+}
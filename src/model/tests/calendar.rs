@@ -0,0 +1,155 @@
+use crate::Calendar;
+use chrono::{DateTime, TimeZone, Utc};
+
+const HOUR: i64 = 3600 * 1000;
+
+fn millis(time: &str) -> i64 {
+    DateTime::parse_from_rfc3339(time)
+        .unwrap()
+        .timestamp_millis()
+}
+
+#[test]
+fn model_calendar_add_in_hours() {
+    let calendar = Calendar::new().with_name("office");
+
+    // 2026-10-12 is Monday
+    let start = millis("2026-10-12T10:00:00Z");
+    assert_eq!(
+        calendar.add(start, 2 * HOUR).unwrap(),
+        millis("2026-10-12T12:00:00Z")
+    );
+    assert_eq!(calendar.add(start, 0).unwrap(), start);
+}
+
+#[test]
+fn model_calendar_add_next_day() {
+    let calendar = Calendar::new().with_name("office");
+
+    let start = millis("2026-10-12T15:00:00Z");
+    assert_eq!(
+        calendar.add(start, 4 * HOUR).unwrap(),
+        millis("2026-10-13T11:00:00Z")
+    );
+
+    // start before the working hours
+    let start = millis("2026-10-12T06:00:00Z");
+    assert_eq!(
+        calendar.add(start, HOUR).unwrap(),
+        millis("2026-10-12T10:00:00Z")
+    );
+}
+
+#[test]
+fn model_calendar_add_weekend_and_holiday() {
+    let calendar = Calendar::new()
+        .with_name("office")
+        .with_holiday("2026-10-19");
+
+    // Friday 16:00 + 2h skips the weekend and the Monday holiday
+    let start = millis("2026-10-16T16:00:00Z");
+    assert_eq!(
+        calendar.add(start, 2 * HOUR).unwrap(),
+        millis("2026-10-20T10:00:00Z")
+    );
+}
+
+#[test]
+fn model_calendar_add_with_hours_and_timezone() {
+    let calendar = Calendar::new()
+        .with_name("office")
+        .with_timezone("Asia/Shanghai")
+        .with_workdays(&["Mon", "Tue", "Wed", "Thu", "Fri", "Sat"])
+        .with_hours(&["09:00-12:00", "13:00-18:00"]);
+
+    // 2026-10-17 11:00 in Shanghai is Saturday, 1h before the lunch
+    let start = chrono_tz::Asia::Shanghai
+        .with_ymd_and_hms(2026, 10, 17, 11, 0, 0)
+        .unwrap()
+        .timestamp_millis();
+    let due = calendar.add(start, 2 * HOUR).unwrap();
+    let expect = chrono_tz::Asia::Shanghai
+        .with_ymd_and_hms(2026, 10, 17, 14, 0, 0)
+        .unwrap()
+        .timestamp_millis();
+    assert_eq!(due, expect);
+}
+
+#[test]
+fn model_calendar_elapsed() {
+    let calendar = Calendar::new().with_name("office");
+
+    let start = millis("2026-10-16T16:00:00Z");
+    let end = millis("2026-10-19T10:30:00Z");
+    assert_eq!(calendar.elapsed(start, end).unwrap(), 2 * HOUR + HOUR / 2);
+    assert_eq!(calendar.elapsed(end, start).unwrap(), 0);
+}
+
+#[test]
+fn model_calendar_hours_overlapped() {
+    let calendar = Calendar::new()
+        .with_name("office")
+        .with_hours(&["09:00-12:00", "11:00-18:00"]);
+    assert!(calendar.valid().is_err());
+    assert!(calendar.add(0, HOUR).is_err());
+}
+
+#[test]
+fn model_calendar_hours_not_in_order() {
+    let calendar = Calendar::new()
+        .with_name("office")
+        .with_hours(&["13:00-18:00", "09:00-12:00"]);
+    assert!(calendar.valid().is_err());
+}
+
+#[test]
+fn model_calendar_from_yml() {
+    let text = r#"
+    name: office
+    timezone: Europe/Berlin
+    workdays: [Mon, Tue, Wed, Thu]
+    hours:
+      - 08:00-16:00
+    holidays:
+      - 2026-12-24
+    "#;
+    let calendar = Calendar::from_yml(text).unwrap();
+    assert_eq!(calendar.name, "office");
+    assert_eq!(calendar.workdays.len(), 4);
+    assert_eq!(calendar.hours, ["08:00-16:00"]);
+    assert_eq!(calendar.holidays, ["2026-12-24"]);
+}
+
+#[test]
+fn model_calendar_invalid() {
+    assert!(Calendar::new().valid().is_err());
+
+    let calendar = Calendar::new().with_name("c1");
+    assert!(calendar.clone().with_timezone("Mars/Base").valid().is_err());
+    assert!(calendar
+        .clone()
+        .with_workdays(&["Someday"])
+        .valid()
+        .is_err());
+    assert!(calendar
+        .clone()
+        .with_hours(&["18:00-09:00"])
+        .valid()
+        .is_err());
+    assert!(calendar.clone().with_holiday("2026-13-01").valid().is_err());
+    assert!(calendar.valid().is_ok());
+}
+
+#[test]
+fn model_calendar_no_working_time() {
+    let mut calendar = Calendar::new().with_name("c1");
+    calendar.holidays = (0..4000)
+        .map(|day| {
+            (Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap() + chrono::Days::new(day))
+                .format("%Y-%m-%d")
+                .to_string()
+        })
+        .collect();
+    let start = millis("2026-01-01T00:00:00Z");
+    assert!(calendar.add(start, HOUR).is_err());
+}
@@ -0,0 +1,87 @@
+use crate::{Act, Include, Workflow};
+
+#[test]
+fn model_fragment_yml() {
+    let text = r#"
+    id: m1
+    fragments:
+      - id: approval
+        inputs:
+          role: admin
+        step:
+          name: approval
+          acts:
+            - !req
+              id: approve
+      - id: notify
+        acts:
+          - !msg
+            id: notify
+    steps:
+      - id: step1
+        include:
+          uses: approval
+          inputs:
+            role: manager
+      - id: step2
+        acts:
+          - !include
+            id: notify1
+            uses: fragments/notify@2
+    "#;
+    let workflow = Workflow::from_yml(text).unwrap();
+    assert_eq!(workflow.fragments.len(), 2);
+    assert_eq!(
+        workflow.fragments[0].inputs.get::<String>("role").unwrap(),
+        "admin"
+    );
+    assert!(workflow.fragments[0].step.is_some());
+    assert_eq!(workflow.fragments[1].acts.len(), 1);
+
+    let include = workflow.steps[0].include.as_ref().unwrap();
+    assert_eq!(include.uses, "approval");
+    assert_eq!(include.inputs.get::<String>("role").unwrap(), "manager");
+
+    let Act::Include(include) = &workflow.steps[1].acts[0] else {
+        panic!("the act should be include");
+    };
+    assert_eq!(include.id, "notify1");
+    assert_eq!(include.uses, "fragments/notify@2");
+}
+
+#[test]
+fn model_include_parse() {
+    let include = Include::new().with_uses("fragments/approval@2");
+    assert_eq!(
+        include.parse().unwrap(),
+        (
+            Some("fragments".to_string()),
+            "approval".to_string(),
+            Some(2)
+        )
+    );
+
+    let include = Include::new().with_uses("fragments/approval");
+    assert_eq!(
+        include.parse().unwrap(),
+        (Some("fragments".to_string()), "approval".to_string(), None)
+    );
+
+    let include = Include::new().with_uses("approval");
+    assert_eq!(
+        include.parse().unwrap(),
+        (None, "approval".to_string(), None)
+    );
+}
+
+#[test]
+fn model_include_parse_error() {
+    assert!(Include::new().with_uses("").parse().is_err());
+    assert!(Include::new().with_uses("fragments/").parse().is_err());
+    assert!(Include::new().with_uses("/approval").parse().is_err());
+    assert!(Include::new()
+        .with_uses("fragments/approval@v2")
+        .parse()
+        .is_err());
+    assert!(Include::new().with_uses("approval@2").parse().is_err());
+}
@@ -0,0 +1,506 @@
+use crate::{Severity, Workflow};
+
+fn has(m: &Workflow, severity: Severity, node: &str, text: &str) -> bool {
+    m.lint()
+        .iter()
+        .any(|d| d.severity == severity && d.node == node && d.message.contains(text))
+}
+
+#[test]
+fn model_lint_ok() {
+    let text = r#"
+    id: m1
+    outputs:
+        a:
+    steps:
+        - id: step1
+          acts:
+            - !set
+              a: 10
+        - id: step2
+          branches:
+            - id: b1
+              if: a > 0
+              steps:
+                - id: step3
+            - id: b2
+              else: true
+              needs:
+                - b1
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(m.lint().is_empty());
+}
+
+#[test]
+fn model_lint_duplicate_id() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          acts:
+            - !req
+              id: step1
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Error, "step1", "duplicated"));
+}
+
+#[test]
+fn model_lint_duplicate_id_nested() {
+    let text = r#"
+    id: m1
+    setup:
+        - !msg
+          id: msg1
+    steps:
+        - id: step1
+          setup:
+            - !msg
+              id: msg1
+          catches:
+            - err: err1
+              then:
+                - !req
+                  id: act1
+          timeout:
+            - on: 1h
+              then:
+                - !if
+                  on: true
+                  then:
+                    - !req
+                      id: act1
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Error, "msg1", "duplicated 2 times"));
+    assert!(has(&m, Severity::Error, "act1", "duplicated 2 times"));
+}
+
+#[test]
+fn model_lint_next_not_found() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          next: step3
+        - id: step2
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Error, "step1", "'step3' is not found"));
+}
+
+#[test]
+fn model_lint_back_not_found() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+        - id: step2
+          run: act.back("step3")
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Error, "step2", "'step3' is not found"));
+}
+
+#[test]
+fn model_lint_unreachable_step() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          next: step3
+        - id: step2
+        - id: step3
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Warning, "step2", "unreachable"));
+    assert!(!has(&m, Severity::Warning, "step3", "unreachable"));
+}
+
+#[test]
+fn model_lint_next_jump_back() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          next: step3
+        - id: step2
+          next: step5
+        - id: step3
+          next: step2
+        - id: step4
+        - id: step5
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(!has(&m, Severity::Warning, "step2", "unreachable"));
+    assert!(has(&m, Severity::Warning, "step4", "unreachable"));
+    assert!(!has(&m, Severity::Warning, "step5", "unreachable"));
+}
+
+#[test]
+fn model_lint_branch_steps_unreachable() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          next: step2
+          branches:
+            - id: b1
+              else: true
+              steps:
+                - id: step3
+        - id: step2
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Warning, "step3", "unreachable"));
+}
+
+#[test]
+fn model_lint_needs_not_found() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          branches:
+            - id: b1
+              else: true
+              needs:
+                - b2
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Error, "b1", "'b2' is not found"));
+}
+
+#[test]
+fn model_lint_needs_circular() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          branches:
+            - id: b1
+              if: true
+              needs:
+                - b2
+            - id: b2
+              else: true
+              needs:
+                - b1
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Error, "b1", "circular"));
+    assert!(has(&m, Severity::Error, "b2", "circular"));
+}
+
+#[test]
+fn model_lint_no_else_branch() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          branches:
+            - id: b1
+              if: true
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Warning, "step1", "no else branch"));
+}
+
+#[test]
+fn model_lint_outputs_never_produced() {
+    let text = r#"
+    id: m1
+    outputs:
+        a:
+        b: 10
+    steps:
+        - id: step1
+          acts:
+            - !req
+              id: act1
+              rets:
+                c:
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Warning, "m1", "'a' is never produced"));
+    assert!(!has(&m, Severity::Warning, "m1", "'b'"));
+}
+
+#[test]
+fn model_lint_rets_never_produced() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          acts:
+            - !set
+              a: 10
+            - !req
+              id: act1
+              rets:
+                a:
+                b:
+                c: 10
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Warning, "act1", "'b' is never produced"));
+    assert!(!has(&m, Severity::Warning, "act1", "'a'"));
+    assert!(!has(&m, Severity::Warning, "act1", "'c'"));
+}
+
+#[test]
+fn model_lint_sort_by_severity() {
+    let text = r#"
+    id: m1
+    outputs:
+        a:
+    steps:
+        - id: step1
+          next: step2
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    let diagnostics = m.lint();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[1].severity, Severity::Warning);
+}
+
+#[test]
+fn model_scripts() {
+    let text = r#"
+    id: m1
+    inputs:
+        a: ${ 5 }
+    steps:
+        - id: step1
+          if: a > 0
+          run: console.log(a)
+          acts:
+            - !if
+              on: a > 1
+              then:
+                - !chain
+                  in: '["u1"]'
+                  run:
+                    - !msg
+                      id: msg1
+        - id: step2
+          branches:
+            - id: b1
+              if: a > 2
+              inputs:
+                b: ${ a + 1 }
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    let paths = m
+        .scripts()
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [
+            "m1.inputs.a",
+            "step1.if",
+            "step1.run",
+            "step1.acts[0].on",
+            "step1.acts[0].then[0].in",
+            "b1.if",
+            "b1.inputs.b",
+        ]
+    );
+}
+
+#[test]
+fn model_lint_join_no_else_branch() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          join: any
+          branches:
+            - id: b1
+            - id: b2
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(m.lint().is_empty());
+}
+
+#[test]
+fn model_lint_loop_max_zero() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          acts:
+            - !loop
+              id: loop1
+              max: 0
+              until: $("loop_index") > 2
+              run:
+                - !req
+                  id: act1
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    let diagnostics = m.lint();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].node, "loop1");
+    assert!(m
+        .scripts()
+        .iter()
+        .any(|(path, _)| path == "step1.acts[0].until"));
+}
+
+#[test]
+fn model_lint_trigger_invalid() {
+    let text = r#"
+    id: m1
+    triggers:
+        - id: t1
+          cron: every day
+        - id: t2
+          cron: 0 9 * * *
+          timezone: Mars/Olympus
+    steps:
+        - id: step1
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Error, "t1", "cron"));
+    assert!(has(&m, Severity::Error, "t2", "timezone"));
+}
+
+#[test]
+fn model_lint_trigger_inputs_produced() {
+    let text = r#"
+    id: m1
+    triggers:
+        - cron: 0 9 * * *
+          inputs:
+            kind: daily
+    outputs:
+        kind:
+    steps:
+        - id: step1
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(m.lint().is_empty());
+}
+
+#[test]
+fn model_lint_wait_signal_required() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          acts:
+            - !wait
+              id: wait1
+              signal: ""
+              key: order:${ $("order_id") }
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Error, "wait1", "signal"));
+    assert!(m
+        .scripts()
+        .iter()
+        .any(|(path, script)| path == "step1.acts[0].key" && script == r#"$("order_id")"#));
+}
+
+#[test]
+fn model_lint_delay() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          acts:
+            - !delay
+              id: delay1
+            - !delay
+              id: delay2
+              duration: 2 weeks
+            - !delay
+              id: delay3
+              until: ${ $("due") }
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Error, "delay1", "requires"));
+    assert!(has(&m, Severity::Error, "delay2", "duration"));
+    assert!(!m.lint().iter().any(|d| d.node == "delay3"));
+    assert!(m
+        .scripts()
+        .iter()
+        .any(|(path, _)| path == "step1.acts[2].until"));
+}
+
+#[test]
+fn model_lint_timeout() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          timeout:
+            - on: 1h
+              max: 3
+          acts:
+            - !req
+              id: act1
+              timeout:
+                - every: 0s
+                  until: ${ $("due") }
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Warning, "step1", "max"));
+    assert!(has(&m, Severity::Error, "act1", "every"));
+    assert!(m
+        .scripts()
+        .iter()
+        .any(|(path, _)| path == "step1.acts[0].timeout[0].until"));
+}
+
+#[test]
+fn model_lint_retry() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          retry:
+            times: 0
+          acts:
+            - !req
+              id: act1
+              retry:
+                backoff: 1s
+            - !req
+              id: act2
+              retry:
+                times: 2
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(has(&m, Severity::Warning, "step1", "times"));
+    assert!(has(&m, Severity::Warning, "act1", "times"));
+    assert!(!has(&m, Severity::Warning, "act2", "times"));
+}
+
+#[test]
+fn model_lint_compensate_scripts() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          acts:
+            - !req
+              id: act1
+              compensate:
+                - !msg
+                  id: undo1
+                  inputs:
+                    order: ${ $("order") }
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert!(m
+        .scripts()
+        .iter()
+        .any(|(path, _)| path == "step1.acts[0].compensate[0].inputs.order"));
+}
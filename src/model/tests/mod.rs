@@ -1,7 +1,12 @@
 mod act;
 mod action_result;
 mod branch;
+mod calendar;
+mod fragment;
+mod lint;
+mod schema;
 mod step;
+mod trigger;
 mod valid;
 mod vars;
 mod workflow;
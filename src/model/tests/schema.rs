@@ -0,0 +1,142 @@
+use crate::{Param, ParamType, Vars, Workflow};
+use serde_json::json;
+
+#[test]
+fn model_schema_parse() {
+    let text = r#"
+    id: m1
+    schema:
+        inputs:
+            days:
+                type: integer
+                required: true
+                description: the leave days
+            kind:
+                type: string
+                enum: [annual, sick]
+                default: annual
+        outputs:
+            approved:
+                type: boolean
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    let days = m.schema.inputs.get("days").unwrap();
+    assert_eq!(days.r#type, Some(ParamType::Integer));
+    assert!(days.required);
+    assert_eq!(days.description, "the leave days");
+
+    let kind = m.schema.inputs.get("kind").unwrap();
+    assert_eq!(kind.r#enum, [json!("annual"), json!("sick")]);
+    assert_eq!(kind.default, Some(json!("annual")));
+
+    let approved = m.schema.outputs.get("approved").unwrap();
+    assert_eq!(approved.r#type, Some(ParamType::Boolean));
+}
+
+#[test]
+fn model_schema_builder() {
+    let m = Workflow::new()
+        .with_input_schema("a", |p| p.with_type(ParamType::Number).with_required(true))
+        .with_output_schema("b", |p| p.with_type(ParamType::String));
+    assert_eq!(
+        m.schema.inputs.get("a"),
+        Some(
+            &Param::new()
+                .with_type(ParamType::Number)
+                .with_required(true)
+        )
+    );
+    assert_eq!(
+        m.schema.outputs.get("b").unwrap().r#type,
+        Some(ParamType::String)
+    );
+}
+
+#[test]
+fn model_schema_serialize_empty() {
+    let m = Workflow::new().with_id("m1");
+    assert!(!m.to_yml().unwrap().contains("schema"));
+}
+
+#[test]
+fn model_schema_check_required() {
+    let m = Workflow::new().with_input_schema("a", |p| p.with_required(true));
+    let mut vars = Vars::new();
+    let err = m.schema.check_inputs(&mut vars).unwrap_err();
+    assert_eq!(err.to_string(), "the input 'a' is required");
+
+    let mut vars = Vars::new().with("a", json!(null));
+    assert!(m.schema.check_inputs(&mut vars).is_err());
+}
+
+#[test]
+fn model_schema_check_default() {
+    let m =
+        Workflow::new().with_input_schema("a", |p| p.with_required(true).with_default(json!(10)));
+    let mut vars = Vars::new();
+    m.schema.check_inputs(&mut vars).unwrap();
+    assert_eq!(vars.get::<i32>("a"), Some(10));
+}
+
+#[test]
+fn model_schema_check_type() {
+    let m = Workflow::new()
+        .with_input_schema("a", |p| p.with_type(ParamType::Integer))
+        .with_input_schema("b", |p| p.with_type(ParamType::Array));
+
+    let mut vars = Vars::new().with("a", 1).with("b", json!(["u1"]));
+    assert!(m.schema.check_inputs(&mut vars).is_ok());
+
+    let mut vars = Vars::new().with("a", 1.5);
+    let err = m.schema.check_inputs(&mut vars).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the input 'a' expects type 'integer' but got '1.5'"
+    );
+
+    let mut vars = Vars::new().with("b", "u1");
+    assert!(m.schema.check_inputs(&mut vars).is_err());
+}
+
+#[test]
+fn model_schema_check_enum() {
+    let m =
+        Workflow::new().with_input_schema("a", |p| p.with_enum(&[json!("annual"), json!("sick")]));
+
+    let mut vars = Vars::new().with("a", "sick");
+    assert!(m.schema.check_inputs(&mut vars).is_ok());
+
+    let mut vars = Vars::new().with("a", "other");
+    let err = m.schema.check_inputs(&mut vars).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r#"the input 'a' expects one of ["annual", "sick"] but got '"other"'"#
+    );
+}
+
+#[test]
+fn model_schema_check_not_declared() {
+    let m = Workflow::new().with_input_schema("a", |p| p.with_type(ParamType::String));
+    let mut vars = Vars::new().with("pid", 1);
+    assert!(m.schema.check_inputs(&mut vars).is_ok());
+}
+
+#[test]
+fn model_schema_check_outputs() {
+    let m = Workflow::new()
+        .with_output_schema("a", |p| p.with_required(true).with_default(json!(10)))
+        .with_output_schema("b", |p| p.with_enum(&[json!(1), json!(2)]));
+
+    let vars = Vars::new().with("a", 5).with("b", 2);
+    assert!(m.schema.check_outputs(&vars).is_ok());
+
+    let err = m.schema.check_outputs(&Vars::new()).unwrap_err();
+    assert_eq!(err.to_string(), "the output 'a' is required");
+
+    let vars = Vars::new().with("a", 5).with("b", 3);
+    let err = m.schema.check_outputs(&vars).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the output 'b' expects one of [1, 2] but got '3'"
+    );
+}
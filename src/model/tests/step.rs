@@ -1,9 +1,12 @@
 mod acts;
 mod catch;
+mod join;
+mod limits;
+mod retry;
 mod setup;
 mod timeout;
 
-use crate::{Act, Step, Workflow};
+use crate::{Act, ModelBase, Step, StmtBuild, Workflow};
 use serde_json::json;
 
 #[test]
@@ -131,3 +134,57 @@ fn model_step_uses() {
     let step = Step::new().with_uses("p1");
     assert_eq!(step.uses.unwrap(), "p1");
 }
+
+#[test]
+fn model_step_yml_compensate() {
+    let text = r#"
+    name: workflow
+    id: m1
+    steps:
+        - id: step1
+          compensate:
+            - !msg
+              id: undo_step1
+          acts:
+            - !req
+              id: act1
+              compensate:
+                - !req
+                  id: undo1
+            - !pack
+              id: pack1
+              uses: pack1
+              compensate:
+                - !pack
+                  id: refund
+                  uses: refund
+            - !call
+              id: call1
+              mid: m2
+              compensate:
+                - !msg
+                  id: undo_call1
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    let step = m.steps.first().unwrap();
+    assert_eq!(step.compensate.first().unwrap().id(), "undo_step1");
+
+    let ids = step
+        .acts
+        .iter()
+        .map(|act| act.compensate().first().unwrap().id().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["undo1", "refund", "undo_call1"]);
+}
+
+#[test]
+fn model_step_compensate() {
+    let step = Step::new()
+        .with_compensate(|stmts| stmts.add(Act::msg(|msg| msg.with_id("undo1"))))
+        .with_act(Act::req(|act| {
+            act.with_id("act1")
+                .with_compensate(|stmts| stmts.add(Act::msg(|msg| msg.with_id("undo2"))))
+        }));
+    assert_eq!(step.compensate.len(), 1);
+    assert_eq!(step.acts.first().unwrap().compensate().len(), 1);
+}
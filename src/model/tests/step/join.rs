@@ -0,0 +1,47 @@
+use crate::{Join, Step, Workflow};
+
+#[test]
+fn model_step_join() {
+    let step = Step::new();
+    assert_eq!(step.join, None);
+
+    let step = step.with_join(Join::Any);
+    assert_eq!(step.join, Some(Join::Any));
+}
+
+#[test]
+fn model_step_yml_join() {
+    let text = r#"
+    id: m1
+    steps:
+        - id: step1
+          join: all
+        - id: step2
+          join: any
+        - id: step3
+          join: !count 2
+        - id: step4
+          join: !expr joined >= total - 1
+        - id: step5
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert_eq!(m.steps[0].join, Some(Join::All));
+    assert_eq!(m.steps[1].join, Some(Join::Any));
+    assert_eq!(m.steps[2].join, Some(Join::Count(2)));
+    assert_eq!(
+        m.steps[3].join,
+        Some(Join::Expr("joined >= total - 1".to_string()))
+    );
+    assert_eq!(m.steps[4].join, None);
+}
+
+#[test]
+fn model_step_json_join() {
+    let m = Workflow::new()
+        .with_step(|step| step.with_id("step1").with_join(Join::Count(2)))
+        .with_step(|step| step.with_id("step2").with_join(Join::Any));
+    let text = m.to_json().unwrap();
+    let m2 = Workflow::from_json(&text).unwrap();
+    assert_eq!(m2.steps[0].join, Some(Join::Count(2)));
+    assert_eq!(m2.steps[1].join, Some(Join::Any));
+}
@@ -0,0 +1,51 @@
+use crate::{Act, Limits, Step, Workflow};
+
+#[test]
+fn model_step_yml_limits() {
+    let text = r#"
+    name: workflow
+    id: m1
+    steps:
+        - id: step1
+          limits:
+            timeout: 2s
+            memory: 1048576
+          run: |
+            let v = 1;
+          acts:
+            - !pack
+              id: pack1
+              uses: pack1
+              limits:
+                timeout: 500ms
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    let step = m.steps.first().unwrap();
+    let limits = step.limits.as_ref().unwrap();
+    assert_eq!(limits.timeout.as_ref().unwrap().as_millis(), 2000);
+    assert_eq!(limits.memory, Some(1048576));
+
+    let limits = step.acts.first().unwrap().limits().unwrap();
+    assert_eq!(limits.timeout.as_ref().unwrap().as_millis(), 500);
+    assert_eq!(limits.memory, None);
+}
+
+#[test]
+fn model_step_limits_builder() {
+    let step = Step::new().with_limits(|l| l.with_timeout("1s").with_memory(1024));
+    let limits = step.limits.as_ref().unwrap();
+    assert_eq!(limits.timeout.as_ref().unwrap().as_secs(), 1);
+    assert_eq!(limits.memory, Some(1024));
+
+    let act = Act::req(|act| act.with_limits(|l| l.with_memory(2048)));
+    assert_eq!(act.limits().unwrap().memory, Some(2048));
+}
+
+#[test]
+fn model_step_limits_or() {
+    let limits = Limits::new()
+        .with_timeout("1s")
+        .or(&Limits::new().with_timeout("5s").with_memory(1024));
+    assert_eq!(limits.timeout.as_ref().unwrap().as_secs(), 1);
+    assert_eq!(limits.memory, Some(1024));
+}
@@ -0,0 +1,93 @@
+use crate::{model::step::TimeoutUnit, Act, Retry, Step, Workflow};
+
+#[test]
+fn model_step_yml_retry() {
+    let text = r#"
+    name: workflow
+    id: m1
+    steps:
+        - id: step1
+          retry:
+            times: 3
+            backoff: 10s
+            max_delay: 1m
+            on: [timeout, busy]
+          acts:
+            - !req
+              id: act1
+              retry:
+                times: 2
+            - !pack
+              id: pack1
+              uses: pack1
+              retry:
+                times: 1
+                backoff: PT5S
+            - !call
+              id: call1
+              mid: m2
+              retry:
+                times: 1
+                on: [err1]
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    let step = m.steps.first().unwrap();
+    let retry = step.retry.as_ref().unwrap();
+    assert_eq!(retry.times, 3);
+    assert_eq!(retry.backoff.as_ref().unwrap().as_secs(), 10);
+    assert_eq!(retry.max_delay.as_ref().unwrap().unit, TimeoutUnit::Minute);
+    assert_eq!(retry.on, ["timeout", "busy"]);
+
+    let retries = step
+        .acts
+        .iter()
+        .map(|act| act.retry().unwrap().times)
+        .collect::<Vec<_>>();
+    assert_eq!(retries, [2, 1, 1]);
+
+    let retry = step.acts.get(1).unwrap().retry().unwrap();
+    assert_eq!(retry.backoff.as_ref().unwrap().as_secs(), 5);
+}
+
+#[test]
+fn model_step_retry_builder() {
+    let step = Step::new().with_retry(|r| {
+        r.with_times(2)
+            .with_backoff("1s")
+            .with_max_delay("3s")
+            .with_on("err1")
+    });
+
+    let retry = step.retry.as_ref().unwrap();
+    assert_eq!(retry.times, 2);
+    assert_eq!(retry.on, ["err1"]);
+
+    let act = Act::req(|act| act.with_retry(|r| r.with_times(1)));
+    assert_eq!(act.retry().unwrap().times, 1);
+}
+
+#[test]
+fn model_step_retry_delay() {
+    let retry = Retry::new()
+        .with_times(5)
+        .with_backoff("1s")
+        .with_max_delay("5s");
+    assert_eq!(retry.delay(1), 1000);
+    assert_eq!(retry.delay(2), 2000);
+    assert_eq!(retry.delay(3), 4000);
+    assert_eq!(retry.delay(4), 5000);
+
+    // no backoff retries immediately
+    let retry = Retry::new().with_times(1);
+    assert_eq!(retry.delay(1), 0);
+}
+
+#[test]
+fn model_step_retry_is_match() {
+    let retry = Retry::new();
+    assert!(retry.is_match("err1"));
+
+    let retry = Retry::new().with_on("err1");
+    assert!(retry.is_match("err1"));
+    assert!(!retry.is_match("err2"));
+}
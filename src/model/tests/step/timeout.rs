@@ -1,5 +1,5 @@
 use crate::{
-    model::step::{TimeoutLimit, TimeoutUnit},
+    model::step::{TimeoutLimit, TimeoutMode, TimeoutUnit},
     Act, Step, StmtBuild, Workflow,
 };
 
@@ -63,6 +63,48 @@ fn model_timeout_parse_days() {
     assert_eq!(timeout.as_secs(), 100 * 60 * 60 * 24);
 }
 
+#[test]
+fn model_timeout_parse_millis_and_weeks() {
+    let timeout = TimeoutLimit::parse("500ms").unwrap();
+    assert_eq!(timeout.value, 500);
+    assert_eq!(timeout.unit, TimeoutUnit::Millisecond);
+    assert_eq!(timeout.as_millis(), 500);
+    assert_eq!(timeout.to_string(), "500ms");
+
+    let timeout = TimeoutLimit::parse("2w").unwrap();
+    assert_eq!(timeout.value, 2);
+    assert_eq!(timeout.unit, TimeoutUnit::Week);
+    assert_eq!(timeout.as_secs(), 2 * 7 * 24 * 60 * 60);
+}
+
+#[test]
+fn model_timeout_parse_iso() {
+    let timeout = TimeoutLimit::parse("PT30M").unwrap();
+    assert_eq!(timeout.value, 30);
+    assert_eq!(timeout.unit, TimeoutUnit::Minute);
+
+    let timeout = TimeoutLimit::parse("P1DT12H").unwrap();
+    assert_eq!(timeout.value, 36);
+    assert_eq!(timeout.unit, TimeoutUnit::Hour);
+
+    let timeout = TimeoutLimit::parse("P1W").unwrap();
+    assert_eq!(timeout.unit, TimeoutUnit::Week);
+    assert_eq!(timeout.to_string(), "1w");
+
+    let timeout = TimeoutLimit::parse("PT1.5S").unwrap();
+    assert_eq!(timeout.value, 1500);
+    assert_eq!(timeout.unit, TimeoutUnit::Millisecond);
+}
+
+#[test]
+fn model_timeout_parse_iso_error() {
+    assert!(TimeoutLimit::parse("P").is_err());
+    assert!(TimeoutLimit::parse("P1DT").is_err());
+    assert!(TimeoutLimit::parse("P1Y").is_err());
+    assert!(TimeoutLimit::parse("P1M").is_err());
+    assert!(TimeoutLimit::parse("PT1X").is_err());
+}
+
 #[test]
 fn model_timeout_parse_error() {
     let timeout = TimeoutLimit::parse("");
@@ -151,3 +193,50 @@ fn model_step_yml_timeout() {
     assert_eq!(timeout.on.as_secs(), 3 * 60);
     assert_eq!(timeout.then.len(), 1);
 }
+
+#[test]
+fn model_step_yml_timeout_reminder() {
+    let text = r#"
+    name: workflow
+    id: m1
+    steps:
+        - id: act1
+          timeout:
+            - until: ${ $("due_date") }
+              every: P1D
+              max: 3
+              then:
+                - !msg
+                  id: remind
+            - on: 1w
+              mode: interrupting
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    let step = m.steps.first().unwrap();
+
+    let timeout = step.timeout.first().unwrap();
+    assert_eq!(timeout.until.as_ref().unwrap(), r#"${ $("due_date") }"#);
+    assert_eq!(timeout.every.as_ref().unwrap().unit, TimeoutUnit::Day);
+    assert_eq!(timeout.max, Some(3));
+    assert_eq!(timeout.mode, TimeoutMode::NonInterrupting);
+
+    let timeout = step.timeout.get(1).unwrap();
+    assert_eq!(timeout.on.unit, TimeoutUnit::Week);
+    assert_eq!(timeout.mode, TimeoutMode::Interrupting);
+}
+
+#[test]
+fn model_step_timeout_builder() {
+    let step = Step::new().with_timeout(|t| {
+        t.with_until(1000)
+            .with_every("1h")
+            .with_max(2)
+            .with_mode(TimeoutMode::Interrupting)
+    });
+
+    let timeout = step.timeout.first().unwrap();
+    assert_eq!(timeout.until.as_ref().unwrap(), 1000);
+    assert_eq!(timeout.every.as_ref().unwrap().as_secs(), 60 * 60);
+    assert_eq!(timeout.max, Some(2));
+    assert_eq!(timeout.mode, TimeoutMode::Interrupting);
+}
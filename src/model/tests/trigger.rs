@@ -0,0 +1,147 @@
+use crate::{CatchUp, Trigger, Workflow};
+use serde_json::json;
+
+// 2024-01-01T00:00:00Z
+const JAN_1: i64 = 1704067200000;
+const HOUR: i64 = 3600 * 1000;
+const DAY: i64 = 24 * HOUR;
+
+#[test]
+fn model_trigger_parse() {
+    let text = r#"
+    id: m1
+    triggers:
+        - id: daily
+          cron: 0 9 * * *
+          timezone: Asia/Shanghai
+          catch_up: once
+          inputs:
+            kind: daily
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert_eq!(m.triggers.len(), 1);
+
+    let trigger = &m.triggers[0];
+    assert_eq!(trigger.id, "daily");
+    assert_eq!(trigger.cron, "0 9 * * *");
+    assert_eq!(trigger.timezone, Some("Asia/Shanghai".to_string()));
+    assert_eq!(trigger.catch_up, CatchUp::Once);
+    assert_eq!(trigger.inputs.get::<String>("kind").unwrap(), "daily");
+}
+
+#[test]
+fn model_trigger_builder() {
+    let m = Workflow::new().with_trigger(|t| {
+        t.with_id("t1")
+            .with_cron("*/5 * * * *")
+            .with_input("a", json!(5))
+            .with_catch_up(CatchUp::All)
+    });
+    let trigger = &m.triggers[0];
+    assert_eq!(trigger.id, "t1");
+    assert_eq!(trigger.timezone, None);
+    assert_eq!(trigger.catch_up, CatchUp::All);
+    assert_eq!(trigger.inputs.get::<i64>("a").unwrap(), 5);
+}
+
+#[test]
+fn model_trigger_default_catch_up() {
+    let text = r#"
+    id: m1
+    triggers:
+        - cron: 0 9 * * *
+    "#;
+    let m = Workflow::from_yml(text).unwrap();
+    assert_eq!(m.triggers[0].catch_up, CatchUp::Skip);
+}
+
+#[test]
+fn model_trigger_next_times_utc() {
+    let trigger = Trigger::new().with_cron("0 9 * * *");
+    let times = trigger.next_times(JAN_1, 2).unwrap();
+    assert_eq!(times, [JAN_1 + 9 * HOUR, JAN_1 + DAY + 9 * HOUR]);
+}
+
+#[test]
+fn model_trigger_next_times_timezone() {
+    let trigger = Trigger::new()
+        .with_cron("0 9 * * *")
+        .with_timezone("Asia/Shanghai");
+    let times = trigger.next_times(JAN_1, 2).unwrap();
+    assert_eq!(times, [JAN_1 + HOUR, JAN_1 + DAY + HOUR]);
+}
+
+#[test]
+fn model_trigger_crontab_weekday_range() {
+    // 2024-01-01 is Monday
+    let trigger = Trigger::new().with_cron("0 9 * * 1-5");
+    let times = trigger.next_times(JAN_1, 6).unwrap();
+    assert_eq!(
+        times,
+        [0, 1, 2, 3, 4, 7].map(|day| JAN_1 + day * DAY + 9 * HOUR)
+    );
+}
+
+#[test]
+fn model_trigger_crontab_weekday_sunday() {
+    for cron in ["0 9 * * 0", "0 9 * * 7", "0 9 * * Sun"] {
+        let trigger = Trigger::new().with_cron(cron);
+        assert_eq!(
+            trigger.next_time(JAN_1).unwrap(),
+            JAN_1 + 6 * DAY + 9 * HOUR
+        );
+    }
+}
+
+#[test]
+fn model_trigger_crontab_weekday_list_and_step() {
+    let trigger = Trigger::new().with_cron("0 9 * * 0,5-7");
+    let times = trigger.next_times(JAN_1, 3).unwrap();
+    assert_eq!(times, [4, 5, 6].map(|day| JAN_1 + day * DAY + 9 * HOUR));
+
+    // Monday, Wednesday and Friday
+    let trigger = Trigger::new().with_cron("0 9 * * 1-5/2");
+    let times = trigger.next_times(JAN_1, 3).unwrap();
+    assert_eq!(times, [0, 2, 4].map(|day| JAN_1 + day * DAY + 9 * HOUR));
+}
+
+#[test]
+fn model_trigger_crontab_weekday_invalid() {
+    assert!(Trigger::new().with_cron("0 9 * * 8").schedule().is_err());
+    assert!(Trigger::new().with_cron("0 9 * * 5-1").schedule().is_err());
+    assert!(Trigger::new()
+        .with_cron("0 9 * * Mon-5")
+        .schedule()
+        .is_err());
+}
+
+#[test]
+fn model_trigger_with_seconds() {
+    let trigger = Trigger::new().with_cron("30 0 9 * * *");
+    assert_eq!(trigger.next_time(JAN_1).unwrap(), JAN_1 + 9 * HOUR + 30000);
+}
+
+#[test]
+fn model_trigger_fire_times() {
+    let trigger = Trigger::new().with_cron("0 * * * *");
+    let times = trigger.fire_times(JAN_1, JAN_1 + 3 * HOUR, 10).unwrap();
+    assert_eq!(times, [JAN_1 + HOUR, JAN_1 + 2 * HOUR, JAN_1 + 3 * HOUR]);
+
+    let times = trigger.fire_times(JAN_1, JAN_1 + 3 * HOUR, 2).unwrap();
+    assert_eq!(times.len(), 2);
+}
+
+#[test]
+fn model_trigger_invalid_cron() {
+    let trigger = Trigger::new().with_cron("every day");
+    assert!(trigger.schedule().is_err());
+    assert!(trigger.next_times(JAN_1, 1).is_err());
+}
+
+#[test]
+fn model_trigger_invalid_timezone() {
+    let trigger = Trigger::new()
+        .with_cron("0 9 * * *")
+        .with_timezone("Mars/Olympus");
+    assert!(trigger.tz().is_err());
+}
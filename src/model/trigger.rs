@@ -0,0 +1,208 @@
+use crate::{ActError, Result, Vars};
+use chrono::TimeZone;
+use chrono_tz::Tz;
+use cron::Schedule;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::str::FromStr;
+
+/// The policy of the missed fires when the engine restarts
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CatchUp {
+    /// ignore the missed fires
+    #[default]
+    Skip,
+
+    /// fire once for all of the missed fires
+    Once,
+
+    /// fire for every missed fire
+    All,
+}
+
+/// The cron trigger to start the workflow on a schedule
+///
+/// the cron has 5 fields (min hour day month weekday)
+/// or 6 fields with the seconds in the front
+///
+/// the numeric weekdays are the same as crontab, 0 or 7 is Sunday, 1 is Monday and so on,
+/// the names such as `Mon-Fri` are also supported
+///
+/// # Example
+/// ```yaml
+/// triggers:
+///   - id: daily
+///     cron: 0 9 * * Mon-Fri
+///     timezone: Asia/Shanghai
+///     catch_up: once
+///     inputs:
+///       kind: daily
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Trigger {
+    #[serde(default)]
+    pub id: String,
+
+    pub cron: String,
+
+    /// the iana timezone name, default to UTC
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+
+    /// the inputs to start the proc
+    #[serde(default)]
+    pub inputs: Vars,
+
+    #[serde(default)]
+    pub catch_up: CatchUp,
+}
+
+impl Trigger {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    pub fn with_cron(mut self, cron: &str) -> Self {
+        self.cron = cron.to_string();
+        self
+    }
+
+    pub fn with_timezone(mut self, timezone: &str) -> Self {
+        self.timezone = Some(timezone.to_string());
+        self
+    }
+
+    pub fn with_input(mut self, name: &str, value: JsonValue) -> Self {
+        self.inputs.insert(name.to_string(), value);
+        self
+    }
+
+    pub fn with_catch_up(mut self, catch_up: CatchUp) -> Self {
+        self.catch_up = catch_up;
+        self
+    }
+
+    pub fn schedule(&self) -> Result<Schedule> {
+        let invalid = |err: String| {
+            ActError::Model(format!(
+                "the trigger cron '{}' is invalid: {err}",
+                self.cron
+            ))
+        };
+        let mut fields = self
+            .cron
+            .split_whitespace()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>();
+        if fields.len() == 5 {
+            fields.insert(0, "0".to_string());
+        }
+
+        // the weekday is the 6th field with the seconds
+        if let Some(field) = fields.get_mut(5) {
+            *field = crontab_weekday(field).map_err(invalid)?;
+        }
+        Schedule::from_str(&fields.join(" ")).map_err(|err| invalid(err.to_string()))
+    }
+
+    pub fn tz(&self) -> Result<Tz> {
+        match &self.timezone {
+            Some(name) => Tz::from_str(name).map_err(|err| {
+                ActError::Model(format!("the trigger timezone '{name}' is invalid: {err}"))
+            }),
+            None => Ok(Tz::UTC),
+        }
+    }
+
+    /// the next fire times in millis after the time
+    pub fn next_times(&self, after: i64, count: usize) -> Result<Vec<i64>> {
+        let schedule = self.schedule()?;
+        let tz = self.tz()?;
+        let Some(start) = tz.timestamp_millis_opt(after).single() else {
+            return Ok(Vec::new());
+        };
+        Ok(schedule
+            .after(&start)
+            .take(count)
+            .map(|t| t.timestamp_millis())
+            .collect())
+    }
+
+    /// the next fire time in millis after the time, `i64::MAX` if it never fires
+    pub fn next_time(&self, after: i64) -> Result<i64> {
+        Ok(self
+            .next_times(after, 1)?
+            .first()
+            .copied()
+            .unwrap_or(i64::MAX))
+    }
+
+    /// the fire times in millis between `(start, end]`, at most `limit` times
+    pub fn fire_times(&self, start: i64, end: i64, limit: usize) -> Result<Vec<i64>> {
+        let schedule = self.schedule()?;
+        let tz = self.tz()?;
+        let Some(start) = tz.timestamp_millis_opt(start).single() else {
+            return Ok(Vec::new());
+        };
+        Ok(schedule
+            .after(&start)
+            .map(|t| t.timestamp_millis())
+            .take_while(|t| *t <= end)
+            .take(limit)
+            .collect())
+    }
+}
+
+/// convert the crontab weekday field (0-7, Sun=0 or 7) to the weekday of the cron crate (1-7, Sun=1)
+fn crontab_weekday(field: &str) -> std::result::Result<String, String> {
+    let mut items = Vec::new();
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, Some(step)),
+            None => (item, None),
+        };
+
+        // the names and the `*` are the same in both of them
+        let bounds = range.split('-').collect::<Vec<_>>();
+        let is_num = bounds
+            .iter()
+            .map(|v| v.parse::<u32>().is_ok())
+            .collect::<Vec<_>>();
+        if range == "*" || range == "?" || is_num.iter().all(|v| !v) {
+            items.push(item.to_string());
+            continue;
+        }
+        if is_num.iter().any(|v| !v) || bounds.len() > 2 {
+            return Err(format!("the weekday '{item}' mixes the names and numbers"));
+        }
+
+        let start = bounds[0].parse::<u32>().unwrap();
+        let end = match (bounds.get(1), step) {
+            (Some(end), _) => end.parse::<u32>().unwrap(),
+            (None, Some(_)) => 6,
+            (None, None) => start,
+        };
+        let step = match step {
+            Some(step) => step
+                .parse::<usize>()
+                .ok()
+                .filter(|v| *v > 0)
+                .ok_or(format!("the weekday step '{step}' is invalid"))?,
+            None => 1,
+        };
+        if end > 7 || start > end {
+            return Err(format!("the weekday '{item}' is out of range 0-7"));
+        }
+        for day in (start..=end).step_by(step) {
+            items.push((day % 7 + 1).to_string());
+        }
+    }
+
+    Ok(items.join(","))
+}
@@ -1,4 +1,6 @@
-use crate::{sch::NodeTree, Act, ActError, ModelBase, Result, Step, Vars};
+use crate::{
+    sch::NodeTree, Act, ActError, Fragment, ModelBase, Param, Result, Schema, Step, Trigger, Vars,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
@@ -28,6 +30,18 @@ pub struct Workflow {
     #[serde(default)]
     pub setup: Vec<Act>,
 
+    /// the typed schema of inputs and outputs
+    #[serde(default, skip_serializing_if = "Schema::is_empty")]
+    pub schema: Schema,
+
+    /// the cron triggers to start the proc on a schedule
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triggers: Vec<Trigger>,
+
+    /// the named fragments to include in this or other models
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fragments: Vec<Fragment>,
+
     #[serde(default)]
     ver: u32,
 }
@@ -93,6 +107,11 @@ impl Workflow {
         self.ver = ver;
     }
 
+    /// the deployed model version
+    pub fn ver(&self) -> u32 {
+        self.ver
+    }
+
     pub fn to_yml<'a>(&self) -> Result<String> {
         match serde_yaml::to_string(self) {
             Ok(s) => Ok(s),
@@ -110,6 +129,10 @@ impl Workflow {
     pub fn valid(&self) -> Result<()> {
         let mut root = NodeTree::new();
         root.load(self)?;
+        for trigger in &self.triggers {
+            trigger.schedule()?;
+            trigger.tz()?;
+        }
         Ok(())
     }
 }
@@ -156,15 +179,41 @@ impl Workflow {
         self
     }
 
+    pub fn with_input_schema(mut self, name: &str, build: fn(Param) -> Param) -> Self {
+        self.schema
+            .inputs
+            .insert(name.to_string(), build(Param::default()));
+        self
+    }
+
+    pub fn with_output_schema(mut self, name: &str, build: fn(Param) -> Param) -> Self {
+        self.schema
+            .outputs
+            .insert(name.to_string(), build(Param::default()));
+        self
+    }
+
     pub fn with_step(mut self, build: fn(Step) -> Step) -> Self {
         let step = Step::default();
         self.steps.push(build(step));
         self
     }
 
+    pub fn with_trigger(mut self, build: fn(Trigger) -> Trigger) -> Self {
+        let trigger = Trigger::default();
+        self.triggers.push(build(trigger));
+        self
+    }
+
     pub fn with_setup(mut self, build: fn(Vec<Act>) -> Vec<Act>) -> Self {
         let stmts = Vec::new();
         self.setup = build(stmts);
         self
     }
+
+    pub fn with_fragment(mut self, build: fn(Fragment) -> Fragment) -> Self {
+        let fragment = Fragment::default();
+        self.fragments.push(build(fragment));
+        self
+    }
 }
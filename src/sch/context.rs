@@ -3,10 +3,10 @@ use crate::{
     event::{Action, Model},
     sch::{tree::NodeContent, Node, Proc, Task},
     utils::{self, consts, shortid},
-    Act, ActError, Message, ModelBase, Msg, NodeKind, Result, TaskState, Vars,
+    Act, ActError, Block, Limits, Message, ModelBase, Msg, NodeKind, Result, TaskState, Vars,
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{cell::RefCell, sync::Arc};
+use std::{cell::RefCell, cmp::Reverse, sync::Arc};
 use tracing::debug;
 
 tokio::task_local! {
@@ -149,7 +149,27 @@ impl Context {
     }
 
     pub fn eval<T: DeserializeOwned + Serialize>(&self, expr: &str) -> Result<T> {
-        Context::scope(self.clone(), || self.runtime.env().eval::<T>(expr))
+        self.eval_with(expr, &Limits::default())
+    }
+
+    /// evaluate the script with the limits
+    /// the unset limits fall back to the ones of the current act and its parent steps
+    pub fn eval_with<T: DeserializeOwned + Serialize>(
+        &self,
+        expr: &str,
+        limits: &Limits,
+    ) -> Result<T> {
+        let mut limits = limits.clone();
+        let mut node = Some(self.task().node().clone());
+        while let Some(n) = node {
+            if let Some(l) = n.content.limits() {
+                limits = limits.or(l);
+            }
+            node = n.parent();
+        }
+        Context::scope(self.clone(), || {
+            self.runtime.env().eval_with::<T>(expr, &limits)
+        })
     }
 
     #[allow(unused)]
@@ -167,7 +187,9 @@ impl Context {
         debug!("append_act: {act:?}  {:?}", self.task);
         let mut task = self.task();
         if task.is_kind(NodeKind::Act) {
-            let is_package_act = task.is_act(consts::ACT_TYPE_BLOCK);
+            let is_package_act = task.is_act(consts::ACT_TYPE_BLOCK)
+                || task.is_act(consts::ACT_TYPE_LOOP)
+                || task.is_act(consts::ACT_TYPE_EACH);
 
             // not package act or completed package
             if !is_package_act || (is_package_act && task.state().is_completed()) {
@@ -262,6 +284,9 @@ impl Context {
             self.emit_task(task)?;
         }
 
+        if task.parent().is_none() {
+            return self.abort_root(task);
+        }
         task.set_state(TaskState::Aborted);
         self.emit_task(task)?;
 
@@ -269,25 +294,43 @@ impl Context {
         let ctx = self;
         let mut parent = task.parent();
         while let Some(task) = parent {
+            if task.parent().is_none() {
+                return ctx.abort_root(&task);
+            }
             task.set_state(TaskState::Aborted);
             ctx.set_task(&task);
             ctx.emit_task(&ctx.task())?;
-
-            for t in task.children() {
-                if t.state().is_pending() {
-                    t.set_state(TaskState::Skipped);
-                    ctx.emit_task(&t)?;
-                } else if t.state().is_running() {
-                    t.set_state(TaskState::Aborted);
-                    ctx.emit_task(&t)?;
-                }
-            }
+            ctx.abort_children(&task)?;
 
             parent = task.parent();
         }
         Ok(())
     }
 
+    /// abort the root task after running the compensations
+    fn abort_root(&self, root: &Arc<Task>) -> Result<()> {
+        self.set_task(root);
+        self.abort_children(root)?;
+        if !self.compensate_task(root, TaskState::Aborted)? {
+            root.set_state(TaskState::Aborted);
+            self.emit_task(root)?;
+        }
+        Ok(())
+    }
+
+    fn abort_children(&self, task: &Arc<Task>) -> Result<()> {
+        for t in task.children() {
+            if t.state().is_pending() {
+                t.set_state(TaskState::Skipped);
+                self.emit_task(&t)?;
+            } else if t.state().is_running() {
+                t.set_state(TaskState::Aborted);
+                self.emit_task(&t)?;
+            }
+        }
+        Ok(())
+    }
+
     /// undo task
     /// the undo task is a step task, set the task as completed and set the children acts as cancelled
     pub fn undo_task(&self, task: &Arc<Task>) -> Result<()> {
@@ -319,8 +362,150 @@ impl Context {
         Ok(())
     }
 
+    /// cancel the task and all of its children which are not completed
+    pub fn cancel_task(&self, task: &Arc<Task>) -> Result<()> {
+        let mut tasks = vec![task.clone()];
+        while !tasks.is_empty() {
+            let mut nexts = Vec::new();
+            for t in &tasks {
+                if t.state().is_completed() {
+                    continue;
+                }
+                t.set_state(TaskState::Cancelled);
+                self.emit_task(t)?;
+                nexts.extend_from_slice(&t.children());
+            }
+
+            tasks = nexts;
+        }
+
+        Ok(())
+    }
+
+    /// re-run the failed task by its retry policy
+    /// returns false when there is no retry or the retries are exhausted
+    pub fn retry_task(&self, task: &Arc<Task>) -> Result<bool> {
+        let (Some(retry), Some(err)) = (task.node().content.retry(), task.err()) else {
+            return Ok(false);
+        };
+        let attempt = task
+            .with_data(|data| data.get::<u32>(consts::RETRY_ATTEMPT))
+            .unwrap_or_default()
+            + 1;
+        if attempt > retry.times || !retry.is_match(&err.ecode) {
+            return Ok(false);
+        }
+
+        // cancel the unfinished children, they will be created again by the re-running
+        for child in task.children() {
+            self.cancel_task(&child)?;
+        }
+
+        let due = utils::time::time_millis() + retry.delay(attempt);
+        task.set_data_with(|data| {
+            data.set(consts::RETRY_ATTEMPT, attempt);
+            data.set(consts::RETRY_AT, due);
+        });
+        task.set_state(TaskState::Interrupt);
+        self.runtime.cache().upsert(task)?;
+
+        // the timer service re-runs the task when it is due
+        self.runtime.timer().add(due, &task.pid, &task.id);
+        Ok(true)
+    }
+
+    /// run the compensations of the completed tasks in reverse completion order
+    /// the root ends with the `state` after all of the compensations are completed
+    /// returns false when there is nothing to compensate
+    pub fn compensate_task(&self, root: &Arc<Task>, state: TaskState) -> Result<bool> {
+        // the compensations run only once, a failure in them ends the root directly
+        if let Some(value) = root.with_data(|data| data.get::<String>(consts::COMPENSATE_STATE)) {
+            if TaskState::from(value).is_running() {
+                self.finish_compensate(root, TaskState::Error)?;
+            }
+            return Ok(false);
+        }
+
+        let mut tasks = self
+            .proc
+            .tasks()
+            .into_iter()
+            .filter(|t| t.state().is_success() && !t.node().content.compensate().is_empty())
+            .collect::<Vec<_>>();
+        if tasks.is_empty() {
+            return Ok(false);
+        }
+        // the parent is completed after its children in the same millisecond
+        tasks.sort_by_key(|t| (Reverse(t.end_time()), t.node().level, Reverse(t.timestamp)));
+
+        // chain the compensations by blocks to run them one by one with the saved task data
+        let mut block: Option<Block> = None;
+        for task in tasks.iter().rev() {
+            block = Some(Block {
+                id: shortid(),
+                acts: task.node().content.compensate().to_vec(),
+                inputs: task.data(),
+                next: block.map(Box::new),
+            });
+        }
+
+        let running: String = TaskState::Running.into();
+        let tids = tasks.iter().map(|t| t.id.clone()).collect::<Vec<_>>();
+        for task in &tasks {
+            task.set_data_with(|data| data.set(consts::COMPENSATE_STATE, &running));
+            self.runtime.cache().upsert(task)?;
+            self.runtime.emitter().emit_message(&task.create_message());
+        }
+
+        let err = root.err();
+        root.set_data_with(|data| {
+            data.set(consts::COMPENSATE_STATE, &running);
+            data.set(consts::COMPENSATE_TASKS, &tids);
+            data.set::<String>(consts::COMPENSATE_END, state.clone().into());
+            if let Some(err) = &err {
+                data.set(consts::COMPENSATE_ERR, err);
+            }
+        });
+        root.set_state(TaskState::Running);
+        self.runtime.cache().upsert(root)?;
+
+        if let Some(block) = block {
+            self.set_task(root);
+            self.append_act(&Act::Block(block))?;
+        }
+        Ok(true)
+    }
+
+    /// mark the compensations as completed or failed by the `state`
+    pub(crate) fn finish_compensate(&self, root: &Arc<Task>, state: TaskState) -> Result<()> {
+        let value: String = state.into();
+        let tids = root
+            .with_data(|data| data.get::<Vec<String>>(consts::COMPENSATE_TASKS))
+            .unwrap_or_default();
+        for tid in tids {
+            if let Some(task) = self.proc.task(&tid) {
+                task.set_data_with(|data| data.set(consts::COMPENSATE_STATE, &value));
+                self.runtime.cache().upsert(&task)?;
+                self.runtime.emitter().emit_message(&task.create_message());
+            }
+        }
+        root.set_data_with(|data| data.set(consts::COMPENSATE_STATE, &value));
+        Ok(())
+    }
+
     pub fn emit_error(&self) -> Result<()> {
         let task = self.task();
+        if task.state().is_error() && self.retry_task(&task)? {
+            return Ok(());
+        }
+
+        // the unhandled error of the root runs the compensations before ending
+        if task.state().is_error()
+            && task.parent().is_none()
+            && self.compensate_task(&task, TaskState::Error)?
+        {
+            return Ok(());
+        }
         if task.state().is_error() {
             self.emit_task(&task)?;
 
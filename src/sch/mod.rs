@@ -4,6 +4,7 @@ mod queue;
 mod runtime;
 mod scher;
 mod state;
+mod timer;
 mod tree;
 
 #[cfg(test)]
@@ -17,6 +18,7 @@ pub use proc::{Proc, StatementBatch, Task, TaskLifeCycle};
 pub use runtime::Runtime;
 pub use scher::Scheduler;
 pub use state::TaskState;
+pub use timer::Timer;
 pub use tree::{Node, NodeContent, NodeKind, NodeTree};
 
 #[async_trait]
@@ -6,14 +6,21 @@ use crate::{
         Context, Runtime, Task, TaskLifeCycle, TaskState,
     },
     utils::{self, consts},
-    ActError, Error, NodeKind, ProcInfo, Result, ShareLock, Vars, Workflow,
+    ActError, Error, MigrateChange, MigrateKind, MigrateReport, NodeKind, ProcInfo, Result,
+    ShareLock, Vars, Workflow,
 };
 use serde::Deserialize;
 use std::{
     cell::RefCell,
+    collections::HashMap,
     fmt,
-    sync::{Arc, RwLock},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Condvar, Mutex, RwLock,
+    },
+    thread::{self, ThreadId},
 };
+use tokio::runtime::{Handle, RuntimeFlavor};
 use tracing::{debug, error, instrument};
 
 #[derive(Clone)]
@@ -29,9 +36,23 @@ pub struct Proc {
     env_local: ShareLock<Vars>,
     runtime: Arc<Runtime>,
     // cache: Arc<Cache>,
-    // sync: Arc<std::sync::Mutex<usize>>,
+    sync: Arc<SyncLock>,
+
+    /// the count of the tasks waiting in the scheduler to run
+    queued: Arc<AtomicUsize>,
+}
+
+/// The reentrant lock to run the tasks and actions of one proc one by one
+/// the thread holding it can lock again, such as calling `act.complete()` in the running task
+/// it blocks the thread, so the async paths of the engine take it in the blocking threads
+#[derive(Default)]
+struct SyncLock {
+    owner: Mutex<Option<(ThreadId, usize)>>,
+    cond: Condvar,
 }
 
+struct SyncGuard<'a>(&'a SyncLock);
+
 impl std::fmt::Debug for Proc {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Proc")
@@ -61,7 +82,8 @@ impl Proc {
             start_time: Arc::new(RwLock::new(0)),
             end_time: Arc::new(RwLock::new(0)),
             tasks: Arc::new(RwLock::new(TaskTree::new())),
-            // sync: Arc::new(std::sync::Mutex::new(0)),
+            sync: Arc::new(SyncLock::default()),
+            queued: Arc::new(AtomicUsize::new(0)),
             timestamp: timestamp,
             env_local: Arc::new(RwLock::new(Vars::new())),
             err: Arc::new(RwLock::new(None)),
@@ -99,6 +121,117 @@ impl Proc {
         tree.load(model)
     }
 
+    /// migrate the proc to the new model
+    /// the tasks keep the state and data, and are bound to the new nodes by node id or the mapping
+    /// the completed tasks of the removed nodes are dropped
+    pub fn migrate(
+        &self,
+        model: &Workflow,
+        mapping: &HashMap<String, String>,
+        dry_run: bool,
+    ) -> Result<MigrateReport> {
+        // the running tasks and actions of the proc must not see the tree in the middle of swap
+        self.sync(|| {
+            // the queued tasks still hold the nodes of the current tree
+            if !dry_run && self.queued() > 0 {
+                return Err(ActError::Runtime(format!(
+                    "proc '{}' has queued tasks, please migrate it later",
+                    self.id
+                )));
+            }
+
+            let mut tree = NodeTree::new();
+            tree.load(model)?;
+
+            let workflow = self.model();
+            let mut report = MigrateReport {
+                pid: self.id.clone(),
+                mid: workflow.id.clone(),
+                from_ver: workflow.ver(),
+                to_ver: model.ver(),
+                dry_run,
+                changes: Vec::new(),
+                added: Vec::new(),
+            };
+
+            let mut tasks = Vec::new();
+            let mut items = self.tasks();
+            items.sort_by_key(|t| t.timestamp);
+            for task in items {
+                let nid = task.node().id();
+                let target = mapping.get(nid).map(|v| v.as_str()).unwrap_or(nid);
+                let mut change = MigrateChange {
+                    tid: task.id.clone(),
+                    state: task.state().into(),
+                    from: nid.to_string(),
+                    to: None,
+                    kind: MigrateKind::Removed,
+                };
+                match tree.node(target) {
+                    Some(node) => {
+                        if node.kind() != task.node().kind() {
+                            return Err(ActError::Runtime(format!(
+                                "cannot migrate task({}) from {} '{}' to {} '{}'",
+                                task.id,
+                                task.node().kind(),
+                                nid,
+                                node.kind(),
+                                target
+                            )));
+                        }
+                        change.to = Some(target.to_string());
+                        change.kind = if target != nid {
+                            MigrateKind::Mapped
+                        } else if node.to_string() != task.node().to_string() {
+                            MigrateKind::Changed
+                        } else {
+                            MigrateKind::Kept
+                        };
+                        tasks.push(Arc::new(task.with_node(&node)));
+                    }
+                    None => {
+                        if !task.state().is_completed() {
+                            return Err(ActError::Runtime(format!(
+                                "the node '{}' of active task({}) is not found in model '{}' ver {}, please set the mapping",
+                                target,
+                                task.id,
+                                model.id,
+                                model.ver()
+                            )));
+                        }
+
+                        // the completed task keeps its history with the removed node
+                        tasks.push(task);
+                    }
+                }
+                report.changes.push(change);
+            }
+
+            {
+                let old = self.tree();
+                let mut added = tree
+                    .node_map
+                    .read()
+                    .unwrap()
+                    .keys()
+                    .filter(|id| old.node(id).is_none())
+                    .cloned()
+                    .collect::<Vec<_>>();
+                added.sort();
+                report.added = added;
+            }
+
+            if !dry_run {
+                *self.tree.write().unwrap() = tree;
+                for task in tasks {
+                    self.push_task(task);
+                }
+            }
+
+            Ok(report)
+        })
+    }
+
     pub fn tree(&self) -> std::sync::RwLockReadGuard<'_, NodeTree> {
         self.tree.read().unwrap()
     }
@@ -184,6 +317,7 @@ impl Proc {
             id: self.id.clone(),
             name: workflow.name.clone(),
             mid: workflow.id.clone(),
+            ver: workflow.ver(),
             state: self.state().into(),
             start_time: self.start_time(),
             end_time: self.end_time(),
@@ -320,10 +454,31 @@ impl Proc {
             action.options = options;
         }
 
-        let ctx = task.create_context();
-        ctx.set_action(&action)?;
-        task.update(&ctx)?;
-        Ok(())
+        self.sync(|| {
+            let ctx = task.create_context();
+            ctx.set_action(&action)?;
+            task.update(&ctx)
+        })
+    }
+
+    /// run the function with the proc lock held
+    /// the tasks run by the scheduler workers and the actions from the clients are not interleaved
+    /// the count of the tasks waiting in the scheduler to run
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn inc_queued(&self) {
+        self.queued.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub(crate) fn dec_queued(&self) {
+        self.queued.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    pub fn sync<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _guard = self.sync.lock();
+        f()
     }
 
     #[instrument()]
@@ -485,3 +640,63 @@ impl Proc {
         })
     }
 }
+
+impl SyncLock {
+    fn lock(&self) -> SyncGuard<'_> {
+        if let Some(guard) = self.try_lock() {
+            return guard;
+        }
+
+        // the lock is held by another thread running a task of the same proc,
+        // tell tokio that the current worker is going to block, so that its other tasks can move on
+        match Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(|| self.wait())
+            }
+            _ => self.wait(),
+        }
+    }
+
+    fn try_lock(&self) -> Option<SyncGuard<'_>> {
+        let id = thread::current().id();
+        let mut owner = self.owner.lock().unwrap();
+        match &mut *owner {
+            Some((tid, count)) if *tid == id => *count += 1,
+            Some(_) => return None,
+            None => *owner = Some((id, 1)),
+        }
+        Some(SyncGuard(self))
+    }
+
+    fn wait(&self) -> SyncGuard<'_> {
+        let id = thread::current().id();
+        let mut owner = self.owner.lock().unwrap();
+        loop {
+            match &mut *owner {
+                Some((tid, count)) if *tid == id => {
+                    *count += 1;
+                    break;
+                }
+                Some(_) => owner = self.cond.wait(owner).unwrap(),
+                None => {
+                    *owner = Some((id, 1));
+                    break;
+                }
+            }
+        }
+        SyncGuard(self)
+    }
+}
+
+impl Drop for SyncGuard<'_> {
+    fn drop(&mut self) {
+        let mut owner = self.0.owner.lock().unwrap();
+        if let Some((_, count)) = &mut *owner {
+            *count -= 1;
+            if *count == 0 {
+                *owner = None;
+                self.0.cond.notify_one();
+            }
+        }
+    }
+}
@@ -99,6 +99,13 @@ impl Task {
         &self.node
     }
 
+    /// clone the task with the new node and keep the state and data
+    pub(crate) fn with_node(&self, node: &Arc<Node>) -> Self {
+        let mut task = self.clone();
+        task.node = node.clone();
+        task
+    }
+
     pub fn start_time(&self) -> i64 {
         *self.start_time.read().unwrap()
     }
@@ -164,6 +171,16 @@ impl Task {
             inputs.set(consts::ACT_ERR_KEY, err);
         }
 
+        // add the retry attempt to inputs
+        if let Some(attempt) = self.with_data(|data| data.get::<u32>(consts::RETRY_ATTEMPT)) {
+            inputs.set(consts::RETRY_ATTEMPT, attempt);
+        }
+
+        // add the compensation state to inputs
+        if let Some(state) = self.with_data(|data| data.get::<String>(consts::COMPENSATE_STATE)) {
+            inputs.set(consts::COMPENSATE_STATE, state);
+        }
+
         // if there is no key, use id instead
         let mut key = self.node.key();
         if key.is_empty() {
@@ -511,15 +528,24 @@ impl Task {
                     task.id
                 )))?;
 
-                for sub in parent.siblings().iter() {
-                    if sub.state().is_completed() {
-                        continue;
+                // the retrying task runs again without skipping others
+                // and the other instances of the each with `until` are left to the `until`
+                task.set_err(&err);
+                if !ctx.retry_task(task)? {
+                    let is_each_until = parent.parent().is_some_and(|p| {
+                        matches!(&p.node().content, NodeContent::Act(Act::Each(each)) if each.until.is_some())
+                    });
+                    if !is_each_until {
+                        for sub in parent.siblings().iter() {
+                            if sub.state().is_completed() {
+                                continue;
+                            }
+                            sub.set_state(TaskState::Skipped);
+                            ctx.emit_task(sub)?;
+                        }
                     }
-                    sub.set_state(TaskState::Skipped);
-                    ctx.emit_task(&sub)?;
+                    task.error(ctx)?;
                 }
-                task.set_err(&err);
-                task.error(ctx)?;
             }
         };
 
@@ -588,7 +614,7 @@ impl Task {
     pub fn add_hook_stmts(&self, key: TaskLifeCycle, value: &Act) {
         let mut hooks = self.hooks.write().unwrap();
 
-        let batch = StatementBatch::Statement(value.clone());
+        let batch = StatementBatch::Statement(Box::new(value.clone()));
         hooks
             .entry(key)
             .and_modify(|list| list.push(batch.clone()))
@@ -608,7 +634,7 @@ impl Task {
     pub fn add_hook_timeout(&self, key: TaskLifeCycle, value: &Timeout) {
         let mut hooks = self.hooks.write().unwrap();
 
-        let batch = StatementBatch::Timeout(value.clone());
+        let batch = StatementBatch::Timeout(Box::new(value.clone()));
         hooks
             .entry(key)
             .and_modify(|list| list.push(batch.clone()))
@@ -704,6 +730,9 @@ impl Task {
             hooks: serde_json::to_string(&self.hooks()).map_err(ActError::from)?,
             timestamp: self.timestamp,
             err: self.err().map(|err| err.to_string()),
+            wait_key: self
+                .with_data(|data| data.get::<String>(consts::WAIT_KEY))
+                .unwrap_or_default(),
         })
     }
 
@@ -1,16 +1,21 @@
 mod block;
 mod call;
 mod cmd;
+mod delay;
+mod each;
+mod r#loop;
 mod pack;
 mod req;
+mod wait;
 
 use super::TaskLifeCycle;
 use crate::{
     sch::Context,
     utils::{self, consts},
-    Act, ActTask, Block, Result, TaskState, Vars,
+    Act, ActError, ActTask, Block, Result, TaskState,
 };
 use async_trait::async_trait;
+use serde_json::Value as JsonValue;
 use std::{cell::RefCell, rc::Rc};
 use tracing::debug;
 
@@ -21,7 +26,11 @@ impl ActTask for Act {
             Act::Req(req) => req.init(ctx),
             Act::Call(u) => u.init(ctx),
             Act::Block(b) => b.init(ctx),
+            Act::Loop(l) => l.init(ctx),
+            Act::Each(e) => e.init(ctx),
             Act::Pack(p) => p.init(ctx),
+            Act::Wait(w) => w.init(ctx),
+            Act::Delay(d) => d.init(ctx),
             _ => Ok(()),
         }
     }
@@ -31,7 +40,11 @@ impl ActTask for Act {
             Act::Req(req) => req.run(ctx),
             Act::Call(u) => u.run(ctx),
             Act::Block(b) => b.run(ctx),
+            Act::Loop(l) => l.run(ctx),
+            Act::Each(e) => e.run(ctx),
             Act::Pack(p) => p.run(ctx),
+            Act::Wait(w) => w.run(ctx),
+            Act::Delay(d) => d.run(ctx),
             _ => Ok(()),
         }
     }
@@ -41,7 +54,11 @@ impl ActTask for Act {
             Act::Req(req) => req.next(ctx),
             Act::Call(u) => u.next(ctx),
             Act::Block(b) => b.next(ctx),
+            Act::Loop(l) => l.next(ctx),
+            Act::Each(e) => e.next(ctx),
             Act::Pack(p) => p.next(ctx),
+            Act::Wait(w) => w.next(ctx),
+            Act::Delay(d) => d.next(ctx),
             _ => Ok(false),
         }
     }
@@ -51,10 +68,21 @@ impl ActTask for Act {
             Act::Req(req) => req.review(ctx),
             Act::Call(u) => u.review(ctx),
             Act::Block(b) => b.review(ctx),
+            Act::Loop(l) => l.review(ctx),
+            Act::Each(e) => e.review(ctx),
             Act::Pack(p) => p.review(ctx),
+            Act::Wait(w) => w.review(ctx),
+            Act::Delay(d) => d.review(ctx),
             _ => Ok(true),
         }
     }
+
+    fn error(&self, ctx: &Context) -> Result<()> {
+        match self {
+            Act::Each(e) => e.error(ctx),
+            _ => ctx.emit_error(),
+        }
+    }
 }
 
 impl Act {
@@ -76,22 +104,22 @@ impl Act {
             }
             Act::Req(req) => {
                 let mut req = req.clone();
-                if let Some(v) = ctx.get_var::<u32>(consts::ACT_INDEX) {
+                if let Some(v) = ctx.get_var::<JsonValue>(consts::ACT_INDEX) {
                     req.inputs.set(consts::ACT_INDEX, v);
                 }
 
-                if let Some(v) = ctx.get_var::<String>(consts::ACT_VALUE) {
+                if let Some(v) = ctx.get_var::<JsonValue>(consts::ACT_VALUE) {
                     req.inputs.set(consts::ACT_VALUE, v);
                 }
                 ctx.append_act(&Act::Req(req))?;
             }
             Act::Msg(msg) => {
                 let mut msg = msg.clone();
-                if let Some(v) = ctx.get_var::<u32>(consts::ACT_INDEX) {
+                if let Some(v) = ctx.get_var::<JsonValue>(consts::ACT_INDEX) {
                     msg.inputs.set(consts::ACT_INDEX, v);
                 }
 
-                if let Some(v) = ctx.get_var::<String>(consts::ACT_VALUE) {
+                if let Some(v) = ctx.get_var::<JsonValue>(consts::ACT_VALUE) {
                     msg.inputs.set(consts::ACT_VALUE, v);
                 }
                 if task.state().is_none() {
@@ -116,6 +144,15 @@ impl Act {
             Act::Pack(p) => {
                 ctx.append_act(&Act::Pack(p.clone()))?;
             }
+            Act::Loop(l) => {
+                ctx.append_act(&Act::Loop(l.clone()))?;
+            }
+            Act::Wait(w) => {
+                ctx.append_act(&Act::Wait(w.clone()))?;
+            }
+            Act::Delay(d) => {
+                ctx.append_act(&Act::Delay(d.clone()))?;
+            }
             Act::If(cond) => {
                 let result = ctx.eval(&cond.on)?;
                 if result {
@@ -128,37 +165,39 @@ impl Act {
                     }
                 }
             }
+            Act::Each(each) if each.is_taskable() => {
+                ctx.append_act(&Act::Each(each.clone()))?;
+            }
             Act::Each(each) => {
                 let cans = each.parse(ctx, &each.r#in)?;
-                for (index, value) in cans.iter().enumerate() {
-                    ctx.set_var(consts::ACT_INDEX, index);
-                    ctx.set_var(consts::ACT_VALUE, value);
-                    for s in &each.run {
+                for (index, value) in cans.iter() {
+                    let vars = each.item_vars(index, value);
+                    for (key, value) in &vars {
+                        ctx.set_var(key, value);
+                    }
+                    for s in &each.item_acts(&vars) {
                         s.exec(ctx)?;
                     }
                 }
             }
             Act::Chain(chain) => {
                 let cans = chain.parse(ctx, &chain.r#in)?;
-                let stmts = &chain.run;
-                let mut items = cans.iter().enumerate();
+                let mut items = cans.iter();
                 if let Some((index, value)) = items.next() {
                     let head = Rc::new(RefCell::new(Block::new()));
+                    let vars = chain.item_vars(index, value);
 
                     head.borrow_mut().id = utils::shortid();
-                    head.borrow_mut().inputs = Vars::new()
-                        .with(consts::ACT_INDEX, index)
-                        .with(consts::ACT_VALUE, value);
-                    head.borrow_mut().acts = stmts.clone();
+                    head.borrow_mut().acts = chain.item_acts(&vars);
+                    head.borrow_mut().inputs = vars;
 
                     let mut pre = head.clone();
                     while let Some((index, value)) = items.next() {
                         let p = Rc::new(RefCell::new(Block::new()));
+                        let vars = chain.item_vars(index, value);
                         p.borrow_mut().id = utils::shortid();
-                        p.borrow_mut().inputs = Vars::new()
-                            .with(consts::ACT_INDEX, index)
-                            .with(consts::ACT_VALUE, value);
-                        p.borrow_mut().acts = stmts.clone();
+                        p.borrow_mut().acts = chain.item_acts(&vars);
+                        p.borrow_mut().inputs = vars;
 
                         pre.borrow_mut().next = Some(Box::new((*p).clone().into_inner()));
                         pre = p;
@@ -213,6 +252,12 @@ impl Act {
                     task.add_hook_timeout(TaskLifeCycle::Timeout, s);
                 }
             }
+            Act::Include(include) => {
+                return Err(ActError::Runtime(format!(
+                    "the include '{}' is not expanded",
+                    include.uses
+                )));
+            }
         }
         Ok(())
     }
@@ -68,17 +68,17 @@ impl ActTask for Block {
             let tasks = task.children();
 
             let mut count = 0;
-            for task in tasks.iter() {
-                if task.state().is_error() {
+            for t in tasks.iter() {
+                if t.state().is_error() {
                     ctx.emit_error()?;
                     return Ok(false);
                 }
-                if task.state().is_skip() {
+                if t.state().is_skip() {
                     task.set_state(TaskState::Skipped);
                     return Ok(true);
                 }
 
-                if task.state().is_success() {
+                if t.state().is_success() {
                     count += 1;
                 }
             }
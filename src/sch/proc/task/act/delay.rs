@@ -0,0 +1,48 @@
+use crate::{
+    sch::{Context, TaskState},
+    utils::{self, consts},
+    ActError, ActTask, Delay, Result, TimeoutLimit,
+};
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+
+#[async_trait]
+impl ActTask for Delay {
+    fn init(&self, ctx: &Context) -> Result<()> {
+        let task = ctx.task();
+        let due = self.due(ctx, utils::time::time_millis())?;
+        task.set_data_with(move |data| data.set(consts::DELAY_UNTIL, due));
+        task.set_state(TaskState::Interrupt);
+
+        // the timer service resumes the task when it is due
+        ctx.runtime.timer().add(due, &task.pid, &task.id);
+        Ok(())
+    }
+}
+
+impl Delay {
+    /// the time in millis to resume the task
+    fn due(&self, ctx: &Context, now: i64) -> Result<i64> {
+        if let Some(duration) = &self.duration {
+            let millis = TimeoutLimit::parse(duration)?.as_millis();
+            return match &self.calendar {
+                Some(name) => ctx.runtime.calendar(name)?.add(now, millis),
+                None => Ok(now + millis),
+            };
+        }
+
+        let Some(until) = &self.until else {
+            return Err(ActError::Runtime(format!(
+                "the delay '{}' requires 'duration' or 'until'",
+                self.id
+            )));
+        };
+        let value = match until.as_str().and_then(utils::get_expr) {
+            Some(expr) => ctx.eval::<JsonValue>(&expr)?,
+            None => until.clone(),
+        };
+        utils::time::to_millis(&value).ok_or(ActError::Runtime(format!(
+            "the delay until '{value}' is invalid"
+        )))
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{
+    sch::Context,
+    utils::{consts, shortid},
+    Act, ActTask, Block, Each, EachMode, Result, TaskState, Vars,
+};
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+
+#[async_trait]
+impl ActTask for Each {
+    fn init(&self, ctx: &Context) -> Result<()> {
+        ctx.task().set_emit_disabled(true);
+        Ok(())
+    }
+
+    fn run(&self, ctx: &Context) -> Result<()> {
+        // evaluate the items once and keep them for the review
+        let items = self.parse(ctx, &self.r#in)?;
+        ctx.task()
+            .set_data_with(|data| data.set(consts::EACH_ITEMS, &items));
+        match self.mode {
+            EachMode::Parallel => {
+                for (index, value) in items.iter() {
+                    self.append(ctx, index, value)?;
+                }
+            }
+            EachMode::Sequential => {
+                if let Some((index, value)) = items.first() {
+                    self.append(ctx, index, value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn next(&self, ctx: &Context) -> Result<bool> {
+        Ok(ctx.task().state().is_running())
+    }
+
+    fn review(&self, ctx: &Context) -> Result<bool> {
+        let task = ctx.task();
+        if task.state().is_running() {
+            let tasks = task.children();
+            let items = task
+                .with_data(|data| data.get::<Vec<(JsonValue, JsonValue)>>(consts::EACH_ITEMS))
+                .unwrap_or_default();
+            let total = items.len();
+            let completed = tasks.iter().filter(|t| t.state().is_success()).count();
+            let skipped = tasks.iter().filter(|t| t.state().is_skip()).count();
+            let errored = tasks.iter().filter(|t| t.state().is_error()).count();
+            let finished = completed + skipped + errored;
+            task.update_data(
+                &Vars::new()
+                    .with("total", total)
+                    .with("completed", completed)
+                    .with("skipped", skipped)
+                    .with("errored", errored)
+                    .with("finished", finished),
+            );
+
+            if let Some(expr) = &self.until {
+                let is_done = ctx.eval::<bool>(&format!(
+                    "((total, completed, skipped, errored, finished) => ({expr}))({total}, {completed}, {skipped}, {errored}, {finished})"
+                ))?;
+                if is_done {
+                    for t in tasks.iter() {
+                        ctx.cancel_task(t)?;
+                    }
+                    task.set_state(TaskState::Completed);
+                    return Ok(true);
+                }
+            }
+
+            // the errored instance fails the each if there is no `until`,
+            // or all of the instances are finished without meeting the `until`
+            if let Some(err) = tasks.iter().find_map(|t| t.err()) {
+                if self.until.is_none() || finished == total {
+                    task.set_err(&err);
+                    ctx.emit_error()?;
+                    return Ok(false);
+                }
+            }
+
+            if tasks.iter().any(|t| !t.state().is_completed()) {
+                return Ok(false);
+            }
+
+            if let Some((index, value)) = items.get(tasks.len()) {
+                self.append(ctx, index, value)?;
+                return Ok(false);
+            }
+            task.set_state(TaskState::Completed);
+        }
+
+        Ok(true)
+    }
+
+    fn error(&self, ctx: &Context) -> Result<()> {
+        let task = ctx.task();
+        if self.until.is_some() && task.children().iter().any(|t| t.state().is_error()) {
+            // review the errored instance by the `until` instead of failing at once
+            task.set_state(TaskState::Running);
+            task.review(ctx)?;
+            return Ok(());
+        }
+
+        ctx.emit_error()
+    }
+}
+
+impl Each {
+    fn append(&self, ctx: &Context, index: &JsonValue, value: &JsonValue) -> Result<()> {
+        let vars = self.item_vars(index, value);
+        let block = Block {
+            acts: self.item_acts(&vars),
+            inputs: vars,
+            ..Block::new().with_id(&shortid())
+        };
+        ctx.append_act(&Act::Block(block))?;
+        Ok(())
+    }
+}
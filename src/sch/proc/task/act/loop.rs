@@ -0,0 +1,133 @@
+use crate::{
+    sch::{Context, Task},
+    utils::{consts, shortid},
+    Act, ActError, ActTask, Block, Error, Loop, Result, TaskState, Vars,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+#[async_trait]
+impl ActTask for Loop {
+    fn init(&self, ctx: &Context) -> Result<()> {
+        ctx.task().set_emit_disabled(true);
+        if self.max == 0 {
+            return Err(ActError::Runtime(format!(
+                "loop's 'max' must be greater than 0 in task({})",
+                ctx.task().id
+            )));
+        }
+        Ok(())
+    }
+
+    fn run(&self, ctx: &Context) -> Result<()> {
+        if self.is_continue(ctx, 0)? {
+            self.append(ctx, 0)?;
+        } else {
+            ctx.task().set_state(TaskState::Completed);
+        }
+        Ok(())
+    }
+
+    fn next(&self, ctx: &Context) -> Result<bool> {
+        Ok(ctx.task().state().is_running())
+    }
+
+    fn review(&self, ctx: &Context) -> Result<bool> {
+        let task = ctx.task();
+        if task.state().is_running() {
+            let tasks = task.children();
+            for task in tasks.iter() {
+                if task.state().is_error() {
+                    ctx.emit_error()?;
+                    return Ok(false);
+                }
+                if !task.state().is_completed() {
+                    return Ok(false);
+                }
+            }
+
+            // keep the data of the last iteration in the loop task
+            if let Some(last) = tasks.last() {
+                let mut vars = Vars::new();
+                collect_data(last, &mut vars);
+                task.update_data(&vars);
+            }
+
+            let index = tasks.len();
+            if self.is_continue(ctx, index)? {
+                if index < self.max as usize {
+                    self.append(ctx, index)?;
+                    return Ok(false);
+                }
+
+                // the condition still holds after the max iterations
+                // the loop without conditions just repeats `max` times
+                if self.r#while.is_some() || self.until.is_some() {
+                    task.set_err(&Error::new(
+                        &format!(
+                            "loop exceeds the max iterations {} in task({})",
+                            self.max, task.id
+                        ),
+                        consts::ERR_LOOP_MAX,
+                    ));
+                    ctx.emit_error()?;
+                    return Ok(false);
+                }
+            }
+            task.set_state(TaskState::Completed);
+        }
+
+        Ok(true)
+    }
+}
+
+impl Loop {
+    fn index_name(&self) -> &str {
+        self.index.as_deref().unwrap_or(consts::LOOP_INDEX)
+    }
+
+    /// the conditions see the count of the finished iterations as the index var
+    fn is_continue(&self, ctx: &Context, index: usize) -> Result<bool> {
+        ctx.task()
+            .update_data(&Vars::new().with(self.index_name(), index));
+        if let Some(expr) = &self.r#while {
+            if !ctx.eval::<bool>(expr)? {
+                return Ok(false);
+            }
+        }
+
+        // the until is checked after the first iteration
+        if let Some(expr) = &self.until {
+            if index > 0 && ctx.eval::<bool>(expr)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn append(&self, ctx: &Context, index: usize) -> Result<()> {
+        let block = Block::new()
+            .with_id(&shortid())
+            .with_input(self.index_name(), index);
+        let block = Block {
+            acts: self.run.clone(),
+            ..block
+        };
+        ctx.append_act(&Act::Block(block))?;
+        Ok(())
+    }
+}
+
+fn collect_data(task: &Arc<Task>, vars: &mut Vars) {
+    for (key, value) in &task.data() {
+        if key == consts::TASK_EMIT_DISABLED {
+            continue;
+        }
+        vars.insert(key.clone(), value.clone());
+    }
+
+    for child in task.children() {
+        collect_data(&child, vars);
+    }
+}
@@ -12,7 +12,9 @@ impl ActTask for Pack {
         let task = ctx.task();
         let pack = ctx.runtime.cache().store().packages().find(&self.uses)?;
         let script: String = String::from_utf8(pack.file_data).map_err(ActError::from)?;
-        ctx.eval(&script)?;
+        // the own limits go before the package ones
+        let limits = self.limits.clone().unwrap_or_default();
+        ctx.eval_with(&script, &limits.or(&pack.limits.unwrap_or_default()))?;
 
         if task.state().is_running() {
             task.set_state(TaskState::Completed);
@@ -0,0 +1,23 @@
+use super::TaskLifeCycle;
+use crate::{
+    sch::{Context, TaskState},
+    utils::{self, consts},
+    ActTask, Result, Wait,
+};
+use async_trait::async_trait;
+
+#[async_trait]
+impl ActTask for Wait {
+    fn init(&self, ctx: &Context) -> Result<()> {
+        let task = ctx.task();
+        for s in &self.timeout {
+            task.add_hook_timeout(TaskLifeCycle::Timeout, s);
+        }
+
+        // resolve the correlation key when starting to wait
+        let key = utils::fill_template(&self.key, ctx)?;
+        task.set_data_with(move |data| data.set(consts::WAIT_KEY, &key));
+        task.set_state(TaskState::Interrupt);
+        Ok(())
+    }
+}
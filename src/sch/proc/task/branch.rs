@@ -1,6 +1,6 @@
 use crate::{
     model::Branch,
-    sch::{Context, TaskState},
+    sch::{Context, NodeContent, TaskState},
     ActTask, Result,
 };
 use async_trait::async_trait;
@@ -27,8 +27,16 @@ impl ActTask for Branch {
             }
             None => {
                 let mut branch_count = 1;
+                let mut is_join = false;
                 if let Some(parent) = task.node.parent() {
                     branch_count = parent.children().len();
+                    is_join =
+                        matches!(&parent.content, NodeContent::Step(step) if step.join.is_some());
+                }
+
+                // the branch without if runs in parallel when the step has join policy
+                if is_join && !self.r#else {
+                    return Ok(());
                 }
 
                 if !self.r#else {
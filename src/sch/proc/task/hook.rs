@@ -1,8 +1,9 @@
 use crate::{
     utils::{self, consts},
-    Act, ActTask, Catch, Context, Result, TaskState, Timeout,
+    Act, ActError, ActTask, Catch, Context, NodeKind, Result, TaskState, Timeout, TimeoutMode,
 };
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 
 #[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum TaskLifeCycle {
@@ -17,9 +18,9 @@ pub enum TaskLifeCycle {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum StatementBatch {
-    Statement(Act),
+    Statement(Box<Act>),
     Catch(Catch),
-    Timeout(Timeout),
+    Timeout(Box<Timeout>),
 }
 
 impl StatementBatch {
@@ -52,20 +53,28 @@ impl StatementBatch {
             }
             StatementBatch::Timeout(t) => {
                 let task = ctx.task();
-                let key = format!("{}{}", consts::IS_TIMEOUT_PROCESSED_PREFIX, t.on);
-                let is_timeout_processed = task
-                    .with_data(|data| data.get::<bool>(&key))
-                    .unwrap_or_default();
-                if is_timeout_processed {
+                let Some(due) = t.next_due(ctx)? else {
                     return Ok(());
-                }
-
-                let millis = utils::time::time_millis() - task.start_time();
-                if millis >= t.on.as_secs() * 1000 {
-                    task.set_data_with(|data| data.set(&key, true));
+                };
+                if utils::time::time_millis() >= due {
+                    let (key, count) = t.fired(ctx);
+                    task.set_data_with(|data| data.set(&key, count + 1));
+                    let children = task.children();
                     for s in &t.then {
                         s.exec(ctx)?;
                     }
+
+                    if t.mode == TimeoutMode::Interrupting {
+                        for child in &children {
+                            ctx.cancel_task(child)?;
+                        }
+                        if task.is_kind(NodeKind::Act) {
+                            task.set_state(TaskState::Completed);
+                            task.next(ctx)?;
+                        } else {
+                            task.review(ctx)?;
+                        }
+                    }
                 }
             }
         }
@@ -73,3 +82,61 @@ impl StatementBatch {
         Ok(())
     }
 }
+
+impl Timeout {
+    /// the data key and the fired count of the timeout
+    fn fired(&self, ctx: &Context) -> (String, u32) {
+        let key = format!("{}{}", consts::IS_TIMEOUT_PROCESSED_PREFIX, self.key());
+
+        // the old data saves true when the timeout is processed
+        let count = ctx
+            .task()
+            .with_data(|data| match data.get::<JsonValue>(&key) {
+                Some(JsonValue::Bool(true)) => 1,
+                Some(JsonValue::Number(n)) => n.as_u64().unwrap_or_default() as u32,
+                _ => 0,
+            });
+        (key, count)
+    }
+
+    /// the time in millis to fire the timeout next time
+    /// returns `None` when the task is completed or the timeout has fired `max` times
+    pub(crate) fn next_due(&self, ctx: &Context) -> Result<Option<i64>> {
+        let task = ctx.task();
+        if task.state().is_completed() {
+            return Ok(None);
+        }
+
+        let (_, count) = self.fired(ctx);
+        let max = match &self.every {
+            Some(_) => self.max.unwrap_or(u32::MAX),
+            None => 1,
+        };
+        if count >= max {
+            return Ok(None);
+        }
+
+        let (start, mut millis) = match &self.until {
+            Some(until) => {
+                let value = match until.as_str().and_then(utils::get_expr) {
+                    Some(expr) => ctx.eval::<JsonValue>(&expr)?,
+                    None => until.clone(),
+                };
+                let start = utils::time::to_millis(&value).ok_or(ActError::Runtime(format!(
+                    "the timeout until '{value}' is invalid"
+                )))?;
+                (start, 0)
+            }
+            None => (task.start_time(), self.on.as_millis()),
+        };
+        if let Some(every) = &self.every {
+            millis += every.as_millis() * count as i64;
+        }
+
+        let due = match &self.calendar {
+            Some(name) => ctx.runtime.calendar(name)?.add(start, millis)?,
+            None => start + millis,
+        };
+        Ok(Some(due))
+    }
+}
@@ -1,10 +1,11 @@
 use super::hook::TaskLifeCycle;
 use crate::{
-    model::Step,
-    sch::{Context, NodeContent, TaskState},
-    ActError, ActTask, Result, StoreAdapter,
+    model::{Join, Step},
+    sch::{Context, NodeContent, Task, TaskState},
+    ActError, ActTask, NodeKind, Result, StoreAdapter,
 };
 use async_trait::async_trait;
+use std::sync::Arc;
 
 #[async_trait]
 impl ActTask for Step {
@@ -51,7 +52,9 @@ impl ActTask for Step {
         if let Some(pack_id) = &self.uses {
             let pack = ctx.runtime.cache().store().packages().find(pack_id)?;
             let script: String = String::from_utf8(pack.file_data).map_err(ActError::from)?;
-            ctx.eval(&script)?;
+            // the own limits go before the package ones
+            let limits = self.limits.clone().unwrap_or_default();
+            ctx.eval_with(&script, &limits.or(&pack.limits.unwrap_or_default()))?;
         }
         let children = task.node.children();
         if children.len() > 0 {
@@ -77,6 +80,7 @@ impl ActTask for Step {
         let mut is_next: bool = false;
         if state.is_running() {
             let tasks = task.children();
+            self.join(ctx, &tasks)?;
             let mut count = 0;
 
             for task in tasks.iter() {
@@ -120,6 +124,7 @@ impl ActTask for Step {
         let state = task.state();
         if state.is_running() {
             let tasks = task.children();
+            self.join(ctx, &tasks)?;
             let mut count = 0;
             for task in tasks.iter() {
                 if task.state().is_pending() && task.is_ready() {
@@ -157,3 +162,42 @@ impl ActTask for Step {
         return Ok(false);
     }
 }
+
+impl Step {
+    /// cancel the branches which are not completed if the join policy is satisfied
+    fn join(&self, ctx: &Context, tasks: &[Arc<Task>]) -> Result<()> {
+        let Some(join) = &self.join else {
+            return Ok(());
+        };
+
+        let branches = tasks
+            .iter()
+            .filter(|t| t.is_kind(NodeKind::Branch))
+            .collect::<Vec<_>>();
+        if branches.iter().all(|t| t.state().is_completed()) {
+            return Ok(());
+        }
+
+        let joined = branches.iter().filter(|t| t.state().is_success()).count();
+        let total = branches
+            .iter()
+            .filter(|t| !t.state().is_skip() && !t.state().is_pending())
+            .count();
+        let is_joined = match join {
+            Join::All => false,
+            Join::Any => joined > 0,
+            Join::Count(n) => joined >= *n,
+            Join::Expr(expr) => {
+                ctx.eval::<bool>(&format!("((joined, total) => ({expr}))({joined}, {total})"))?
+            }
+        };
+
+        if is_joined {
+            for t in branches {
+                ctx.cancel_task(t)?;
+            }
+        }
+
+        Ok(())
+    }
+}
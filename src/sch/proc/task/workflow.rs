@@ -1,4 +1,4 @@
-use crate::{sch::Context, ActTask, Result, TaskState, Workflow};
+use crate::{sch::Context, utils::consts, ActTask, Error, Result, TaskState, Workflow};
 use async_trait::async_trait;
 
 #[async_trait]
@@ -48,6 +48,24 @@ impl ActTask for Workflow {
         let task = ctx.task();
         let state = task.state();
         if state.is_running() {
+            // end with the saved state after all of the compensations are completed
+            if let Some(end) = task.with_data(|data| data.get::<String>(consts::COMPENSATE_END)) {
+                if !task.children().iter().all(|t| t.state().is_completed()) {
+                    return Ok(false);
+                }
+                ctx.finish_compensate(&task, TaskState::Completed)?;
+                match task.with_data(|data| data.get::<Error>(consts::COMPENSATE_ERR)) {
+                    Some(err) => task.set_err(&err),
+                    None => task.set_state(TaskState::from(end)),
+                }
+                return Ok(true);
+            }
+
+            // the outputs must match the schema to complete the proc
+            if let Err(err) = self.schema.check_outputs(&task.outputs()) {
+                task.set_err(&Error::new(&err.to_string(), consts::ERR_OUTPUTS));
+                return Ok(true);
+            }
             task.set_state(TaskState::Completed);
             return Ok(true);
         }
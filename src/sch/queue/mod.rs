@@ -1,5 +1,5 @@
 mod queue;
 mod signal;
 
-pub use queue::Queue;
+pub use queue::{Backlog, Queue};
 pub use signal::Signal;
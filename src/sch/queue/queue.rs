@@ -1,20 +1,25 @@
 use crate::sch::queue::Signal;
-use std::sync::Arc;
-use tokio::{runtime::Handle, sync::mpsc, sync::Mutex};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tokio::{sync::mpsc, sync::Mutex};
 
 #[derive(Clone)]
 pub struct Queue {
-    receiver: Arc<Mutex<mpsc::Receiver<Signal>>>,
-    sender: Arc<mpsc::Sender<Signal>>,
+    receiver: Arc<Mutex<mpsc::UnboundedReceiver<Signal>>>,
+    sender: Arc<mpsc::UnboundedSender<Signal>>,
+    count: Arc<AtomicUsize>,
 }
 
 impl Queue {
     pub fn new() -> Arc<Self> {
-        let (tx, rx) = mpsc::channel::<Signal>(100);
+        let (tx, rx) = mpsc::unbounded_channel::<Signal>();
 
         let queue = Arc::new(Self {
             receiver: Arc::new(Mutex::new(rx)),
             sender: Arc::new(tx),
+            count: Arc::new(AtomicUsize::new(0)),
         });
 
         queue
@@ -22,16 +27,69 @@ impl Queue {
 
     pub async fn next(&self) -> Option<Signal> {
         let receiver = &mut *self.receiver.lock().await;
-        receiver.recv().await
+        let ret = receiver.recv().await;
+        if ret.is_some() {
+            self.count.fetch_sub(1, Ordering::SeqCst);
+        }
+        ret
     }
 
+    /// enqueue the signal without waiting
+    /// the signals are received in the same order as they are sent
     pub(crate) fn send(&self, sig: &Signal) {
-        let sender = self.sender.clone();
-        let sig = sig.clone();
-        Handle::current().spawn(async move { sender.send(sig).await });
+        self.count.fetch_add(1, Ordering::SeqCst);
+        if self.sender.send(sig.clone()).is_err() {
+            self.count.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// the count of the signals waiting in the queue
+    pub fn len(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
     }
 
     pub fn terminate(&self) {
         self.send(&Signal::Terminal);
     }
 }
+
+/// The unbounded backlog of one scheduler worker
+/// pushing never waits, so that a busy worker does not block dispatching the other procs
+pub struct Backlog {
+    receiver: Mutex<mpsc::UnboundedReceiver<Signal>>,
+    sender: mpsc::UnboundedSender<Signal>,
+    count: AtomicUsize,
+}
+
+impl Backlog {
+    pub fn new() -> Arc<Self> {
+        let (tx, rx) = mpsc::unbounded_channel::<Signal>();
+        Arc::new(Self {
+            receiver: Mutex::new(rx),
+            sender: tx,
+            count: AtomicUsize::new(0),
+        })
+    }
+
+    pub async fn next(&self) -> Option<Signal> {
+        let receiver = &mut *self.receiver.lock().await;
+        let ret = receiver.recv().await;
+        if ret.is_some() {
+            self.count.fetch_sub(1, Ordering::SeqCst);
+        }
+        ret
+    }
+
+    /// push the signal in order without waiting
+    pub fn push(&self, sig: Signal) {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        if self.sender.send(sig).is_err() {
+            self.count.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// the count of the signals waiting in the backlog
+    pub fn len(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+}
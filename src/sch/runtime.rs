@@ -1,16 +1,29 @@
 use tokio::{runtime::Handle, time};
 use tracing::{debug, error};
 
-use super::{Proc, Scheduler, Task};
+use super::{Node, Proc, Scheduler, Task, TaskState, Timer};
 use crate::{
     cache::Cache,
     data,
     env::Enviroment,
     event::Emitter,
+    handler::Handlers,
+    store::{Cond, Expr, Query, StoreAdapter},
     utils::{self, consts},
-    ActError, Action, ActionResult, Config, Engine, Result, Vars, Workflow,
+    ActError, Action, ActionResult, Calendar, CatchUp, Config, Engine, HistoryMode, Limits, Result,
+    TimeoutLimit, Trigger, Vars, Workflow,
 };
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    time::Duration,
+};
+
+/// the max fires of a trigger in one tick
+const MAX_TRIGGER_FIRES: usize = 100;
 
 #[derive(Debug, Clone)]
 pub struct Runtime {
@@ -19,6 +32,9 @@ pub struct Runtime {
     env: Arc<Enviroment>,
     cache: Arc<Cache>,
     emitter: Arc<Emitter>,
+    timer: Arc<Timer>,
+    calendars: Arc<RwLock<HashMap<String, Calendar>>>,
+    handlers: Arc<Handlers>,
 }
 
 impl Runtime {
@@ -43,6 +59,32 @@ impl Runtime {
         &self.env
     }
 
+    pub fn timer(&self) -> &Arc<Timer> {
+        &self.timer
+    }
+
+    pub fn add_calendar(&self, calendar: &Calendar) -> Result<()> {
+        calendar.valid()?;
+        self.calendars
+            .write()
+            .unwrap()
+            .insert(calendar.name.clone(), calendar.clone());
+        Ok(())
+    }
+
+    pub fn calendar(&self, name: &str) -> Result<Calendar> {
+        self.calendars
+            .read()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .ok_or(ActError::Runtime(format!("cannot find calendar '{name}'")))
+    }
+
+    pub fn handlers(&self) -> &Arc<Handlers> {
+        &self.handlers
+    }
+
     pub fn emitter(&self) -> &Arc<Emitter> {
         &self.emitter
     }
@@ -61,6 +103,8 @@ impl Runtime {
         self.scher.init(engine);
         self.cache.init(engine);
         self.emitter.init(&engine.runtime());
+        self.restore_timers()
+            .unwrap_or_else(|err| error!("runtime.init restore_timers={}", err));
     }
 
     pub fn start(self: &Arc<Self>, model: &Workflow, options: &Vars) -> Result<Arc<Proc>> {
@@ -137,9 +181,178 @@ impl Runtime {
         state.end_with_result(ret)
     }
 
+    /// start the procs of the due cron triggers
+    /// the missed fires follow the trigger `catch_up` policy when `is_restart` is true
+    pub(crate) fn fire_triggers(
+        self: &Arc<Self>,
+        now: i64,
+        is_restart: bool,
+    ) -> Result<Vec<Arc<Proc>>> {
+        let store = self.cache.store();
+        let query = Query::new().push(Cond::and().push(Expr::le("next_time", now)));
+        let mut procs = Vec::new();
+        for mut data in store.triggers().query(&query)? {
+            let trigger: Trigger = serde_json::from_str(&data.data).map_err(ActError::from)?;
+            let mut times = trigger.fire_times(data.last_time, now, MAX_TRIGGER_FIRES)?;
+            if is_restart {
+                match trigger.catch_up {
+                    CatchUp::Skip => times.clear(),
+                    CatchUp::Once => times.truncate(1),
+                    CatchUp::All => {}
+                }
+            }
+
+            for time in &times {
+                debug!("fire_trigger: id={} time={time}", data.id);
+                match self.start_trigger(&data.mid, &trigger, *time) {
+                    Ok(proc) => procs.push(proc),
+                    Err(err) => error!("fire_trigger id={} err={}", data.id, err),
+                }
+            }
+
+            data.last_time = now;
+            data.next_time = trigger.next_time(now)?;
+            store.triggers().update(&data)?;
+        }
+
+        Ok(procs)
+    }
+
+    /// resume the waiting tasks matched by the signal name and the correlation key
+    /// returns the resumed task count
+    pub fn signal(self: &Arc<Self>, name: &str, key: &str, vars: &Vars) -> Result<usize> {
+        let state: String = TaskState::Interrupt.into();
+        let query = Query::new().push(
+            Cond::and()
+                .push(Expr::eq("kind", consts::ACT_TYPE_WAIT))
+                .push(Expr::eq("name", name))
+                .push(Expr::eq("state", state))
+                .push(Expr::eq("wait_key", key)),
+        );
+        let mut count = 0;
+        for task in self.cache.store().tasks().query(&query)? {
+            debug!(
+                "signal: name={name} key={key} pid={} tid={}",
+                task.pid, task.tid
+            );
+            let action = Action::new(&task.pid, &task.tid, consts::EVT_NEXT, vars);
+            match self.do_action(&action) {
+                Ok(_) => count += 1,
+                Err(err) => error!("signal pid={} tid={} err={}", task.pid, task.tid, err),
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// re-arm the timers of the delayed and retrying tasks from the store
+    pub(crate) fn restore_timers(&self) -> Result<()> {
+        let state: String = TaskState::Interrupt.into();
+        let query = Query::new().push(Cond::and().push(Expr::eq("state", state.clone())));
+        for task in self.cache.store().tasks().query(&query)? {
+            if task.state != state {
+                continue;
+            }
+            let data: Vars = serde_json::from_str(&task.data).map_err(ActError::from)?;
+            let due = match task.kind.as_str() {
+                consts::ACT_TYPE_DELAY => data.get::<i64>(consts::DELAY_UNTIL),
+                _ => data.get::<i64>(consts::RETRY_AT),
+            };
+            if let Some(due) = due {
+                debug!("restore_timer: pid={} tid={} due={due}", task.pid, task.tid);
+                self.timer.add(due, &task.pid, &task.tid);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// run the handlers again for the pending `req` tasks from the store
+    /// the handlers are registered after the engine is initialized, so it is called in every registration
+    pub(crate) fn restore_handlers(self: &Arc<Self>) -> Result<()> {
+        let state: String = TaskState::Interrupt.into();
+        let query = Query::new().push(
+            Cond::and()
+                .push(Expr::eq("state", state.clone()))
+                .push(Expr::eq("kind", consts::ACT_TYPE_REQ)),
+        );
+        let mut model_tags = HashMap::new();
+        for task in self.cache.store().tasks().query(&query)? {
+            if task.state != state || task.kind != consts::ACT_TYPE_REQ {
+                continue;
+            }
+            // only load the procs of the tasks matched by the handlers
+            let Some((key, tag)) = Node::key_tag_from_str(&task.node_data) else {
+                continue;
+            };
+            let model_tag: &String = model_tags.entry(task.pid.clone()).or_insert_with(|| {
+                self.cache
+                    .store()
+                    .procs()
+                    .find(&task.pid)
+                    .and_then(|p| Workflow::from_json(&p.model))
+                    .map(|model| model.tag)
+                    .unwrap_or_default()
+            });
+            if !self.handlers.is_match(&key, &tag, model_tag) {
+                continue;
+            }
+
+            // the task may be completed by others
+            let task = match self.proc(&task.pid).and_then(|proc| proc.task(&task.tid)) {
+                Some(task) if task.state().is_interrupted() => task,
+                _ => continue,
+            };
+            debug!("restore_handler: pid={} tid={}", task.pid, task.id);
+            self.handlers.dispatch(self, &task.create_message());
+        }
+
+        Ok(())
+    }
+
+    /// resume the delayed task or re-run the retrying task when its timer is due
+    fn fire_timer(self: &Arc<Self>, pid: &str, tid: &str) -> Result<()> {
+        let Some(proc) = self.proc(pid) else {
+            return Ok(());
+        };
+
+        // check and reset the task with the proc lock held, not to race with the running tasks
+        proc.sync(|| {
+            // the task may be completed or canceled by others
+            let task = match proc.task(tid) {
+                Some(task) if task.state().is_interrupted() => task,
+                _ => return Ok(()),
+            };
+            if task
+                .with_data(|data| data.get::<i64>(consts::RETRY_AT))
+                .is_some()
+            {
+                self.rerun_task(&task);
+                return Ok(());
+            }
+            self.do_action(&Action::new(pid, tid, consts::EVT_NEXT, &Vars::new()))?;
+            Ok(())
+        })
+    }
+
+    /// run the task again from the beginning for the retry
+    /// it is called with the proc lock held
+    fn rerun_task(&self, task: &Arc<Task>) {
+        debug!("rerun_task: {task:?}");
+        task.set_data_with(|data| {
+            data.remove(consts::RETRY_AT);
+        });
+
+        // the hooks are added again when initializing the task
+        task.set_hooks(&HashMap::new());
+        task.set_state(TaskState::None);
+        self.push(task);
+    }
+
     pub fn event_loop(self: &Arc<Self>) {
         let scher = self.scher.clone();
         let cache = self.cache.clone();
+        scher.run_workers();
         tokio::spawn(async move {
             loop {
                 let ret = scher.next().await;
@@ -154,14 +367,22 @@ impl Runtime {
     fn create(config: &Config) -> Arc<Runtime> {
         let scher = Scheduler::new_with(config);
         let env = Arc::new(Enviroment::new());
+        env.set_limits(Limits {
+            timeout: config.script_timeout.map(TimeoutLimit::from_millis),
+            memory: config.script_memory,
+        });
         let cache = Arc::new(Cache::new(config.cache_cap));
         let emitter = Arc::new(Emitter::new());
+        let timer = Arc::new(Timer::new());
         let runtime = Arc::new(Runtime {
             config: Arc::new(config.clone()),
             emitter,
             scher,
             env,
             cache,
+            timer,
+            calendars: Arc::new(RwLock::new(HashMap::new())),
+            handlers: Arc::new(Handlers::new()),
         });
 
         runtime.initialize(config);
@@ -194,7 +415,12 @@ impl Runtime {
 
                         // proc.print();
                         debug!("remove: {:?}", proc.tasks());
-                        cache.remove(&proc.id()).unwrap_or_else(|err| {
+                        let result = match rt.config().history_mode {
+                            HistoryMode::Delete => cache.remove(proc.id()),
+                            HistoryMode::Keep => cache.keep(proc),
+                            HistoryMode::Archive => cache.archive(proc),
+                        };
+                        result.unwrap_or_else(|err| {
                             error!("scher.initialize remove={}", err);
                             false
                         });
@@ -233,6 +459,7 @@ impl Runtime {
                         let msg = e.create_message();
                         debug!("emit_message:{msg:?}");
                         rt.emitter().emit_message(&msg);
+                        rt.handlers().dispatch(&rt, &msg);
                     }
                 }
             });
@@ -255,7 +482,18 @@ impl Runtime {
 
             let evt = self.emitter().clone();
             let cache = self.cache.clone();
-            self.emitter().on_tick(move |_| {
+            let rt = self.clone();
+            let is_restart = AtomicBool::new(true);
+            self.emitter().on_tick(move |now| {
+                // start the procs of the due cron triggers
+                // the first tick catches up the fires missed while the engine was stopped
+                let now = *now;
+                let is_restart = is_restart.swap(false, Ordering::SeqCst);
+                rt.fire_triggers(now, is_restart).unwrap_or_else(|err| {
+                    error!("scher.initialize triggers={}", err);
+                    Vec::new()
+                });
+
                 // do the proc tick works
                 for proc in cache.procs().iter() {
                     if proc.state().is_running() {
@@ -265,6 +503,7 @@ impl Runtime {
 
                 // re-send the messages if it is neither acked or nor completed
                 cache.store().with_no_response_messages(
+                    now,
                     default_interval_millis,
                     max_message_retry_times,
                     |m| {
@@ -273,17 +512,82 @@ impl Runtime {
                 );
             });
 
+            // start the timer service
+            let timer = self.timer.clone();
+            let rt = Arc::downgrade(self);
+            Handle::current().spawn(async move {
+                loop {
+                    let now = utils::time::time_millis();
+                    let items = timer.take(now);
+                    if !items.is_empty() {
+                        let Some(rt) = rt.upgrade() else {
+                            break;
+                        };
+                        // the timer actions take the proc lock, so run them in the blocking thread
+                        let ret = tokio::task::spawn_blocking(move || {
+                            for (pid, tid) in items {
+                                rt.fire_timer(&pid, &tid).unwrap_or_else(|err| {
+                                    error!("scher.initialize timer pid={pid} tid={tid} err={err}")
+                                });
+                            }
+                        })
+                        .await;
+                        if let Err(err) = ret {
+                            error!("scher.initialize timer {err}");
+                        }
+                    }
+
+                    match timer.next_due() {
+                        Some(due) => {
+                            let millis = (due - utils::time::time_millis()).max(0) as u64;
+                            tokio::select! {
+                                _ = time::sleep(Duration::from_millis(millis)) => {}
+                                _ = timer.changed() => {}
+                            }
+                        }
+                        None => timer.changed().await,
+                    }
+                }
+            });
+
             let evt = self.emitter().clone();
             Handle::current().spawn(async move {
-                let mut intv = time::interval(Duration::from_millis(default_interval_millis));
+                let start = time::Instant::now();
+                let start_millis = utils::time::time_millis();
+                let mut intv =
+                    time::interval_at(start, Duration::from_millis(default_interval_millis));
                 loop {
-                    intv.tick().await;
-                    evt.emit_tick();
+                    // use the scheduled time of the tick, not the time when the blocking thread runs it,
+                    // so the message re-sending keeps the same interval
+                    let at = intv.tick().await;
+                    let time_millis = start_millis + at.duration_since(start).as_millis() as i64;
+
+                    // the tick works start procs and emit messages, which may take the proc lock
+                    let evt = evt.clone();
+                    if let Err(err) =
+                        tokio::task::spawn_blocking(move || evt.emit_tick(time_millis)).await
+                    {
+                        error!("scher.initialize tick {err}");
+                    }
                 }
             });
         }
     }
 
+    fn start_trigger(
+        self: &Arc<Self>,
+        mid: &str,
+        trigger: &Trigger,
+        time: i64,
+    ) -> Result<Arc<Proc>> {
+        let workflow = self.cache.store().workflow(mid, None)?;
+
+        let mut vars = trigger.inputs.clone();
+        workflow.schema.check_inputs(&mut vars)?;
+        vars.insert(consts::TRIGGER_TIME.to_string(), time.into());
+        self.start(&workflow, &vars)
+    }
+
     fn return_to_act(self: &Arc<Self>, pid: &str, tid: &str, proc: &Proc) {
         debug!("scher.return_to_act");
         let state = proc.state();
@@ -303,7 +607,7 @@ impl Runtime {
         }
         let action = Action::new(pid, tid, event, &vars);
         let scher = self.clone();
-        tokio::spawn(async move {
+        tokio::task::spawn_blocking(move || {
             let _ = scher
                 .do_action(&action)
                 .map_err(|err| error!("scher::return_to_act {}", err.to_string()));
@@ -2,17 +2,22 @@ use crate::{
     config::Config,
     event::{Emitter, TaskExtra},
     sch::{
-        queue::{Queue, Signal},
-        Proc, Task,
+        queue::{Backlog, Queue, Signal},
+        Proc, Task, TaskState,
     },
     Engine, Event, Result,
 };
-use std::sync::{Arc, Mutex};
-use tracing::debug;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex},
+};
+use tracing::{debug, error};
 
 #[derive(Clone)]
 pub struct Scheduler {
     queue: Arc<Queue>,
+    workers: Vec<Arc<Backlog>>,
     emitter: Arc<Emitter>,
     closed: Arc<Mutex<bool>>,
 }
@@ -28,9 +33,11 @@ impl Scheduler {
         Scheduler::new_with(&Config::default())
     }
 
-    pub fn new_with(_options: &Config) -> Arc<Self> {
+    pub fn new_with(options: &Config) -> Arc<Self> {
+        let count = options.worker_count.max(1);
         let scher = Arc::new(Self {
             queue: Queue::new(),
+            workers: (0..count).map(|_| Backlog::new()).collect(),
             emitter: Arc::new(Emitter::new()),
             closed: Arc::new(Mutex::new(false)),
         });
@@ -44,22 +51,48 @@ impl Scheduler {
 
     pub fn push(&self, task: &Arc<Task>) {
         debug!("sch::push  task={:?}", task);
+        task.proc().inc_queued();
         self.queue.send(&Signal::Task(task.clone()));
     }
 
+    /// start the workers to run the tasks dispatched by [`Scheduler::next`]
+    /// the tasks are executed on the blocking threads one by one in each worker,
+    /// so that the running scripts do not block the async runtime
+    pub fn run_workers(self: &Arc<Self>) {
+        for (index, worker) in self.workers.iter().enumerate() {
+            let worker = worker.clone();
+            tokio::spawn(async move {
+                debug!("sch::worker({index}) start");
+                while let Some(signal) = worker.next().await {
+                    match signal {
+                        Signal::Task(task) => {
+                            let ret = tokio::task::spawn_blocking(move || Self::exec(&task)).await;
+                            if let Err(err) = ret {
+                                error!("sch::worker({index}) {err}");
+                            }
+                        }
+                        Signal::Terminal => break,
+                    }
+                }
+                debug!("sch::worker({index}) exit");
+            });
+        }
+    }
+
     pub async fn next(self: &Arc<Self>) -> bool {
         if let Some(signal) = self.queue.next().await {
             debug!("next: {:?}", signal);
             match signal {
                 Signal::Task(task) => {
-                    let ctx = &task.create_context();
-                    task.exec(ctx).unwrap_or_else(|err| {
-                        eprintln!("error: {err}");
-                        task.set_err(&err.into());
-                        let _ = ctx.emit_error();
-                    });
+                    // the tasks with same pid are always dispatched to the same worker
+                    // to keep the order in one proc
+                    let worker = &self.workers[self.worker_index(&task.pid)];
+                    worker.push(Signal::Task(task));
                 }
                 Signal::Terminal => {
+                    for worker in self.workers.iter() {
+                        worker.push(Signal::Terminal);
+                    }
                     *self.closed.lock().unwrap() = true;
                     return false;
                 }
@@ -69,6 +102,16 @@ impl Scheduler {
         true
     }
 
+    /// the count of the scheduler workers
+    pub fn workers(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// the count of the tasks waiting to run
+    pub fn queue_depth(&self) -> usize {
+        self.queue.len() + self.workers.iter().map(|w| w.len()).sum::<usize>()
+    }
+
     pub fn close(&self) {
         debug!("sch::close");
         self.queue.terminate();
@@ -93,4 +136,27 @@ impl Scheduler {
     pub fn emit_task_event(&self, task: &Arc<Task>) -> Result<()> {
         self.emitter.emit_task_event(task)
     }
+
+    fn exec(task: &Arc<Task>) {
+        let ctx = &task.create_context();
+        ctx.proc.sync(|| {
+            task.proc().dec_queued();
+
+            // the task is cancelled before running, such as by the step join
+            if task.state() == TaskState::Cancelled {
+                return;
+            }
+            task.exec(ctx).unwrap_or_else(|err| {
+                eprintln!("error: {err}");
+                task.set_err(&err.into());
+                let _ = ctx.emit_error();
+            })
+        });
+    }
+
+    pub fn worker_index(&self, pid: &str) -> usize {
+        let mut hasher = DefaultHasher::new();
+        pid.hash(&mut hasher);
+        (hasher.finish() % self.workers.len() as u64) as usize
+    }
 }
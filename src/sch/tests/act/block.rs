@@ -1,6 +1,7 @@
 use crate::{
     sch::{tests::create_proc_signal, TaskState},
-    utils, Act, StmtBuild, Workflow,
+    utils::{self, consts},
+    Act, StmtBuild, Vars, Workflow,
 };
 
 #[tokio::test]
@@ -84,3 +85,39 @@ async fn sch_act_block_acts() {
         TaskState::Running
     );
 }
+
+#[tokio::test]
+async fn sch_act_block_skipped() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::block(|act| {
+            act.with_id("block1")
+                .with_acts(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    workflow.print();
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        println!("message: {:?}", e);
+        if e.is_key("act1") && e.is_state("created") {
+            e.do_action(&e.pid, &e.tid, consts::EVT_SKIP, &Vars::new())
+                .unwrap();
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    // the block is skipped with the skipped act
+    assert_eq!(
+        proc.task_by_nid("block1").first().unwrap().state(),
+        TaskState::Skipped
+    );
+    assert_eq!(
+        proc.task_by_nid("step1").first().unwrap().state(),
+        TaskState::Completed
+    );
+}
@@ -3,6 +3,7 @@ use crate::{
     utils::{self, consts},
     Act, StmtBuild, Vars, Workflow,
 };
+use serde_json::json;
 
 #[tokio::test]
 async fn sch_act_chain_list() {
@@ -114,3 +115,37 @@ async fn sch_act_chain_var_not_exist() {
     proc.print();
     assert!(proc.state().is_error());
 }
+
+#[tokio::test]
+async fn sch_act_chain_objects() {
+    let mut main = Workflow::new()
+        .with_input("users", json!([{ "uid": "u1" }, { "uid": "u2" }]))
+        .with_step(|step| {
+            step.with_id("step1").with_act(Act::chain(|act| {
+                act.with_in(r#"$("users")"#)
+                    .with_item("user")
+                    .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+            }))
+        });
+
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut main, &utils::longid());
+    let uids = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            let user = e.inputs.get::<Vars>("user").unwrap();
+            uids.lock()
+                .unwrap()
+                .push(user.get::<String>("uid").unwrap());
+            e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                .unwrap();
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.send(uids.lock().unwrap().clone());
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret, ["u1", "u2"]);
+}
@@ -0,0 +1,148 @@
+use crate::{
+    sch::{tests::create_proc_signal, TaskState},
+    utils::{self, consts},
+    Act, StmtBuild, Vars, Workflow,
+};
+
+#[tokio::test]
+async fn sch_act_delay_duration() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::delay(|act| act.with_id("delay1").with_duration("1s")))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    let start = utils::time::time_millis();
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    let delay1 = proc.task_by_nid("delay1").first().unwrap().clone();
+    assert_eq!(delay1.state(), TaskState::Completed);
+    assert!(utils::time::time_millis() - start >= 1000);
+    let due = delay1.data().get::<i64>(consts::DELAY_UNTIL).unwrap();
+    assert!(due >= start + 1000);
+}
+
+#[tokio::test]
+async fn sch_act_delay_until_expr() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_setup(|setup| setup.add(Act::set(Vars::new().with("delay_ms", 200))))
+            .with_act(Act::delay(|act| {
+                act.with_id("delay1")
+                    .with_until(r#"${ Date.now() + $("delay_ms") }"#)
+            }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    let start = utils::time::time_millis();
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    let delay1 = proc.task_by_nid("delay1").first().unwrap().clone();
+    assert_eq!(delay1.state(), TaskState::Completed);
+    let due = delay1.data().get::<i64>(consts::DELAY_UNTIL).unwrap();
+    assert!(due >= start + 200);
+    assert!(delay1.end_time() >= due);
+}
+
+#[tokio::test]
+async fn sch_act_delay_until_past_date() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::delay(|act| {
+            act.with_id("delay1").with_until("2024-01-01T00:00:00Z")
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    let delay1 = proc.task_by_nid("delay1").first().unwrap().clone();
+    assert_eq!(delay1.state(), TaskState::Completed);
+    assert_eq!(
+        delay1.data().get::<i64>(consts::DELAY_UNTIL).unwrap(),
+        1704067200000
+    );
+}
+
+#[tokio::test]
+async fn sch_act_delay_no_time_error() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::delay(|act| act.with_id("delay1")))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_error(move |_| {
+        rx.close();
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert!(proc.state().is_error());
+}
+
+#[tokio::test]
+async fn sch_act_delay_restore_timers() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::delay(|act| act.with_id("delay1").with_duration("1d")))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("delay1") && e.is_state("created") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    assert_eq!(scher.timer().len(), 1);
+
+    // re-arm the timers from the store like the engine is restarted
+    scher.timer().take(i64::MAX);
+    assert!(scher.timer().is_empty());
+    scher.restore_timers().unwrap();
+    assert_eq!(scher.timer().len(), 1);
+    assert!(scher.timer().next_due().unwrap() > utils::time::time_millis());
+}
+
+#[tokio::test]
+async fn sch_act_delay_calendar_not_found() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::delay(|act| {
+            act.with_id("delay1")
+                .with_duration("1h")
+                .with_calendar("no_exists")
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_error(move |_| {
+        rx.close();
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    assert!(proc.state().is_error());
+}
@@ -1,9 +1,10 @@
 use crate::{
     sch::{tests::create_proc_signal, TaskState},
     utils::{self, consts},
-    Act, StmtBuild, Vars, Workflow,
+    Act, EachMode, StmtBuild, Vars, Workflow,
 };
 use serde_json::json;
+use std::sync::{Arc, Mutex};
 
 #[tokio::test]
 async fn sch_step_setup_each_list() {
@@ -20,7 +21,8 @@ async fn sch_step_setup_each_list() {
     let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
     emitter.on_message(move |e| {
         println!("message: {:?}", e);
-        if e.is_source("act") {
+        // wait until the last item is created
+        if e.is_key("act1") && e.inputs.get::<String>(consts::ACT_VALUE).as_deref() == Some("u2") {
             rx.close();
         }
     });
@@ -68,7 +70,8 @@ async fn sch_step_setup_each_var() {
     let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
     emitter.on_message(move |e| {
         println!("message: {:?}", e);
-        if e.is_source("act") {
+        // wait until the last item is created
+        if e.is_key("act1") && e.inputs.get::<String>(consts::ACT_VALUE).as_deref() == Some("u2") {
             rx.close();
         }
     });
@@ -166,3 +169,461 @@ async fn sch_step_setup_each_code() {
         &json!("u3")
     );
 }
+
+#[tokio::test]
+async fn sch_act_each_until_any() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::each(|each| {
+            each.with_in(r#"["u1", "u2", "u3"]"#)
+                .with_until("completed >= 1")
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    let created = Arc::new(Mutex::new(Vec::new()));
+    emitter.on_message(move |e| {
+        // do the action after all of the items are created
+        if e.is_key("act1") && e.is_state("created") {
+            let mut created = created.lock().unwrap();
+            created.push(e.inner().clone());
+            if created.len() == 3 {
+                let m = created
+                    .iter()
+                    .find(|m| m.inputs.get::<String>(consts::ACT_VALUE).unwrap() == "u2")
+                    .unwrap();
+                e.do_action(&m.pid, &m.tid, consts::EVT_NEXT, &Vars::new())
+                    .unwrap();
+            }
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    let states = proc
+        .task_by_nid("act1")
+        .iter()
+        .map(|t| t.state())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        states,
+        [
+            TaskState::Cancelled,
+            TaskState::Completed,
+            TaskState::Cancelled
+        ]
+    );
+}
+
+#[tokio::test]
+async fn sch_act_each_until_rate() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::each(|each| {
+            each.with_in(r#"["u1", "u2", "u3", "u4", "u5"]"#)
+                .with_until("completed / total >= 0.6")
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                .unwrap();
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    let tasks = proc.task_by_nid("act1");
+    let completed = tasks.iter().filter(|t| t.state().is_success()).count();
+    assert_eq!(completed, 3);
+    assert!(proc
+        .task_by_nid("step1")
+        .first()
+        .unwrap()
+        .state()
+        .is_success());
+}
+
+#[tokio::test]
+async fn sch_act_each_until_reject() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::each(|each| {
+            each.with_in(r#"["u1", "u2", "u3"]"#)
+                .with_until("skipped > 0")
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    let created = Arc::new(Mutex::new(Vec::new()));
+    emitter.on_message(move |e| {
+        // do the action after all of the items are created
+        if e.is_key("act1") && e.is_state("created") {
+            let mut created = created.lock().unwrap();
+            created.push(e.inner().clone());
+            if created.len() == 3 {
+                let m = created
+                    .iter()
+                    .find(|m| m.inputs.get::<String>(consts::ACT_VALUE).unwrap() == "u1")
+                    .unwrap();
+                e.do_action(&m.pid, &m.tid, consts::EVT_SKIP, &Vars::new())
+                    .unwrap();
+            }
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    let states = proc
+        .task_by_nid("act1")
+        .iter()
+        .map(|t| t.state())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        states,
+        [
+            TaskState::Skipped,
+            TaskState::Cancelled,
+            TaskState::Cancelled
+        ]
+    );
+}
+
+#[tokio::test]
+async fn sch_act_each_until_errored() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::each(|each| {
+            each.with_in(r#"["u1", "u2", "u3"]"#)
+                .with_until("errored > 0")
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    let created = Arc::new(Mutex::new(Vec::new()));
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            let mut created = created.lock().unwrap();
+            created.push(e.inner().clone());
+            if created.len() == 3 {
+                let m = created
+                    .iter()
+                    .find(|m| m.inputs.get::<String>(consts::ACT_VALUE).unwrap() == "u2")
+                    .unwrap();
+                e.do_action(
+                    &m.pid,
+                    &m.tid,
+                    consts::EVT_ERR,
+                    &Vars::new().with(consts::ACT_ERR_KEY, json!({ "ecode": "err1"})),
+                )
+                .unwrap();
+            }
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    let states = proc
+        .task_by_nid("act1")
+        .iter()
+        .map(|t| t.state())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        states,
+        [TaskState::Cancelled, TaskState::Error, TaskState::Cancelled]
+    );
+    assert!(proc
+        .task_by_nid("step1")
+        .get(0)
+        .unwrap()
+        .state()
+        .is_success());
+}
+
+#[tokio::test]
+async fn sch_act_each_until_percent_with_errored() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::each(|each| {
+            each.with_in(r#"["u1", "u2", "u3", "u4", "u5"]"#)
+                .with_until("completed / total >= 0.6")
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            let value = e.inputs.get::<String>(consts::ACT_VALUE).unwrap();
+            if value == "u1" || value == "u2" {
+                e.do_action(
+                    &e.pid,
+                    &e.tid,
+                    consts::EVT_ERR,
+                    &Vars::new().with(consts::ACT_ERR_KEY, json!({ "ecode": "err1"})),
+                )
+                .unwrap();
+            } else {
+                e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                    .unwrap();
+            }
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    let states = proc
+        .task_by_nid("act1")
+        .iter()
+        .map(|t| t.state())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        states,
+        [
+            TaskState::Error,
+            TaskState::Error,
+            TaskState::Completed,
+            TaskState::Completed,
+            TaskState::Completed
+        ]
+    );
+    assert!(proc.state().is_success());
+}
+
+#[tokio::test]
+async fn sch_act_each_until_not_met_with_errored() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::each(|each| {
+            each.with_in(r#"["u1", "u2"]"#)
+                .with_until("completed == total")
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    let (proc, scher, emitter, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            if e.inputs.get::<String>(consts::ACT_VALUE).unwrap() == "u1" {
+                e.do_action(
+                    &e.pid,
+                    &e.tid,
+                    consts::EVT_ERR,
+                    &Vars::new().with(consts::ACT_ERR_KEY, json!({ "ecode": "err1"})),
+                )
+                .unwrap();
+            } else {
+                e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                    .unwrap();
+            }
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    assert!(proc.state().is_error());
+    assert_eq!(proc.err().unwrap().ecode, "err1");
+}
+
+#[tokio::test]
+async fn sch_act_each_sequential() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::each(|each| {
+            each.with_in(r#"["u1", "u2", "u3"]"#)
+                .with_mode(EachMode::Sequential)
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<usize>>(&mut workflow, &utils::longid());
+    let counts = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let p = proc.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            // only one instance is created at a time
+            counts.lock().unwrap().push(p.task_by_nid("act1").len());
+            e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                .unwrap();
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.send(counts.lock().unwrap().clone());
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn sch_act_each_sequential_until() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::each(|each| {
+            each.with_in(r#"["u1", "u2", "u3"]"#)
+                .with_mode(EachMode::Sequential)
+                .with_until("completed >= 2")
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                .unwrap();
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(proc.task_by_nid("act1").len(), 2);
+}
+
+#[tokio::test]
+async fn sch_act_each_sequential_in_once() {
+    let mut workflow = Workflow::new()
+        .with_input("users", json!(["u1", "u2", "u3"]))
+        .with_step(|step| {
+            step.with_id("step1").with_act(Act::each(|each| {
+                each.with_in(r#"$("users")"#)
+                    .with_mode(EachMode::Sequential)
+                    .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+            }))
+        });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            // changing the var does not change the items in running
+            e.do_action(
+                &e.pid,
+                &e.tid,
+                consts::EVT_NEXT,
+                &Vars::new().with("users", json!(["u4"])),
+            )
+            .unwrap();
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(proc.task_by_nid("act1").len(), 3);
+}
+
+#[tokio::test]
+async fn sch_act_each_objects() {
+    let mut workflow = Workflow::new()
+        .with_input(
+            "users",
+            json!([{ "uid": "u1", "role": "admin" }, { "uid": "u2", "role": "user" }]),
+        )
+        .with_step(|step| {
+            step.with_id("step1").with_act(Act::each(|each| {
+                each.with_in(r#"$("users")"#)
+                    .with_item("user")
+                    .with_index("i")
+                    .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+            }))
+        });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    let tasks = proc.task_by_nid("act1");
+    assert_eq!(tasks.len(), 2);
+    let task = tasks
+        .iter()
+        .find(|t| t.inputs().get_value("i") == Some(&json!(1)))
+        .unwrap();
+    assert_eq!(
+        task.inputs().get_value("user").unwrap(),
+        &json!({ "uid": "u2", "role": "user" })
+    );
+    assert!(task.inputs().get_value(consts::ACT_VALUE).is_none());
+}
+
+#[tokio::test]
+async fn sch_act_each_map() {
+    let mut workflow = Workflow::new()
+        .with_input("scores", json!({ "u1": 80, "u2": 90 }))
+        .with_step(|step| {
+            step.with_id("step1").with_act(Act::each(|each| {
+                each.with_in(r#"$("scores")"#).with_run(|stmts| {
+                    stmts.add(Act::req(|act| {
+                        act.with_id("act1")
+                            .with_input("score", r#"${ $("act_value") }"#)
+                    }))
+                })
+            }))
+        });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    let tasks = proc.task_by_nid("act1");
+    assert_eq!(tasks.len(), 2);
+    let task = tasks
+        .iter()
+        .find(|t| t.inputs().get_value(consts::ACT_INDEX) == Some(&json!("u1")))
+        .unwrap();
+    assert_eq!(
+        task.inputs().get_value(consts::ACT_VALUE).unwrap(),
+        &json!(80)
+    );
+}
+
+#[tokio::test]
+async fn sch_act_each_not_collection() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::each(|each| {
+            each.with_in("10")
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    let (proc, scher, _, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert!(proc.state().is_error());
+}
@@ -0,0 +1,251 @@
+use crate::{
+    sch::{tests::create_proc_signal, TaskState},
+    utils::{self, consts},
+    Act, StmtBuild, Vars, Workflow,
+};
+
+#[tokio::test]
+async fn sch_act_loop_until() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::r#loop(|act| {
+            act.with_id("loop1")
+                .with_until(r#"$("loop_index") >= 3"#)
+                .with_max(10)
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    workflow.print();
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                .unwrap();
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    let loop1 = proc.task_by_nid("loop1");
+    let loop1 = loop1.first().unwrap();
+    assert_eq!(loop1.state(), TaskState::Completed);
+    assert_eq!(loop1.children().len(), 3);
+    assert_eq!(proc.task_by_nid("act1").len(), 3);
+}
+
+#[tokio::test]
+async fn sch_act_loop_while() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::r#loop(|act| {
+            act.with_id("loop1")
+                .with_while(r#"$("loop_index") < 2"#)
+                .with_max(10)
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                .unwrap();
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(proc.task_by_nid("act1").len(), 2);
+}
+
+#[tokio::test]
+async fn sch_act_loop_while_false() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::r#loop(|act| {
+            act.with_id("loop1")
+                .with_while("false")
+                .with_max(10)
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(
+        proc.task_by_nid("loop1").first().unwrap().state(),
+        TaskState::Completed
+    );
+    assert_eq!(proc.task_by_nid("act1").len(), 0);
+}
+
+#[tokio::test]
+async fn sch_act_loop_max() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::r#loop(|act| {
+            act.with_id("loop1")
+                .with_while("true")
+                .with_max(4)
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                .unwrap();
+        }
+        if e.is_type("workflow") && e.is_state("error") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(proc.task_by_nid("act1").len(), 4);
+
+    let loop1 = proc.task_by_nid("loop1");
+    let loop1 = loop1.first().unwrap();
+    assert!(loop1.state().is_error());
+    assert_eq!(loop1.err().unwrap().ecode, consts::ERR_LOOP_MAX);
+}
+
+#[tokio::test]
+async fn sch_act_loop_max_reached_by_condition() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::r#loop(|act| {
+            act.with_id("loop1")
+                .with_until(r#"$("loop_index") >= 4"#)
+                .with_max(4)
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                .unwrap();
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(proc.task_by_nid("act1").len(), 4);
+    assert_eq!(
+        proc.task_by_nid("loop1").first().unwrap().state(),
+        TaskState::Completed
+    );
+}
+
+#[tokio::test]
+async fn sch_act_loop_max_without_conditions() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::r#loop(|act| {
+            act.with_id("loop1")
+                .with_max(3)
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                .unwrap();
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(proc.task_by_nid("act1").len(), 3);
+    assert_eq!(
+        proc.task_by_nid("loop1").first().unwrap().state(),
+        TaskState::Completed
+    );
+}
+
+#[tokio::test]
+async fn sch_act_loop_max_zero() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::r#loop(|act| {
+            act.with_id("loop1")
+                .with_max(0)
+                .with_run(|stmts| stmts.add(Act::req(|act| act.with_id("act1"))))
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_state("error") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert!(proc
+        .task_by_nid("loop1")
+        .first()
+        .unwrap()
+        .state()
+        .is_error());
+    assert_eq!(proc.task_by_nid("act1").len(), 0);
+}
+
+#[tokio::test]
+async fn sch_act_loop_index_name() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::r#loop(|act| {
+            act.with_id("loop1")
+                .with_index("i")
+                .with_until(r#"$("i") >= 2"#)
+                .with_max(10)
+                .with_run(|stmts| {
+                    stmts.add(Act::req(|act| {
+                        act.with_id("act1").with_input("i", r#"${ $("i") }"#)
+                    }))
+                })
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<i64>>(&mut workflow, &utils::longid());
+    let indexes = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            indexes
+                .lock()
+                .unwrap()
+                .push(e.inputs.get::<i64>("i").unwrap());
+            e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                .unwrap();
+            if indexes.lock().unwrap().len() == 2 {
+                rx.send(indexes.lock().unwrap().clone());
+            }
+        }
+    });
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret, vec![0, 1]);
+}
@@ -2,10 +2,13 @@ mod block;
 mod call;
 mod chain;
 mod cmd;
+mod delay;
 mod each;
 mod expose;
 mod r#if;
+mod r#loop;
 mod msg;
 mod pack;
 mod req;
 mod set;
+mod wait;
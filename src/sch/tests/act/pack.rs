@@ -2,7 +2,7 @@ use crate::{
     data,
     sch::{tests::create_proc_signal2, Proc},
     utils::{self, consts},
-    Act, Event, Message, Signal, StmtBuild, TaskState, Workflow,
+    Act, Event, Limits, Message, Signal, StmtBuild, TaskState, Workflow,
 };
 use serde_json::json;
 use std::sync::Arc;
@@ -131,6 +131,31 @@ async fn sch_act_pack_each_var() {
     assert!(ret);
 }
 
+#[tokio::test]
+async fn sch_act_pack_each_objects() {
+    let workflow = Workflow::new()
+        .with_input("users", json!([{ "uid": "u1" }]))
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_act(Act::pack(|p| p.with_uses("pack1")))
+        });
+    let pack = data::Package {
+        id: "pack1".to_string(),
+        name: "package 1".to_string(),
+        file_data:
+            br#"act.each({ in: $("users"), item: "user", run: [ { msg: { key: "msg1" } } ] })"#
+                .to_vec(),
+        ..Default::default()
+    };
+    let ret = run_test(&workflow, &pack, |e, s| {
+        if e.is_key("msg1") {
+            s.send(e.inputs.get_value("user").unwrap() == &json!({ "uid": "u1" }));
+        }
+    })
+    .await;
+    assert!(ret);
+}
+
 #[tokio::test]
 async fn sch_act_pack_block() {
     let workflow = Workflow::new().with_step(|step| {
@@ -439,6 +464,32 @@ async fn sch_act_pack_catch_error() {
     assert!(ret);
 }
 
+#[tokio::test]
+async fn sch_act_pack_timeout_limit() {
+    let workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_catch(|c| {
+                c.with_err(consts::ERR_SCRIPT_TIMEOUT)
+                    .with_then(|stmts| stmts.add(Act::msg(|act| act.with_id("msg1"))))
+            })
+            .with_act(Act::pack(|p| p.with_uses("pack1")))
+    });
+    let pack = data::Package {
+        id: "pack1".to_string(),
+        name: "package 1".to_string(),
+        file_data: br#"while(true) {}"#.to_vec(),
+        limits: Some(Limits::new().with_timeout("100ms")),
+        ..Default::default()
+    };
+    let ret: bool = run_test(&workflow, &pack, |e, s| {
+        if e.is_key("msg1") {
+            s.send(true);
+        }
+    })
+    .await;
+    assert!(ret);
+}
+
 async fn run_test<T: Clone + Send + 'static + Default>(
     workflow: &Workflow,
     package: &data::Package,
@@ -2,7 +2,7 @@ use crate::{
     event::{Action, MessageState},
     sch::{tests::*, TaskState},
     utils::{self, consts},
-    Act, Message, StmtBuild, Vars, Workflow,
+    Act, Message, StmtBuild, TimeoutMode, Vars, Workflow,
 };
 use serde_json::{json, Value};
 use std::sync::Arc;
@@ -86,7 +86,7 @@ async fn sch_act_req_many() {
     let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
     emitter.on_message(move |e| {
         println!("message: {:?}", e);
-        if e.is_source("act") {
+        if e.is_key("act3") {
             rx.close();
         }
     });
@@ -1505,3 +1505,31 @@ async fn sch_act_req_on_timeout() {
         TaskState::Interrupt
     );
 }
+
+#[tokio::test]
+async fn sch_act_req_on_timeout_interrupting() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::req(|act| {
+            act.with_id("act1").with_timeout(|c| {
+                c.with_on("100ms")
+                    .with_mode(TimeoutMode::Interrupting)
+                    .with_then(|stmts| stmts.add(Act::msg(|msg| msg.with_id("msg1"))))
+            })
+        }))
+    });
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(
+        proc.task_by_nid("act1").first().unwrap().state(),
+        TaskState::Completed
+    );
+}
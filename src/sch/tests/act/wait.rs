@@ -0,0 +1,112 @@
+use crate::{
+    sch::{tests::create_proc_signal, TaskState},
+    utils::{self, consts},
+    Act, Vars, Workflow,
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn sch_act_wait_signal() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_input("order_id", json!("o1"))
+            .with_input("amount", json!(null))
+            .with_act(Act::wait(|act| {
+                act.with_id("wait1")
+                    .with_signal("order_paid")
+                    .with_key(r#"order:${ $("order_id") }"#)
+            }))
+    });
+
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<usize>(&mut workflow, &utils::longid());
+    let rt = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("wait1") && e.is_state("created") {
+            let count = rt
+                .signal("order_paid", "order:o1", &Vars::new().with("amount", 10))
+                .unwrap();
+            rx.update(|data| *data = count);
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    let count = tx.recv().await;
+    proc.print();
+
+    assert_eq!(count, 1);
+    let wait1 = proc.task_by_nid("wait1").first().unwrap().clone();
+    assert_eq!(wait1.state(), TaskState::Completed);
+    assert_eq!(
+        wait1.data().get::<String>(consts::WAIT_KEY).unwrap(),
+        "order:o1"
+    );
+    let step1 = proc.task_by_nid("step1").first().unwrap().clone();
+    assert_eq!(step1.data().get::<i64>("amount").unwrap(), 10);
+}
+
+#[tokio::test]
+async fn sch_act_wait_key_not_matched() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_input("order_id", json!("o1"))
+            .with_act(Act::wait(|act| {
+                act.with_id("wait1")
+                    .with_signal("order_paid")
+                    .with_key(r#"order:${ $("order_id") }"#)
+            }))
+    });
+
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<usize>(&mut workflow, &utils::longid());
+    let rt = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("wait1") && e.is_state("created") {
+            let mut count = rt.signal("order_paid", "order:o2", &Vars::new()).unwrap();
+            count += rt
+                .signal("order_canceled", "order:o1", &Vars::new())
+                .unwrap();
+            rx.send(count);
+        }
+    });
+    scher.launch(&proc);
+    let count = tx.recv().await;
+    proc.print();
+
+    assert_eq!(count, 0);
+    assert_eq!(
+        proc.task_by_nid("wait1").first().unwrap().state(),
+        TaskState::Interrupt
+    );
+}
+
+#[tokio::test]
+async fn sch_act_wait_in_step_acts() {
+    let mut workflow = Workflow::new()
+        .with_step(|step| {
+            step.with_id("step1").with_act(Act::wait(|act| {
+                act.with_id("wait1").with_signal("approved").with_key("k1")
+            }))
+        })
+        .with_step(|step| step.with_id("step2"));
+
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    let rt = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("wait1") && e.is_state("created") {
+            rt.signal("approved", "k1", &Vars::new()).unwrap();
+        }
+        if e.is_key("step2") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(
+        proc.task_by_nid("step2").first().unwrap().state(),
+        TaskState::Completed
+    );
+}
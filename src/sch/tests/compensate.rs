@@ -0,0 +1,257 @@
+use crate::{
+    sch::{
+        tests::{create_proc_signal, create_proc_signal2},
+        TaskState,
+    },
+    utils::{self, consts},
+    Act, Action, MessageState, StmtBuild, Vars, Workflow,
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn sch_compensate_on_abort() {
+    let mut workflow = Workflow::new()
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_act(Act::req(|act| {
+                    act.with_id("act1").with_compensate(|stmts| {
+                        stmts.add(Act::msg(|msg| {
+                            msg.with_id("undo1")
+                                .with_input("order", r#"${ $("order") }"#)
+                        }))
+                    })
+                }))
+                .with_act(Act::req(|act| {
+                    act.with_id("act2").with_compensate(|stmts| {
+                        stmts.add(Act::msg(|msg| {
+                            msg.with_id("undo2")
+                                .with_input("order", r#"${ $("order") }"#)
+                        }))
+                    })
+                }))
+        })
+        .with_step(|step| {
+            step.with_id("step2")
+                .with_act(Act::req(|act| act.with_id("act3")))
+        });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            let options = Vars::new().with("order", "o1");
+            let action = Action::new(&e.pid, &e.tid, consts::EVT_NEXT, &options);
+            s.do_action(&action).unwrap();
+        }
+        if e.is_key("act2") && e.is_state("created") {
+            // complete the act2 later than act1
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            let options = Vars::new().with("order", "o2");
+            let action = Action::new(&e.pid, &e.tid, consts::EVT_NEXT, &options);
+            s.do_action(&action).unwrap();
+        }
+        if e.is_key("act3") && e.is_state("created") {
+            let action = Action::new(&e.pid, &e.tid, consts::EVT_ABORT, &Vars::new());
+            s.do_action(&action).unwrap();
+        }
+        if e.is_type("msg") {
+            let order = e.inputs.get::<String>("order").unwrap_or_default();
+            rx.update(|data| data.push(format!("{}:{order}", e.key)));
+        }
+    });
+
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret, ["undo2:o2", "undo1:o1"]);
+    assert_eq!(proc.state(), TaskState::Aborted);
+
+    let act1 = proc.task_by_nid("act1").first().unwrap().clone();
+    assert_eq!(
+        act1.data().get::<String>(consts::COMPENSATE_STATE).unwrap(),
+        "completed"
+    );
+    let root = proc.root().unwrap();
+    assert_eq!(
+        root.data().get::<String>(consts::COMPENSATE_STATE).unwrap(),
+        "completed"
+    );
+}
+
+#[tokio::test]
+async fn sch_compensate_on_error() {
+    let mut workflow = Workflow::new()
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_act(Act::req(|act| {
+                    act.with_id("act1")
+                        .with_compensate(|stmts| stmts.add(Act::req(|act| act.with_id("undo1"))))
+                }))
+                .with_compensate(|stmts| stmts.add(Act::msg(|msg| msg.with_id("undo_step1"))))
+        })
+        .with_step(|step| {
+            step.with_id("step2")
+                .with_act(Act::req(|act| act.with_id("act2")))
+        });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_type("msg") || (e.is_type("req") && e.is_state("created")) {
+            rx.update(|data| data.push(e.key.clone()));
+        }
+        if (e.is_key("act1") || e.is_key("undo1")) && e.is_state("created") {
+            let action = Action::new(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new());
+            s.do_action(&action).unwrap();
+        }
+        if e.is_key("act2") && e.is_state("created") {
+            let options = Vars::new().with(consts::ACT_ERR_KEY, json!({ "ecode": "err1"}));
+            let action = Action::new(&e.pid, &e.tid, consts::EVT_ERR, &options);
+            s.do_action(&action).unwrap();
+        }
+    });
+
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+
+    // the step completes after its act, so it is compensated first
+    assert_eq!(ret, ["act1", "act2", "undo_step1", "undo1"]);
+    assert_eq!(proc.state(), TaskState::Error);
+    assert_eq!(proc.err().unwrap().ecode, "err1");
+}
+
+#[tokio::test]
+async fn sch_compensate_not_run_when_caught() {
+    let mut workflow = Workflow::new()
+        .with_step(|step| {
+            step.with_id("step1").with_act(Act::req(|act| {
+                act.with_id("act1")
+                    .with_compensate(|stmts| stmts.add(Act::msg(|msg| msg.with_id("undo1"))))
+            }))
+        })
+        .with_step(|step| {
+            step.with_id("step2")
+                .with_catch(|c| {
+                    c.with_then(|stmts| stmts.add(Act::msg(|msg| msg.with_id("catch1"))))
+                })
+                .with_act(Act::req(|act| act.with_id("act2")))
+        });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_type("msg") {
+            rx.update(|data| data.push(e.key.clone()));
+        }
+        if e.is_key("act1") && e.is_state("created") {
+            let action = Action::new(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new());
+            s.do_action(&action).unwrap();
+        }
+        if e.is_key("act2") && e.is_state("created") {
+            let options = Vars::new().with(consts::ACT_ERR_KEY, json!({ "ecode": "err1"}));
+            let action = Action::new(&e.pid, &e.tid, consts::EVT_ERR, &options);
+            s.do_action(&action).unwrap();
+        }
+    });
+
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret, ["catch1"]);
+    assert_eq!(proc.state(), TaskState::Completed);
+}
+
+#[tokio::test]
+async fn sch_compensate_state_in_messages_and_manager() {
+    let workflow = Workflow::new()
+        .with_step(|step| {
+            step.with_id("step1").with_act(Act::req(|act| {
+                act.with_id("act1")
+                    .with_compensate(|stmts| stmts.add(Act::req(|act| act.with_id("undo1"))))
+            }))
+        })
+        .with_step(|step| {
+            step.with_id("step2")
+                .with_act(Act::req(|act| act.with_id("act2")))
+        });
+    let (engine, proc, tx, rx) = create_proc_signal2::<Vec<String>>(&workflow, &utils::longid());
+    let executor = engine.executor().clone();
+    let manager = engine.manager().clone();
+    engine.channel().on_message(move |e| {
+        if e.is_key("act1") {
+            if let Some(state) = e.inputs.get::<String>(consts::COMPENSATE_STATE) {
+                rx.update(|data| data.push(state.clone()));
+            }
+        }
+        if e.is_key("act1") && e.is_state("created") {
+            executor.complete(&e.pid, &e.tid, &Vars::new()).unwrap();
+        }
+        if e.is_key("act2") && e.is_state("created") {
+            executor.abort(&e.pid, &e.tid, &Vars::new()).unwrap();
+        }
+        if e.is_key("undo1") && e.is_state("created") {
+            // the compensation is visible in the proc tasks
+            let info = manager.proc(&e.pid, "json").unwrap();
+            assert!(info.tasks.contains(consts::COMPENSATE_STATE));
+            executor.complete(&e.pid, &e.tid, &Vars::new()).unwrap();
+        }
+        if e.is_source("workflow") && e.state() == MessageState::Aborted {
+            assert_eq!(
+                e.inputs.get::<String>(consts::COMPENSATE_STATE).unwrap(),
+                "completed"
+            );
+        }
+    });
+    engine.runtime().launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret, ["running", "completed"]);
+    assert_eq!(proc.state(), TaskState::Aborted);
+}
+
+#[tokio::test]
+async fn sch_compensate_failed() {
+    let mut workflow = Workflow::new()
+        .with_step(|step| {
+            step.with_id("step1").with_act(Act::req(|act| {
+                act.with_id("act1")
+                    .with_compensate(|stmts| stmts.add(Act::req(|act| act.with_id("undo1"))))
+            }))
+        })
+        .with_step(|step| {
+            step.with_id("step2")
+                .with_act(Act::req(|act| act.with_id("act2")))
+        });
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            let action = Action::new(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new());
+            s.do_action(&action).unwrap();
+        }
+        if e.is_key("act2") && e.is_state("created") {
+            let action = Action::new(&e.pid, &e.tid, consts::EVT_ABORT, &Vars::new());
+            s.do_action(&action).unwrap();
+        }
+        if e.is_key("undo1") && e.is_state("created") {
+            let options = Vars::new().with(consts::ACT_ERR_KEY, json!({ "ecode": "undo_err"}));
+            let action = Action::new(&e.pid, &e.tid, consts::EVT_ERR, &options);
+            s.do_action(&action).unwrap();
+        }
+        if e.is_key("undo1") && e.is_state("error") {
+            rx.close();
+        }
+    });
+
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(proc.state(), TaskState::Error);
+    assert_eq!(proc.err().unwrap().ecode, "undo_err");
+    let act1 = proc.task_by_nid("act1").first().unwrap().clone();
+    assert_eq!(
+        act1.data().get::<String>(consts::COMPENSATE_STATE).unwrap(),
+        "error"
+    );
+}
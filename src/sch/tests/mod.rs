@@ -1,10 +1,13 @@
 mod act;
+mod compensate;
 mod message;
 mod proc;
+mod retry;
 mod scher;
 mod state;
 mod step;
 mod task;
+mod timer;
 mod tree;
 mod vars;
 mod workflow;
@@ -0,0 +1,211 @@
+use crate::{
+    data,
+    sch::{
+        tests::{create_proc_signal, create_proc_signal2},
+        TaskState,
+    },
+    utils::{self, consts},
+    Act, Action, StmtBuild, Vars, Workflow,
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn sch_retry_req_until_exhausted() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::req(|act| {
+            act.with_id("act1")
+                .with_retry(|r| r.with_times(2).with_backoff("100ms"))
+        }))
+    });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<u32>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            let attempt = e
+                .inputs
+                .get::<u32>(consts::RETRY_ATTEMPT)
+                .unwrap_or_default();
+            rx.update(|data| data.push(attempt));
+
+            let options = Vars::new().with(consts::ACT_ERR_KEY, json!({ "ecode": "err1"}));
+            let action = Action::new(&e.pid, &e.tid, consts::EVT_ERR, &options);
+            s.do_action(&action).unwrap();
+        }
+    });
+
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret, [0, 1, 2]);
+    assert_eq!(proc.state(), TaskState::Error);
+
+    // the task is run again instead of creating a new one
+    let tasks = proc.task_by_nid("act1");
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].data().get::<u32>(consts::RETRY_ATTEMPT), Some(2));
+}
+
+#[tokio::test]
+async fn sch_retry_req_then_complete() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::req(|act| {
+            act.with_id("act1").with_retry(|r| r.with_times(3))
+        }))
+    });
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            let action = match e.inputs.get::<u32>(consts::RETRY_ATTEMPT) {
+                Some(_) => Action::new(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new()),
+                None => Action::new(
+                    &e.pid,
+                    &e.tid,
+                    consts::EVT_ERR,
+                    &Vars::new().with(consts::ACT_ERR_KEY, json!({ "ecode": "err1"})),
+                ),
+            };
+            s.do_action(&action).unwrap();
+        }
+        if e.is_key("step1") && e.is_state("completed") {
+            rx.close();
+        }
+    });
+
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    let act1 = proc.task_by_nid("act1").first().unwrap().clone();
+    assert_eq!(act1.state(), TaskState::Completed);
+    assert_eq!(act1.data().get::<u32>(consts::RETRY_ATTEMPT), Some(1));
+}
+
+#[tokio::test]
+async fn sch_retry_not_matched_ecode() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_catch(|c| c.with_then(|stmts| stmts.add(Act::msg(|msg| msg.with_id("catch1")))))
+            .with_act(Act::req(|act| {
+                act.with_id("act1")
+                    .with_retry(|r| r.with_times(3).with_on("timeout"))
+            }))
+    });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<u32>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            rx.update(|data| data.push(0));
+            let options = Vars::new().with(consts::ACT_ERR_KEY, json!({ "ecode": "err1"}));
+            let action = Action::new(&e.pid, &e.tid, consts::EVT_ERR, &options);
+            s.do_action(&action).unwrap();
+        }
+        if e.is_key("catch1") {
+            rx.close();
+        }
+    });
+
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert_eq!(ret.len(), 1);
+}
+
+#[tokio::test]
+async fn sch_retry_pack_then_catch() {
+    let workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_catch(|c| c.with_then(|stmts| stmts.add(Act::msg(|msg| msg.with_id("catch1")))))
+            .with_act(Act::pack(|p| {
+                p.with_id("pack1")
+                    .with_uses("pack1")
+                    .with_retry(|r| r.with_times(2))
+            }))
+    });
+    let pack = data::Package {
+        id: "pack1".to_string(),
+        name: "package 1".to_string(),
+        file_data: br#"throw new Error("failed")"#.to_vec(),
+        ..Default::default()
+    };
+    let (engine, proc, tx, rx) = create_proc_signal2::<()>(&workflow, &utils::longid());
+    engine.manager().publish(&pack).unwrap();
+    engine.channel().on_message(move |e| {
+        if e.is_key("catch1") {
+            rx.close();
+        }
+    });
+    engine.runtime().launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    let pack1 = proc.task_by_nid("pack1").first().unwrap().clone();
+    assert_eq!(pack1.state(), TaskState::Error);
+    assert_eq!(pack1.data().get::<u32>(consts::RETRY_ATTEMPT), Some(2));
+}
+
+#[tokio::test]
+async fn sch_retry_step() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_retry(|r| r.with_times(1))
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<String>>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            rx.update(|data| data.push(e.tid.clone()));
+            let options = Vars::new().with(consts::ACT_ERR_KEY, json!({ "ecode": "err1"}));
+            let action = Action::new(&e.pid, &e.tid, consts::EVT_ERR, &options);
+            s.do_action(&action).unwrap();
+        }
+    });
+
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+
+    // the step re-runs and creates the act again
+    assert_eq!(ret.len(), 2);
+    assert_ne!(ret[0], ret[1]);
+    let step1 = proc.task_by_nid("step1").first().unwrap().clone();
+    assert_eq!(step1.data().get::<u32>(consts::RETRY_ATTEMPT), Some(1));
+    assert_eq!(proc.state(), TaskState::Error);
+}
+
+#[tokio::test]
+async fn sch_retry_restore_timers() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1").with_act(Act::req(|act| {
+            act.with_id("act1")
+                .with_retry(|r| r.with_times(1).with_backoff("1h"))
+        }))
+    });
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    let s = scher.clone();
+    emitter.on_message(move |e| {
+        if e.is_key("act1") && e.is_state("created") {
+            let options = Vars::new().with(consts::ACT_ERR_KEY, json!({ "ecode": "err1"}));
+            let action = Action::new(&e.pid, &e.tid, consts::EVT_ERR, &options);
+            s.do_action(&action).unwrap();
+            rx.close();
+        }
+    });
+
+    scher.launch(&proc);
+    tx.recv().await;
+    let act1 = proc.task_by_nid("act1").first().unwrap().clone();
+    assert_eq!(act1.state(), TaskState::Interrupt);
+    let due = act1.data().get::<i64>(consts::RETRY_AT).unwrap();
+    assert!(due > utils::time::time_millis() + 3500 * 1000);
+
+    // re-arm the timers from the store like the engine is restarted
+    scher.timer().take(i64::MAX);
+    assert!(scher.timer().is_empty());
+    scher.restore_timers().unwrap();
+    assert_eq!(scher.timer().len(), 1);
+    assert_eq!(scher.timer().next_due(), Some(due));
+}
@@ -1,8 +1,10 @@
 use crate::{
+    sch::queue::{Backlog, Signal},
     utils::{self, consts},
-    Act, Action, Engine, TaskState, Vars, Workflow,
+    Act, Action, Builder, Engine, TaskState, Vars, Workflow,
 };
 use serde_json::json;
+use std::sync::{Arc, Mutex};
 
 #[tokio::test]
 async fn sch_scher_next() {
@@ -93,3 +95,111 @@ async fn sch_scher_do_action() {
 
     assert_eq!(proc.state().is_success(), true);
 }
+
+#[tokio::test]
+async fn sch_scher_workers() {
+    let engine = Builder::new().worker_count(4).build();
+    let rt = engine.runtime();
+    assert_eq!(rt.scher().workers(), 4);
+}
+
+#[tokio::test]
+async fn sch_scher_workers_at_least_one() {
+    let engine = Builder::new().worker_count(0).build();
+    let rt = engine.runtime();
+    assert_eq!(rt.scher().workers(), 1);
+}
+
+#[tokio::test]
+async fn sch_scher_worker_index_same_pid() {
+    let engine = Builder::new().worker_count(8).build();
+    let rt = engine.runtime();
+    let pid = utils::longid();
+    let index = rt.scher().worker_index(&pid);
+    assert!(index < 8);
+    assert_eq!(rt.scher().worker_index(&pid), index);
+}
+
+#[tokio::test]
+async fn sch_scher_queue_depth() {
+    let engine = Engine::new();
+    let rt = engine.runtime();
+    assert_eq!(rt.scher().queue_depth(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn sch_scher_procs_run_concurrently() {
+    let engine = Builder::new().worker_count(2).build();
+    let rt = engine.runtime();
+
+    // make sure the two procs are dispatched to different workers
+    let slow_pid = utils::longid();
+    let mut fast_pid = utils::longid();
+    while rt.scher().worker_index(&fast_pid) == rt.scher().worker_index(&slow_pid) {
+        fast_pid = utils::longid();
+    }
+
+    let slow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_run(r#"let t = Date.now(); while (Date.now() - t < 1000) {}"#)
+    });
+    let fast = Workflow::new().with_step(|step| step.with_id("step1"));
+
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    let completed = Arc::new(Mutex::new(Vec::new()));
+    let list = completed.clone();
+    engine.channel().on_complete(move |e| {
+        let mut list = list.lock().unwrap();
+        list.push(e.pid.clone());
+        if list.len() == 2 {
+            s1.close();
+        }
+    });
+
+    let mut vars = Vars::new();
+    vars.insert("pid".to_string(), json!(slow_pid));
+    rt.start(&slow, &vars).unwrap();
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let mut vars = Vars::new();
+    vars.insert("pid".to_string(), json!(fast_pid));
+    rt.start(&fast, &vars).unwrap();
+    sig.recv().await;
+
+    let completed = completed.lock().unwrap();
+    assert_eq!(*completed, vec![fast_pid, slow_pid]);
+}
+
+#[tokio::test]
+async fn sch_scher_busy_step_not_block_runtime() {
+    let engine = Builder::new().worker_count(1).build();
+    let rt = engine.runtime();
+
+    let workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_run(r#"let t = Date.now(); while (Date.now() - t < 1000) {}"#)
+    });
+    let sig = engine.signal(());
+    let s1 = sig.clone();
+    engine.channel().on_complete(move |_| s1.close());
+    rt.start(&workflow, &Vars::new()).unwrap();
+
+    // the timer fires on the current thread runtime while the step is running
+    let start = std::time::Instant::now();
+    tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+    assert!(start.elapsed().as_millis() < 800);
+    sig.recv().await;
+}
+
+#[tokio::test]
+async fn sch_scher_backlog_not_block() {
+    let backlog = Backlog::new();
+    for _ in 0..500 {
+        backlog.push(Signal::Terminal);
+    }
+    assert_eq!(backlog.len(), 500);
+
+    backlog.next().await;
+    assert_eq!(backlog.len(), 499);
+}
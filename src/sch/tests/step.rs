@@ -1,6 +1,7 @@
 mod acts;
 mod catch;
 mod hooks;
+mod join;
 mod run;
 mod setup;
 mod timeout;
@@ -159,7 +159,8 @@ async fn sch_step_acts_each() {
     let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
     emitter.on_message(move |e| {
         println!("message: {:?}", e);
-        if e.is_source("act") {
+        // wait until the last item is created
+        if e.is_key("act1") && e.inputs.get::<String>(consts::ACT_VALUE).as_deref() == Some("u2") {
             rx.close();
         }
     });
@@ -22,7 +22,7 @@ async fn sch_step_hooks_created() {
     let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
     emitter.on_message(move |e| {
         println!("message: {:?}", e);
-        if e.is_source("act") {
+        if e.is_key("act2") {
             rx.close();
         }
     });
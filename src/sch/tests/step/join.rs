@@ -0,0 +1,170 @@
+use crate::{
+    sch::{tests::create_proc_signal, TaskState},
+    utils::{self, consts},
+    Act, Join, Vars, Workflow,
+};
+
+fn create_workflow(join: Join) -> Workflow {
+    let mut workflow = Workflow::new()
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_branch(|b| {
+                    b.with_id("b1").with_step(|step| {
+                        step.with_id("step11")
+                            .with_act(Act::req(|act| act.with_id("act1")))
+                    })
+                })
+                .with_branch(|b| {
+                    b.with_id("b2").with_step(|step| {
+                        step.with_id("step21")
+                            .with_act(Act::req(|act| act.with_id("act2")))
+                    })
+                })
+                .with_branch(|b| {
+                    b.with_id("b3").with_step(|step| {
+                        step.with_id("step31")
+                            .with_act(Act::req(|act| act.with_id("act3")))
+                    })
+                })
+        })
+        .with_step(|step| step.with_id("step2"));
+    workflow.steps[0].join = Some(join);
+    workflow
+}
+
+#[tokio::test]
+async fn sch_step_join_all() {
+    let mut workflow = create_workflow(Join::All);
+    let (proc, scher, emitter, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_type("req") && e.is_state("created") {
+            e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                .unwrap();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    for nid in ["b1", "b2", "b3", "step2"] {
+        assert_eq!(
+            proc.task_by_nid(nid).first().unwrap().state(),
+            TaskState::Completed
+        );
+    }
+}
+
+#[tokio::test]
+async fn sch_step_join_any() {
+    let mut workflow = create_workflow(Join::Any);
+    let (proc, scher, emitter, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act2") && e.is_state("created") {
+            e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                .unwrap();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    assert_eq!(
+        proc.task_by_nid("b2").first().unwrap().state(),
+        TaskState::Completed
+    );
+    for nid in ["b1", "step11", "act1", "b3", "step31", "act3"] {
+        assert_eq!(
+            proc.task_by_nid(nid).first().unwrap().state(),
+            TaskState::Cancelled
+        );
+    }
+    assert_eq!(
+        proc.task_by_nid("step2").first().unwrap().state(),
+        TaskState::Completed
+    );
+}
+
+#[tokio::test]
+async fn sch_step_join_count() {
+    let mut workflow = create_workflow(Join::Count(2));
+    let (proc, scher, emitter, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if (e.is_key("act1") || e.is_key("act3")) && e.is_state("created") {
+            e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                .unwrap();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    assert_eq!(
+        proc.task_by_nid("b1").first().unwrap().state(),
+        TaskState::Completed
+    );
+    assert_eq!(
+        proc.task_by_nid("b2").first().unwrap().state(),
+        TaskState::Cancelled
+    );
+    assert_eq!(
+        proc.task_by_nid("b3").first().unwrap().state(),
+        TaskState::Completed
+    );
+    assert_eq!(
+        proc.task_by_nid("step2").first().unwrap().state(),
+        TaskState::Completed
+    );
+}
+
+#[tokio::test]
+async fn sch_step_join_expr() {
+    let mut workflow = create_workflow(Join::Expr("joined == total - 2".to_string()));
+    let (proc, scher, emitter, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act3") && e.is_state("created") {
+            e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                .unwrap();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    assert_eq!(
+        proc.task_by_nid("b1").first().unwrap().state(),
+        TaskState::Cancelled
+    );
+    assert_eq!(
+        proc.task_by_nid("b3").first().unwrap().state(),
+        TaskState::Completed
+    );
+    assert_eq!(
+        proc.task_by_nid("step2").first().unwrap().state(),
+        TaskState::Completed
+    );
+}
+
+#[tokio::test]
+async fn sch_step_join_with_if() {
+    let mut workflow = create_workflow(Join::All);
+    workflow.steps[0].branches[1].r#if = Some("false".to_string());
+    let (proc, scher, emitter, tx, _) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_type("req") && e.is_state("created") {
+            e.do_action(&e.pid, &e.tid, consts::EVT_NEXT, &Vars::new())
+                .unwrap();
+        }
+    });
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+
+    assert_eq!(
+        proc.task_by_nid("b2").first().unwrap().state(),
+        TaskState::Skipped
+    );
+    assert_eq!(
+        proc.task_by_nid("step2").first().unwrap().state(),
+        TaskState::Completed
+    );
+}
@@ -284,6 +284,26 @@ async fn sch_step_run_catch_error() {
     assert!(ret);
 }
 
+#[tokio::test]
+async fn sch_step_run_timeout_limit() {
+    let workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_limits(|l| l.with_timeout("100ms"))
+            .with_run(r#"while(true) {}"#)
+            .with_catch(|c| {
+                c.with_err(consts::ERR_SCRIPT_TIMEOUT)
+                    .with_then(|stmts| stmts.add(Act::msg(|act| act.with_id("msg1"))))
+            })
+    });
+    let ret = run_test(&workflow, |e, s| {
+        if e.is_key("msg1") {
+            s.send(true);
+        }
+    })
+    .await;
+    assert!(ret);
+}
+
 async fn run_test<T: Clone + Send + 'static + Default>(
     workflow: &Workflow,
     exit_if: fn(&Event<Message>, sig: Signal<T>),
@@ -1,4 +1,8 @@
-use crate::{sch::tests::create_proc_signal, utils, Act, Message, StmtBuild, Workflow};
+use crate::{
+    sch::{tests::create_proc_signal, TaskState},
+    utils::{self, consts},
+    Act, Message, StmtBuild, TimeoutMode, Vars, Workflow,
+};
 
 #[tokio::test]
 async fn sch_step_timeout_one() {
@@ -58,3 +62,101 @@ async fn sch_step_timeout_many() {
     proc.print();
     assert_eq!(ret.len(), 2)
 }
+
+#[tokio::test]
+async fn sch_step_timeout_until_expr() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_setup(|setup| {
+                setup.add(Act::set(Vars::new().with("due", "2024-01-01T00:00:00Z")))
+            })
+            .with_timeout(|t| {
+                t.with_until(r#"${ $("due") }"#)
+                    .with_then(|stmts| stmts.add(Act::msg(|msg| msg.with_id("msg1"))))
+            })
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<bool>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("msg1") {
+            rx.send(true);
+        }
+    });
+
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    proc.print();
+    assert!(ret)
+}
+
+#[tokio::test]
+async fn sch_step_timeout_every_max() {
+    let mut workflow = Workflow::new().with_step(|step| {
+        step.with_id("step1")
+            .with_timeout(|t| {
+                t.with_on("100ms")
+                    .with_every("100ms")
+                    .with_max(2)
+                    .with_then(|stmts| stmts.add(Act::msg(|msg| msg.with_id("remind"))))
+            })
+            .with_act(Act::req(|act| act.with_id("act1")))
+    });
+    let (proc, scher, emitter, tx, rx) =
+        create_proc_signal::<Vec<Message>>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("remind") {
+            rx.update(|data| data.push(e.inner().clone()));
+            if rx.data().len() == 2 {
+                rx.close();
+            }
+        }
+    });
+
+    scher.launch(&proc);
+    let ret = tx.recv().await;
+    assert_eq!(ret.len(), 2);
+
+    // no more reminders after the max
+    tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
+    proc.print();
+    let step1 = proc.task_by_nid("step1").first().unwrap().clone();
+    let key = format!("{}100ms", consts::IS_TIMEOUT_PROCESSED_PREFIX);
+    assert_eq!(step1.data().get::<u32>(&key), Some(2));
+}
+
+#[tokio::test]
+async fn sch_step_timeout_interrupting() {
+    let mut workflow = Workflow::new()
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_timeout(|t| {
+                    t.with_on("100ms")
+                        .with_mode(TimeoutMode::Interrupting)
+                        .with_then(|stmts| stmts.add(Act::msg(|msg| msg.with_id("msg1"))))
+                })
+                .with_act(Act::req(|act| act.with_id("act1")))
+        })
+        .with_step(|step| {
+            step.with_id("step2")
+                .with_act(Act::req(|act| act.with_id("act2")))
+        });
+    let (proc, scher, emitter, tx, rx) = create_proc_signal::<()>(&mut workflow, &utils::longid());
+    emitter.on_message(move |e| {
+        if e.is_key("act2") && e.is_state("created") {
+            rx.close();
+        }
+    });
+
+    scher.launch(&proc);
+    tx.recv().await;
+    proc.print();
+    assert_eq!(
+        proc.task_by_nid("act1").first().unwrap().state(),
+        TaskState::Cancelled
+    );
+    assert_eq!(
+        proc.task_by_nid("step1").first().unwrap().state(),
+        TaskState::Completed
+    );
+}
@@ -0,0 +1,40 @@
+use crate::sch::Timer;
+
+#[test]
+fn sch_timer_take_due() {
+    let timer = Timer::new();
+    timer.add(300, "p1", "t3");
+    timer.add(100, "p1", "t1");
+    timer.add(200, "p2", "t2");
+    assert_eq!(timer.len(), 3);
+    assert_eq!(timer.next_due(), Some(100));
+
+    let items = timer.take(200);
+    assert_eq!(
+        items,
+        [
+            ("p1".to_string(), "t1".to_string()),
+            ("p2".to_string(), "t2".to_string())
+        ]
+    );
+    assert_eq!(timer.next_due(), Some(300));
+}
+
+#[test]
+fn sch_timer_take_empty() {
+    let timer = Timer::new();
+    timer.add(300, "p1", "t1");
+    assert!(timer.take(100).is_empty());
+    assert_eq!(timer.len(), 1);
+}
+
+#[tokio::test]
+async fn sch_timer_changed() {
+    let timer = Timer::new();
+    timer.add(100, "p1", "t1");
+
+    // the notification is kept when adding before waiting
+    tokio::time::timeout(std::time::Duration::from_secs(1), timer.changed())
+        .await
+        .unwrap();
+}
@@ -5,7 +5,7 @@ use crate::{
         tree::{NodeContent, NodeTree},
         Node,
     },
-    Act, NodeKind, Workflow,
+    Act, ActError, ModelBase, NodeKind, StmtBuild, Workflow,
 };
 
 const SIMPLE_WORKFLOW: &str = r#"
@@ -315,3 +315,246 @@ async fn sch_tree_node_act2_ser_de() {
     // not care the parent for act when resume data from string
     assert!(act.parent().is_none());
 }
+
+fn fragments_model() -> Workflow {
+    Workflow::new()
+        .with_id("fragments")
+        .with_fragment(|f| {
+            f.with_id("approval")
+                .with_input("role", "admin")
+                .with_step(|step| {
+                    step.with_id("approval")
+                        .with_name("approval")
+                        .with_act(Act::req(|act| act.with_id("approve")))
+                })
+        })
+        .with_fragment(|f| {
+            f.with_id("notify")
+                .with_input("to", "admin")
+                .with_acts(|acts| acts.add(Act::msg(|msg| msg.with_id("notify"))))
+        })
+}
+
+#[tokio::test]
+async fn sch_tree_expand_local() {
+    let mut workflow = fragments_model()
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_include(|i| i.with_uses("approval"))
+        })
+        .with_step(|step| {
+            step.with_id("step2")
+                .with_include(|i| i.with_uses("approval").with_input("role", "manager"))
+        });
+    NodeTree::expand(&mut workflow, &|_, _| {
+        Err(ActError::Model("no source".to_string()))
+    })
+    .unwrap();
+
+    let step1 = &workflow.steps[0];
+    assert!(step1.include.is_none());
+    assert_eq!(step1.name, "approval");
+    assert_eq!(step1.inputs.get::<String>("role").unwrap(), "admin");
+    assert_eq!(step1.acts[0].id(), "step1_approve");
+    let Act::Req(req) = &step1.acts[0] else {
+        panic!("the act should be req");
+    };
+    assert_eq!(req.key, "approve");
+
+    let step2 = &workflow.steps[1];
+    assert_eq!(step2.inputs.get::<String>("role").unwrap(), "manager");
+    assert_eq!(step2.acts[0].id(), "step2_approve");
+
+    let tree = NodeTree::build(&mut workflow).unwrap();
+    assert!(tree.node("step1_approve").is_some());
+    assert!(tree.node("step2_approve").is_some());
+}
+
+#[tokio::test]
+async fn sch_tree_expand_twice_without_id() {
+    let mut workflow = fragments_model()
+        .with_step(|step| step.with_include(|i| i.with_uses("approval")))
+        .with_step(|step| step.with_include(|i| i.with_uses("approval")));
+    NodeTree::expand(&mut workflow, &|_, _| {
+        Err(ActError::Model("no source".to_string()))
+    })
+    .unwrap();
+
+    let (step1, step2) = (workflow.steps[0].clone(), workflow.steps[1].clone());
+    assert!(!step1.id.is_empty());
+    assert_ne!(step1.id, step2.id);
+    assert_eq!(step1.acts[0].id(), format!("{}_approve", step1.id));
+    assert_eq!(step2.acts[0].id(), format!("{}_approve", step2.id));
+
+    let tree = NodeTree::build(&mut workflow).unwrap();
+    assert!(tree.node(&step1.id).is_some());
+    assert!(tree.node(&step2.id).is_some());
+}
+
+#[tokio::test]
+async fn sch_tree_expand_source() {
+    let mut workflow = Workflow::new().with_id("m1").with_step(|step| {
+        step.with_id("step1").with_act(Act::include(|i| {
+            i.with_id("notify1")
+                .with_uses("fragments/notify@2")
+                .with_input("to", "manager")
+        }))
+    });
+    NodeTree::expand(&mut workflow, &|source, ver| {
+        assert_eq!(source, "fragments");
+        assert_eq!(ver, Some(2));
+        Ok(fragments_model())
+    })
+    .unwrap();
+
+    let Act::Block(block) = &workflow.steps[0].acts[0] else {
+        panic!("the act should be block");
+    };
+    assert_eq!(block.id, "notify1");
+    assert_eq!(block.inputs.get::<String>("to").unwrap(), "manager");
+    assert_eq!(block.acts[0].id(), "notify1_notify");
+    let Act::Msg(msg) = &block.acts[0] else {
+        panic!("the act should be msg");
+    };
+    assert_eq!(msg.key, "notify");
+}
+
+#[tokio::test]
+async fn sch_tree_expand_acts_twice_without_id() {
+    let mut workflow = fragments_model().with_step(|step| {
+        step.with_id("step1")
+            .with_act(Act::include(|i| i.with_uses("notify")))
+            .with_act(Act::include(|i| i.with_uses("notify")))
+    });
+    NodeTree::expand(&mut workflow, &|_, _| {
+        Err(ActError::Model("no source".to_string()))
+    })
+    .unwrap();
+
+    let acts = workflow.steps[0].acts.clone();
+    let (Act::Block(block1), Act::Block(block2)) = (&acts[0], &acts[1]) else {
+        panic!("the acts should be blocks");
+    };
+    assert!(!block1.id.is_empty());
+    assert_ne!(block1.id, block2.id);
+    assert_eq!(block1.acts[0].id(), format!("{}_notify", block1.id));
+    assert_eq!(block2.acts[0].id(), format!("{}_notify", block2.id));
+
+    let tree = NodeTree::build(&mut workflow).unwrap();
+    assert!(tree.node(&block1.id).is_some());
+    assert!(tree.node(&block2.id).is_some());
+}
+
+#[tokio::test]
+async fn sch_tree_expand_branches_twice() {
+    let mut workflow = Workflow::new()
+        .with_id("m1")
+        .with_fragment(|f| {
+            f.with_id("review").with_step(|step| {
+                step.with_id("review").with_branch(|b| {
+                    b.with_id("b1")
+                        .with_if("true")
+                        .with_step(|step| step.with_id("check").with_next("sign"))
+                        .with_step(|step| {
+                            step.with_id("sign")
+                                .with_act(Act::req(|act| act.with_id("act1")))
+                        })
+                })
+            })
+        })
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_include(|i| i.with_uses("review"))
+        })
+        .with_step(|step| {
+            step.with_id("step2")
+                .with_include(|i| i.with_uses("review"))
+        });
+    NodeTree::expand(&mut workflow, &|_, _| {
+        Err(ActError::Model("no source".to_string()))
+    })
+    .unwrap();
+
+    let branch = &workflow.steps[1].branches[0];
+    assert_eq!(branch.id, "step2_b1");
+    assert_eq!(branch.steps[0].id, "step2_check");
+    assert_eq!(branch.steps[0].next.as_deref(), Some("step2_sign"));
+    assert_eq!(branch.steps[1].id, "step2_sign");
+    assert_eq!(branch.steps[1].acts[0].id(), "step2_act1");
+
+    let tree = NodeTree::build(&mut workflow).unwrap();
+    for id in ["step1_b1", "step1_check", "step1_sign", "step1_act1"] {
+        assert!(tree.node(id).is_some());
+    }
+    for id in ["step2_b1", "step2_check", "step2_sign", "step2_act1"] {
+        assert!(tree.node(id).is_some());
+    }
+}
+
+#[tokio::test]
+async fn sch_tree_expand_nested_acts() {
+    let mut workflow = fragments_model().with_step(|step| {
+        step.with_id("step1").with_act(Act::r#if(|cond| {
+            cond.with_on("true")
+                .with_then(|stmts| stmts.add(Act::include(|i| i.with_uses("notify"))))
+        }))
+    });
+    NodeTree::expand(&mut workflow, &|_, _| {
+        Err(ActError::Model("no source".to_string()))
+    })
+    .unwrap();
+
+    let Act::If(cond) = &workflow.steps[0].acts[0] else {
+        panic!("the act should be if");
+    };
+    assert!(matches!(cond.then[0], Act::Block(_)));
+}
+
+#[tokio::test]
+async fn sch_tree_expand_cycle_error() {
+    let mut workflow = Workflow::new()
+        .with_id("m1")
+        .with_fragment(|f| {
+            f.with_acts(|acts| acts.add(Act::include(|i| i.with_uses("b"))))
+                .with_id("a")
+        })
+        .with_fragment(|f| {
+            f.with_acts(|acts| acts.add(Act::include(|i| i.with_uses("a"))))
+                .with_id("b")
+        })
+        .with_step(|step| {
+            step.with_id("step1")
+                .with_act(Act::include(|i| i.with_uses("a")))
+        });
+    let result = NodeTree::expand(&mut workflow, &|_, _| {
+        Err(ActError::Model("no source".to_string()))
+    });
+    assert_eq!(
+        result.unwrap_err(),
+        ActError::Model("found cycle in includes 'm1/a -> m1/b -> m1/a'".to_string())
+    );
+}
+
+#[tokio::test]
+async fn sch_tree_expand_not_found_error() {
+    let mut workflow = Workflow::new().with_id("m1").with_step(|step| {
+        step.with_id("step1")
+            .with_include(|i| i.with_uses("approval"))
+    });
+    let result = NodeTree::expand(&mut workflow, &|_, _| {
+        Err(ActError::Model("no source".to_string()))
+    });
+    assert!(result.is_err());
+
+    let mut workflow = Workflow::new().with_id("m1").with_step(|step| {
+        step.with_id("step1")
+            .with_include(|i| i.with_uses("fragments/approval"))
+    });
+    let result = NodeTree::expand(&mut workflow, &|_, _| {
+        Err(ActError::Model("no source".to_string()))
+    });
+    assert_eq!(
+        result.unwrap_err(),
+        ActError::Model("no source".to_string())
+    );
+}
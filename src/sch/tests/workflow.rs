@@ -110,14 +110,18 @@ async fn sch_workflow_setup_on_completed() {
     workflow.print();
     let (proc, scher, emitter, sig, s1) =
         create_proc_signal::<Vec<Message>>(&mut workflow, &utils::longid());
+    let s2 = sig.clone();
     emitter.on_message(move |e| {
         println!("message: {:?}", e);
         if e.is_type("msg") {
             s1.update(|data| data.push(e.inner().clone()));
-            s1.close();
         }
     });
 
+    emitter.on_complete(move |_| {
+        s2.close();
+    });
+
     scher.launch(&proc);
     let messages = sig.recv().await;
     proc.print();
@@ -0,0 +1,61 @@
+use std::{collections::BTreeSet, sync::Mutex};
+use tokio::sync::Notify;
+
+/// The timer service to resume the delayed tasks
+///
+/// the timers are ordered by the due time, so the service only sleeps until the
+/// nearest one instead of scanning every task on each tick
+#[derive(Debug, Default)]
+pub struct Timer {
+    items: Mutex<BTreeSet<(i64, String, String)>>,
+    notify: Notify,
+}
+
+impl Timer {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// add a timer for the task, the task is resumed at the `due` millis
+    pub fn add(&self, due: i64, pid: &str, tid: &str) {
+        self.items
+            .lock()
+            .unwrap()
+            .insert((due, pid.to_string(), tid.to_string()));
+
+        // wake up the service to check the new due time
+        self.notify.notify_one();
+    }
+
+    /// remove and return the (pid, tid) of the due timers
+    pub fn take(&self, now: i64) -> Vec<(String, String)> {
+        let mut items = self.items.lock().unwrap();
+        let mut ret = Vec::new();
+        while let Some(first) = items.first() {
+            if first.0 > now {
+                break;
+            }
+            let (_, pid, tid) = items.pop_first().unwrap();
+            ret.push((pid, tid));
+        }
+        ret
+    }
+
+    /// the nearest due time
+    pub fn next_due(&self) -> Option<i64> {
+        self.items.lock().unwrap().first().map(|(due, ..)| *due)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// wait until a new timer is added
+    pub async fn changed(&self) {
+        self.notify.notified().await
+    }
+}
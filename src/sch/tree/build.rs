@@ -4,7 +4,7 @@ use super::{
 };
 use crate::{
     utils::{longid, shortid},
-    Act, ActError, Branch, ModelBase, Result, Step, Workflow,
+    Act, ActError, Block, Branch, Fragment, Include, ModelBase, Result, Step, Vars, Workflow,
 };
 use std::sync::Arc;
 
@@ -126,3 +126,251 @@ pub fn build_act(
 
     Ok(())
 }
+
+/// load the workflow of the include source with the optional version
+pub type FragmentLoader<'a> = dyn Fn(&str, Option<u32>) -> Result<Workflow> + 'a;
+
+/// expand the includes in workflow by the fragments
+pub fn expand_workflow(workflow: &mut Workflow, load: &FragmentLoader) -> Result<()> {
+    let owner = workflow.clone();
+    let mut expander = Expander {
+        load,
+        stack: Vec::new(),
+    };
+    expander.acts(&owner, &mut workflow.setup)?;
+    for step in workflow.steps.iter_mut() {
+        expander.step(&owner, step)?;
+    }
+
+    Ok(())
+}
+
+struct Expander<'a> {
+    load: &'a FragmentLoader<'a>,
+    stack: Vec<String>,
+}
+
+impl Expander<'_> {
+    fn step(&mut self, owner: &Workflow, step: &mut Step) -> Result<()> {
+        if let Some(include) = step.include.take() {
+            let (model, fragment) = self.enter(owner, &include)?;
+            let mut expanded = match fragment.step {
+                Some(step) => step,
+                None => Step {
+                    acts: fragment.acts,
+                    ..Default::default()
+                },
+            };
+            for (name, value) in params(&fragment.inputs, &include).iter() {
+                expanded.inputs.insert(name.clone(), value.clone());
+            }
+            self.step(&model, &mut expanded)?;
+            self.stack.pop();
+
+            // keep the ids unique when including the same fragment in several steps
+            if step.id.is_empty() {
+                step.id = shortid();
+            }
+            prefix_step(&mut expanded, &step.id);
+            expanded.id = step.id.clone();
+            if !step.name.is_empty() {
+                expanded.name = step.name.clone();
+            }
+            if step.next.is_some() {
+                expanded.next = step.next.clone();
+            }
+            *step = expanded;
+            return Ok(());
+        }
+
+        self.acts(owner, &mut step.setup)?;
+        self.acts(owner, &mut step.acts)?;
+        self.acts(owner, &mut step.compensate)?;
+        for catch in step.catches.iter_mut() {
+            self.acts(owner, &mut catch.then)?;
+        }
+        for timeout in step.timeout.iter_mut() {
+            self.acts(owner, &mut timeout.then)?;
+        }
+        for branch in step.branches.iter_mut() {
+            for step in branch.steps.iter_mut() {
+                self.step(owner, step)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn acts(&mut self, owner: &Workflow, acts: &mut [Act]) -> Result<()> {
+        for act in acts.iter_mut() {
+            if let Act::Include(include) = act {
+                let (model, fragment) = self.enter(owner, include)?;
+                if fragment.acts.is_empty() {
+                    return Err(ActError::Model(format!(
+                        "the fragment '{}' has no acts to include",
+                        include.uses
+                    )));
+                }
+                if include.id.is_empty() {
+                    include.id = shortid();
+                }
+                let mut block = Block {
+                    id: include.id.clone(),
+                    inputs: params(&fragment.inputs, include),
+                    acts: fragment.acts,
+                    next: None,
+                };
+                self.acts(&model, &mut block.acts)?;
+                self.stack.pop();
+                prefix_acts(&mut block.acts, &block.id);
+
+                *act = Act::Block(block);
+                continue;
+            }
+
+            for children in children_mut(act) {
+                self.acts(owner, children)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// find the fragment and push it to the include stack to check the cycle
+    fn enter(&mut self, owner: &Workflow, include: &Include) -> Result<(Workflow, Fragment)> {
+        let (source, id, ver) = include.parse()?;
+        let model = match &source {
+            Some(source) => (self.load)(source, ver)?,
+            None => owner.clone(),
+        };
+        let fragment =
+            model
+                .fragments
+                .iter()
+                .find(|f| f.id == id)
+                .cloned()
+                .ok_or(ActError::Model(format!(
+                    "cannot find the fragment '{}'",
+                    include.uses
+                )))?;
+
+        let key = match ver {
+            Some(ver) => format!("{}/{id}@{ver}", model.id),
+            None => format!("{}/{id}", model.id),
+        };
+        if self.stack.contains(&key) {
+            self.stack.push(key);
+            return Err(ActError::Model(format!(
+                "found cycle in includes '{}'",
+                self.stack.join(" -> ")
+            )));
+        }
+        self.stack.push(key);
+
+        Ok((model, fragment))
+    }
+}
+
+fn params(defaults: &Vars, include: &Include) -> Vars {
+    let mut inputs = defaults.clone();
+    for (name, value) in include.inputs.iter() {
+        inputs.insert(name.clone(), value.clone());
+    }
+    inputs
+}
+
+fn prefix_step(step: &mut Step, prefix: &str) {
+    prefix_acts(&mut step.setup, prefix);
+    prefix_acts(&mut step.acts, prefix);
+    prefix_acts(&mut step.compensate, prefix);
+    for catch in step.catches.iter_mut() {
+        prefix_acts(&mut catch.then, prefix);
+    }
+    for timeout in step.timeout.iter_mut() {
+        prefix_acts(&mut timeout.then, prefix);
+    }
+    for branch in step.branches.iter_mut() {
+        prefix_id(&mut branch.id, prefix);
+        if let Some(next) = branch.next.as_mut() {
+            prefix_id(next, prefix);
+        }
+        for need in branch.needs.iter_mut() {
+            prefix_id(need, prefix);
+        }
+        for step in branch.steps.iter_mut() {
+            prefix_id(&mut step.id, prefix);
+            if let Some(next) = step.next.as_mut() {
+                prefix_id(next, prefix);
+            }
+            prefix_step(step, prefix);
+        }
+    }
+}
+
+fn prefix_id(id: &mut String, prefix: &str) {
+    if !id.is_empty() {
+        *id = format!("{prefix}_{id}");
+    }
+}
+
+fn prefix_acts(acts: &mut [Act], prefix: &str) {
+    for act in acts.iter_mut() {
+        prefix_act(act, prefix);
+        for children in children_mut(act) {
+            prefix_acts(children, prefix);
+        }
+    }
+}
+
+fn prefix_act(act: &mut Act, prefix: &str) {
+    let id = act.id().to_string();
+    if id.is_empty() {
+        return;
+    }
+    // the clients still receive the messages by the original key
+    match act {
+        Act::Req(req) if req.key.is_empty() => req.key = id.clone(),
+        Act::Msg(msg) if msg.key.is_empty() => msg.key = id.clone(),
+        _ => {}
+    }
+    act.set_id(&format!("{prefix}_{id}"));
+}
+
+fn children_mut(act: &mut Act) -> Vec<&mut Vec<Act>> {
+    let mut ret = Vec::new();
+    match act {
+        Act::Req(req) => {
+            ret.push(&mut req.on_created);
+            ret.push(&mut req.on_completed);
+            ret.push(&mut req.compensate);
+            ret.extend(req.catches.iter_mut().map(|c| &mut c.then));
+            ret.extend(req.timeout.iter_mut().map(|t| &mut t.then));
+        }
+        Act::Pack(pack) => ret.push(&mut pack.compensate),
+        Act::Call(call) => ret.push(&mut call.compensate),
+        Act::Wait(wait) => ret.extend(wait.timeout.iter_mut().map(|t| &mut t.then)),
+        Act::Block(block) => {
+            let mut block = Some(block);
+            while let Some(Block { acts, next, .. }) = block {
+                ret.push(acts);
+                block = next.as_deref_mut();
+            }
+        }
+        Act::If(cond) => {
+            ret.push(&mut cond.then);
+            ret.push(&mut cond.r#else);
+        }
+        Act::Each(each) => ret.push(&mut each.run),
+        Act::Chain(chain) => ret.push(&mut chain.run),
+        Act::Loop(l) => ret.push(&mut l.run),
+        Act::OnCreated(acts)
+        | Act::OnUpdated(acts)
+        | Act::OnBeforeUpdate(acts)
+        | Act::OnStep(acts)
+        | Act::OnCompleted(acts) => ret.push(acts),
+        Act::OnTimeout(timeouts) => ret.extend(timeouts.iter_mut().map(|t| &mut t.then)),
+        Act::OnErrorCatch(catches) => ret.extend(catches.iter_mut().map(|c| &mut c.then)),
+        _ => {}
+    }
+    ret
+}
@@ -1,4 +1,4 @@
-use crate::{Act, Branch, ModelBase, Step, Vars, Workflow};
+use crate::{Act, Branch, Limits, ModelBase, Retry, Step, Vars, Workflow};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, RwLock, Weak};
 
@@ -86,6 +86,30 @@ impl NodeContent {
             _ => Vars::new(),
         }
     }
+
+    pub fn retry(&self) -> Option<&Retry> {
+        match self {
+            NodeContent::Step(node) => node.retry.as_ref(),
+            NodeContent::Act(node) => node.retry(),
+            _ => None,
+        }
+    }
+
+    pub fn limits(&self) -> Option<&Limits> {
+        match self {
+            NodeContent::Step(node) => node.limits.as_ref(),
+            NodeContent::Act(node) => node.limits(),
+            _ => None,
+        }
+    }
+
+    pub fn compensate(&self) -> &[Act] {
+        match self {
+            NodeContent::Step(node) => &node.compensate,
+            NodeContent::Act(node) => node.compensate(),
+            _ => &[],
+        }
+    }
 }
 
 impl Node {
@@ -221,6 +245,17 @@ impl Node {
         serde_json::to_string(&data).unwrap()
     }
 
+    /// read the message key and tag from the saved node data without the node tree
+    pub fn key_tag_from_str(s: &str) -> Option<(String, String)> {
+        let data: NodeJsonData = serde_json::from_str(s).ok()?;
+        let node = Self::new(&data.id, data.content, data.level);
+        let mut key = node.key();
+        if key.is_empty() {
+            key = node.id();
+        }
+        Some((key.to_string(), node.tag().to_string()))
+    }
+
     pub fn from_str(s: &str, tree: &node_tree::NodeTree) -> Arc<Self> {
         let data: NodeJsonData = serde_json::from_str(s).unwrap();
         let ret = Arc::new(Self::new(&data.id, data.content, data.level));
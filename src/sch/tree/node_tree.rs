@@ -39,6 +39,11 @@ impl NodeTree {
         Ok(tree)
     }
 
+    /// expand the fragment includes of the workflow before building the tree
+    pub fn expand(workflow: &mut Workflow, load: &build::FragmentLoader) -> Result<()> {
+        build::expand_workflow(workflow, load)
+    }
+
     pub fn load(&mut self, model: &Workflow) -> Result<()> {
         let mut model = model.clone();
         build::build_workflow(&mut model, self)
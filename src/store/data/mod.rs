@@ -4,10 +4,12 @@ mod model;
 mod package;
 mod proc;
 mod task;
+mod trigger;
 
 pub use data::Data;
 pub use message::{Message, MessageStatus};
-pub use model::Model;
+pub use model::{Model, ModelVersion};
 pub use package::Package;
 pub use proc::Proc;
 pub use task::Task;
+pub use trigger::Trigger;
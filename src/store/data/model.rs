@@ -9,3 +9,28 @@ pub struct Model {
     pub time: i64,
     pub data: String,
 }
+
+impl From<ModelVersion> for Model {
+    fn from(v: ModelVersion) -> Self {
+        Self {
+            id: v.mid,
+            name: v.name,
+            ver: v.ver,
+            size: v.size,
+            time: v.time,
+            data: v.data,
+        }
+    }
+}
+
+/// The deployed model version, the `id` is combined by `mid` and `ver`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ModelVersion {
+    pub id: String,
+    pub mid: String,
+    pub ver: u32,
+    pub name: String,
+    pub size: u32,
+    pub time: i64,
+    pub data: String,
+}
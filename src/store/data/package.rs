@@ -1,3 +1,4 @@
+use crate::Limits;
 use serde::{Deserialize, Serialize};
 
 #[derive(Default, Deserialize, Serialize, Debug, Clone)]
@@ -10,4 +11,8 @@ pub struct Package {
     pub create_time: i64,
     pub update_time: i64,
     pub timestamp: i64,
+
+    /// the default limits to run the package, overridden by the act and step limits
+    #[serde(default)]
+    pub limits: Option<Limits>,
 }
@@ -18,6 +18,10 @@ pub struct Task {
     pub end_time: i64,
     pub hooks: String,
     pub timestamp: i64,
+
+    /// the correlation key of the `!wait` task to query by the signal
+    #[serde(default)]
+    pub wait_key: String,
 }
 
 impl Task {
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+pub struct Trigger {
+    pub id: String,
+    pub mid: String,
+    pub data: String,
+    pub last_time: i64,
+    pub next_time: i64,
+}
@@ -1,6 +1,9 @@
-use super::{database::Database, DbRow, DbSchema};
+use super::{database::Database, to_record};
 use crate::{
-    store::{map_db_err, query::CondType, DbSet, Expr, ExprOp, Query},
+    store::{
+        db::sql::{filter, repeat_var, DbRow, DbSchema, DbValue},
+        map_db_err, DbSet, Query,
+    },
     Result, ShareLock,
 };
 use duckdb::{params, params_from_iter};
@@ -58,41 +61,16 @@ where
         let row = conn
             .prepare(&sql)
             .map_err(map_db_err)?
-            .query_row(params![id], |row| Ok(T::from_row(row)))
+            .query_row(params![id], |row| to_record(row, keys.len()))
             .map_err(map_db_err)?;
-        let model = row.map_err(map_db_err)?;
-        Ok(model)
+        T::from_row(&row)
     }
 
     fn query(&self, q: &Query) -> Result<Vec<T>> {
         debug!("local::{}.query({:?})", self.name, q);
         let db = self.db.read().unwrap();
         let conn = db.pool().get().unwrap();
-        let mut filter = String::new();
-        if q.is_cond() {
-            let mut q = q.clone();
-
-            let queries = q.queries();
-            for (index, cond) in queries.iter().enumerate() {
-                let typ = match cond.r#type {
-                    CondType::And => "and",
-                    CondType::Or => "or",
-                };
-                filter.push_str("(");
-                for (index, expr) in cond.conds().iter().enumerate() {
-                    filter.push_str(&expr.sql()?);
-                    if index != cond.conds().len() - 1 {
-                        filter.push_str(&format!(" {typ} "));
-                    }
-                }
-                filter.push_str(")");
-
-                if index != queries.len() - 1 {
-                    filter.push_str(&format!(" and "));
-                }
-            }
-        }
-
+        let filter = filter(q)?;
         debug!("filter: {filter}");
         let schema = T::schema()?;
         let keys: Vec<&str> = schema.iter().map(|(k, _)| k.as_str()).collect();
@@ -117,10 +95,10 @@ where
         let ret = conn
             .prepare(&sql)
             .map_err(map_db_err)?
-            .query_map([], |row| T::from_row(row))
+            .query_map([], |row| to_record(row, keys.len()))
             .map_err(map_db_err)?
-            .map(|v| v.unwrap())
-            .collect::<Vec<_>>();
+            .map(|v| T::from_row(&v.map_err(map_db_err)?))
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(ret)
     }
@@ -164,15 +142,11 @@ where
             keys.push(format!("{} = ?", k.as_str()));
             values.push(v);
         }
+        values.push(DbValue::Text(model.id().to_string()));
 
         let ret = conn
             .execute(
-                &format!(
-                    "update {} set {} where id = '{}'",
-                    self.name,
-                    keys.join(","),
-                    model.id()
-                ),
+                &format!("update {} set {} where id = ?", self.name, keys.join(",")),
                 params_from_iter(values),
             )
             .map_err(map_db_err)?;
@@ -194,144 +168,3 @@ where
         Ok(ret > 0)
     }
 }
-
-fn repeat_var(len: usize) -> String {
-    let mut var = "?,".repeat(len);
-    var.pop();
-
-    var
-}
-
-impl Expr {
-    pub fn sql(&self) -> Result<String> {
-        let key = &self.key;
-        let op = match self.op {
-            ExprOp::EQ => "=",
-            ExprOp::NE => "!=",
-            ExprOp::LT => "<",
-            ExprOp::LE => "<=",
-            ExprOp::GT => ">",
-            ExprOp::GE => ">=",
-        };
-        match &self.value {
-            serde_json::Value::Null => {
-                if self.op == ExprOp::EQ {
-                    return Ok(format!("{key} is null"));
-                } else if self.op == ExprOp::NE {
-                    return Ok(format!("{key} is not null"));
-                }
-                Err(crate::ActError::Store(format!(
-                    "the operation({op}) is not support for null"
-                )))
-            }
-            serde_json::Value::Bool(v) => {
-                if self.op == ExprOp::EQ || self.op == ExprOp::NE {
-                    return Ok(format!("{key} {op} {v}"));
-                }
-                Err(crate::ActError::Store(format!(
-                    "the operation({op}) is not support for bool"
-                )))
-            }
-            serde_json::Value::Number(v) => Ok(format!("{key} {op} {v}")),
-            serde_json::Value::String(v) => {
-                if self.op == ExprOp::EQ || self.op == ExprOp::NE {
-                    return Ok(format!("{key} {op} '{v}'"));
-                }
-                Err(crate::ActError::Store(format!(
-                    "the operation({op}) is not support for string"
-                )))
-            }
-            v @ _ => Err(crate::ActError::Store(format!(
-                "not support sql value for '{v}'"
-            ))),
-        }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::store::{Expr, MessageStatus};
-    use serde_json::json;
-
-    #[test]
-    fn store_query_expr_eq_sql_null() {
-        let expr = Expr::eq("a", json!(null));
-        assert_eq!(expr.key(), "a");
-        assert_eq!(expr.sql().unwrap(), "a is null");
-    }
-
-    #[test]
-    fn store_query_expr_eq_sql_not_null() {
-        let expr = Expr::ne("a", json!(null));
-        assert_eq!(expr.key(), "a");
-        assert_eq!(expr.sql().unwrap(), "a is not null");
-    }
-
-    #[test]
-    fn store_query_expr_eq_sql_str() {
-        let expr = Expr::eq("a", "abc");
-        assert_eq!(expr.key(), "a");
-        assert_eq!(expr.sql().unwrap(), "a = 'abc'");
-    }
-
-    #[test]
-    fn store_query_expr_ne_sql_str() {
-        let expr = Expr::ne("a", "abc");
-        assert_eq!(expr.key(), "a");
-        assert_eq!(expr.sql().unwrap(), "a != 'abc'");
-    }
-
-    #[test]
-    fn store_query_expr_eq_sql_num() {
-        let expr = Expr::eq("a", 5);
-        assert_eq!(expr.key(), "a");
-        assert_eq!(expr.sql().unwrap(), "a = 5");
-    }
-
-    #[test]
-    fn store_query_expr_ne_sql_num() {
-        let expr = Expr::ne("a", 5);
-        assert_eq!(expr.key(), "a");
-        assert_eq!(expr.sql().unwrap(), "a != 5");
-    }
-
-    #[test]
-    fn store_query_expr_lt_sql_num() {
-        let expr = Expr::lt("a", 5);
-        assert_eq!(expr.key(), "a");
-        assert_eq!(expr.sql().unwrap(), "a < 5");
-    }
-
-    #[test]
-    fn store_query_expr_le_sql_num() {
-        let expr = Expr::le("a", 5);
-        assert_eq!(expr.key(), "a");
-        assert_eq!(expr.sql().unwrap(), "a <= 5");
-    }
-
-    #[test]
-    fn store_query_expr_gt_sql_num() {
-        let expr = Expr::gt("a", 5);
-        assert_eq!(expr.key(), "a");
-        assert_eq!(expr.sql().unwrap(), "a > 5");
-    }
-
-    #[test]
-    fn store_query_expr_ge_sql_num() {
-        let expr = Expr::ge("a", 5);
-        assert_eq!(expr.key(), "a");
-        assert_eq!(expr.sql().unwrap(), "a >= 5");
-    }
-
-    #[test]
-    fn store_query_expr_eq_sql_enum() {
-        let expr = Expr::eq("a", MessageStatus::Created);
-        assert_eq!(expr.sql().unwrap(), "a = 0");
-
-        let expr = Expr::eq("a", MessageStatus::Acked);
-        assert_eq!(expr.sql().unwrap(), "a = 1");
-
-        let expr = Expr::eq("a", MessageStatus::Completed);
-        assert_eq!(expr.sql().unwrap(), "a = 2");
-    }
-}
@@ -1,7 +1,8 @@
-use crate::store::db::local::{DbColumn, DbType};
-#[allow(unused_imports)]
+use crate::{
+    store::db::sql::{DbColumn, DbType},
+    utils::consts,
+};
 use duckdb::{params, AccessMode, Config, DuckdbConnectionManager};
-#[allow(unused_imports)]
 use std::{fs, path::Path};
 use tracing::debug;
 
@@ -17,34 +18,20 @@ impl std::fmt::Debug for Database {
 }
 
 impl Database {
-    #[allow(unused_variables)]
     pub fn new(path: &str, name: &str) -> Self {
-        // the db path will be conflict in tokio::test
-        // just use memory mode to test
-        #[cfg(not(test))]
-        {
+        let manager = if name == consts::MEMORY_DB {
+            DuckdbConnectionManager::memory().unwrap()
+        } else {
             fs::create_dir_all(path).unwrap();
             let config = Config::default()
                 .access_mode(AccessMode::ReadWrite)
                 .unwrap();
-            let manager =
-                DuckdbConnectionManager::file_with_flags(Path::new(path).join(name), config)
-                    .unwrap();
-            let pool = r2d2::Pool::new(manager).unwrap();
-            Self {
-                pool,
-                path: path.to_string(),
-            }
-        }
-
-        #[cfg(test)]
-        {
-            let manager = DuckdbConnectionManager::memory().unwrap();
-            let pool = r2d2::Pool::new(manager).unwrap();
-            Self {
-                pool,
-                path: path.to_string(),
-            }
+            DuckdbConnectionManager::file_with_flags(Path::new(path).join(name), config).unwrap()
+        };
+        let pool = r2d2::Pool::new(manager).unwrap();
+        Self {
+            pool,
+            path: path.to_string(),
         }
     }
 
@@ -61,20 +48,7 @@ impl Database {
         let len = schema.len();
         let mut idx_sqls = Vec::new();
         for (index, (key, col)) in schema.iter().enumerate() {
-            sql.push_str(&format!(
-                "{key} {}",
-                match col.db_type {
-                    DbType::Boolean => "BOOLEAN".to_string(),
-                    DbType::Double => "DOUBLE".to_string(),
-                    DbType::Decimal(width, scale) => format!("DECIMAL({width},{scale})"),
-                    DbType::Int8 => "TINYINT".to_string(),
-                    DbType::Int16 => "SMALLINT".to_string(),
-                    DbType::Int32 => "INTEGER".to_string(),
-                    DbType::Int64 => "BIGINT".to_string(),
-                    DbType::Text => "VARCHAR".to_string(),
-                    DbType::Binary => "BLOB".to_string(),
-                }
-            ));
+            sql.push_str(&format!("{key} {}", column_type(col)));
 
             if col.is_not_null {
                 sql.push_str(" NOT NULL ");
@@ -119,6 +93,52 @@ impl Database {
             }
         }
 
+        // add the new columns to the table created by the older version
+        let columns = tr
+            .prepare("select column_name from information_schema.columns where table_name = ?")
+            .unwrap()
+            .query_map(params![name], |row| row.get::<usize, String>(0))
+            .unwrap()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        let missing = schema
+            .iter()
+            .filter(|(key, _)| !columns.contains(key))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            // duckdb cannot alter the table with indexes, so drop them and create them again
+            let indexes = tr
+                .prepare("select index_name, sql from duckdb_indexes() where table_name = ?")
+                .unwrap()
+                .query_map(params![name], |row| {
+                    Ok((row.get::<usize, String>(0)?, row.get::<usize, String>(1)?))
+                })
+                .unwrap()
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .unwrap();
+            for (index, _) in &indexes {
+                tr.execute(&format!("drop index {index}"), params![])
+                    .unwrap();
+            }
+
+            let mut idx_sqls = indexes.into_iter().map(|(_, sql)| sql).collect::<Vec<_>>();
+            for (key, col) in missing {
+                let mut sql = format!("alter table {name} add column {key} {}", column_type(col));
+                if let Some(default) = &col.default {
+                    sql.push_str(" DEFAULT ");
+                    sql.push_str(default);
+                }
+                debug!("sql={}", sql);
+                tr.execute(&sql, params![]).unwrap();
+                if col.is_index {
+                    idx_sqls.push(format!("create index idx_{name}_{key} on {name} ({key})"));
+                }
+            }
+            for idx_sql in idx_sqls {
+                tr.execute(&idx_sql, params![]).unwrap();
+            }
+        }
+
         tr.commit().unwrap();
     }
 
@@ -128,3 +148,17 @@ impl Database {
         }
     }
 }
+
+fn column_type(col: &DbColumn) -> String {
+    match col.db_type {
+        DbType::Boolean => "BOOLEAN".to_string(),
+        DbType::Double => "DOUBLE".to_string(),
+        DbType::Decimal(width, scale) => format!("DECIMAL({width},{scale})"),
+        DbType::Int8 => "TINYINT".to_string(),
+        DbType::Int16 => "SMALLINT".to_string(),
+        DbType::Int32 => "INTEGER".to_string(),
+        DbType::Int64 => "BIGINT".to_string(),
+        DbType::Text => "VARCHAR".to_string(),
+        DbType::Binary => "BLOB".to_string(),
+    }
+}
@@ -10,6 +10,10 @@ pub struct LocalStore {
     tasks: Arc<Collect<Task>>,
     packages: Arc<Collect<Package>>,
     messages: Arc<Collect<Message>>,
+    proc_histories: Arc<Collect<Proc>>,
+    task_histories: Arc<Collect<Task>>,
+    model_versions: Arc<Collect<ModelVersion>>,
+    triggers: Arc<Collect<Trigger>>,
 }
 
 impl LocalStore {
@@ -20,6 +24,10 @@ impl LocalStore {
         let tasks = Collect::new(&db, "tasks");
         let packages = Collect::new(&db, "packages");
         let messages = Collect::new(&db, "messages");
+        let proc_histories = Collect::new(&db, "proc_histories");
+        let task_histories = Collect::new(&db, "task_histories");
+        let model_versions = Collect::new(&db, "model_versions");
+        let triggers = Collect::new(&db, "triggers");
         let store = Self {
             db: db.clone(),
             models: Arc::new(models),
@@ -27,6 +35,10 @@ impl LocalStore {
             tasks: Arc::new(tasks),
             packages: Arc::new(packages),
             messages: Arc::new(messages),
+            proc_histories: Arc::new(proc_histories),
+            task_histories: Arc::new(task_histories),
+            model_versions: Arc::new(model_versions),
+            triggers: Arc::new(triggers),
         };
 
         store.init();
@@ -60,4 +72,20 @@ impl StoreAdapter for LocalStore {
     fn messages(&self) -> Arc<dyn DbSet<Item = Message>> {
         self.messages.clone()
     }
+
+    fn proc_histories(&self) -> Arc<dyn DbSet<Item = Proc>> {
+        self.proc_histories.clone()
+    }
+
+    fn task_histories(&self) -> Arc<dyn DbSet<Item = Task>> {
+        self.task_histories.clone()
+    }
+
+    fn model_versions(&self) -> Arc<dyn DbSet<Item = ModelVersion>> {
+        self.model_versions.clone()
+    }
+
+    fn triggers(&self) -> Arc<dyn DbSet<Item = Trigger>> {
+        self.triggers.clone()
+    }
 }
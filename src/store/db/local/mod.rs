@@ -1,58 +1,51 @@
 mod collect;
 mod database;
-mod r#impl;
 mod local;
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum DbType {
-    Boolean,
-    Double,
-    Decimal(u32, u32),
-    Int8,
-    Int16,
-    Int32,
-    Int64,
-    Text,
-    Binary,
-}
-
-#[derive(Debug)]
-pub struct DbColumn {
-    pub db_type: DbType,
-    pub is_not_null: bool,
-    pub is_primary_key: bool,
-    pub is_unique: bool,
-    pub is_index: bool,
-    pub default: Option<String>,
-}
+use super::sql::{DbRecord, DbValue};
+use duckdb::{
+    types::{ToSqlOutput, Value},
+    Row, ToSql,
+};
 
-impl Default for DbColumn {
-    fn default() -> Self {
-        Self {
-            db_type: DbType::Text,
-            is_not_null: false,
-            is_primary_key: false,
-            is_unique: false,
-            is_index: false,
-            default: None,
-        }
+impl ToSql for DbValue {
+    fn to_sql(&self) -> duckdb::Result<ToSqlOutput<'_>> {
+        let value = match self {
+            DbValue::Null => Value::Null,
+            DbValue::Boolean(v) => Value::Boolean(*v),
+            DbValue::Int(v) => Value::BigInt(*v),
+            DbValue::Double(v) => Value::Double(*v),
+            DbValue::Text(v) => Value::Text(v.clone()),
+            DbValue::Binary(v) => Value::Blob(v.clone()),
+        };
+        Ok(ToSqlOutput::Owned(value))
     }
 }
 
-use crate::Result;
-use duckdb::{types::Value, Error as DbError, Result as DbResult, Row};
-
-trait DbSchema {
-    fn schema() -> Result<Vec<(String, DbColumn)>>;
-}
+fn to_record(row: &Row<'_>, len: usize) -> duckdb::Result<DbRecord> {
+    let mut values = Vec::with_capacity(len);
+    for index in 0..len {
+        let value = match row.get::<usize, Value>(index)? {
+            Value::Null => DbValue::Null,
+            Value::Boolean(v) => DbValue::Boolean(v),
+            Value::TinyInt(v) => DbValue::Int(v.into()),
+            Value::SmallInt(v) => DbValue::Int(v.into()),
+            Value::Int(v) => DbValue::Int(v.into()),
+            Value::BigInt(v) => DbValue::Int(v),
+            Value::UTinyInt(v) => DbValue::Int(v.into()),
+            Value::USmallInt(v) => DbValue::Int(v.into()),
+            Value::UInt(v) => DbValue::Int(v.into()),
+            Value::UBigInt(v) => DbValue::Int(v as i64),
+            Value::Float(v) => DbValue::Double(v.into()),
+            Value::Double(v) => DbValue::Double(v),
+            Value::Text(v) => DbValue::Text(v),
+            Value::Blob(v) => DbValue::Binary(v),
+            v => DbValue::Text(format!("{v:?}")),
+        };
+        values.push(value);
+    }
 
-trait DbRow {
-    fn id(&self) -> &str;
-    fn from_row<'a>(row: &Row<'a>) -> DbResult<Self, DbError>
-    where
-        Self: Sized;
-    fn to_values(&self) -> Result<Vec<(String, Value)>>;
+    Ok(DbRecord::new(values))
 }
 
 pub use local::LocalStore;
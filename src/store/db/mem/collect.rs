@@ -62,18 +62,23 @@ where
         if q.is_cond() {
             let mut q = q.clone();
             for cond in q.queries_mut() {
-                let mut result = HashSet::new();
+                let mut result = None;
                 for expr in cond.conds().iter() {
+                    let mut matched = HashSet::new();
                     for (k, v) in db.iter() {
                         let prop_value = v.get(expr.key()).unwrap();
                         let cond_value = expr.value();
 
                         if expr.op(prop_value, cond_value) {
-                            result.insert(k.as_bytes().to_vec().into_boxed_slice());
+                            matched.insert(k.as_bytes().to_vec().into_boxed_slice());
                         }
                     }
+                    result = Some(match result {
+                        Some(result) => cond.calc(&result, &matched),
+                        None => matched,
+                    });
                 }
-                cond.calc(&result);
+                cond.result = result.unwrap_or_default();
             }
 
             let keys = q
@@ -129,22 +134,11 @@ where
 }
 
 impl Cond {
-    pub fn calc(&mut self, v: &HashSet<Box<[u8]>>) {
+    /// merge the matched keys of one expr into the result by the cond type
+    pub fn calc(&self, result: &HashSet<Box<[u8]>>, v: &HashSet<Box<[u8]>>) -> HashSet<Box<[u8]>> {
         match self.r#type {
-            CondType::And => {
-                if self.result.len() == 0 {
-                    self.result = v.clone();
-                } else {
-                    self.result = self.result.intersection(v).cloned().collect::<HashSet<_>>()
-                }
-            }
-            CondType::Or => {
-                if self.result.len() == 0 {
-                    self.result = v.clone();
-                } else {
-                    self.result = self.result.union(v).cloned().collect::<HashSet<_>>()
-                }
-            }
+            CondType::And => result.intersection(v).cloned().collect::<HashSet<_>>(),
+            CondType::Or => result.union(v).cloned().collect::<HashSet<_>>(),
         }
     }
 }
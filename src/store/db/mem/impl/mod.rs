@@ -3,3 +3,4 @@ mod model;
 mod package;
 mod proc;
 mod task;
+mod trigger;
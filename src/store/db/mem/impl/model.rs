@@ -1,5 +1,5 @@
 use crate::{
-    store::{db::mem::DbDocument, Model},
+    store::{db::mem::DbDocument, Model, ModelVersion},
     Result,
 };
 use serde_json::{json, Value as JsonValue};
@@ -21,3 +21,21 @@ impl DbDocument for Model {
         Ok(map)
     }
 }
+
+impl DbDocument for ModelVersion {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn doc(&self) -> Result<HashMap<String, JsonValue>> {
+        let mut map = HashMap::new();
+        map.insert("id".to_string(), json!(self.id.clone()));
+        map.insert("mid".to_string(), json!(self.mid.clone()));
+        map.insert("ver".to_string(), json!(self.ver));
+        map.insert("name".to_string(), json!(self.name.clone()));
+        map.insert("size".to_string(), json!(self.size));
+        map.insert("time".to_string(), json!(self.time));
+        map.insert("data".to_string(), json!(self.data.clone()));
+        Ok(map)
+    }
+}
@@ -22,6 +22,7 @@ impl DbDocument for Package {
         map.insert("create_time".to_string(), json!(self.create_time.clone()));
         map.insert("update_time".to_string(), json!(self.update_time.clone()));
         map.insert("timestamp".to_string(), json!(self.timestamp.clone()));
+        map.insert("limits".to_string(), json!(self.limits.clone()));
         Ok(map)
     }
 }
@@ -0,0 +1,22 @@
+use crate::{
+    store::{db::mem::DbDocument, Trigger},
+    Result,
+};
+use serde_json::{json, Value as JsonValue};
+use std::collections::HashMap;
+
+impl DbDocument for Trigger {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn doc(&self) -> Result<HashMap<String, JsonValue>> {
+        let mut map = HashMap::new();
+        map.insert("id".to_string(), json!(self.id.clone()));
+        map.insert("mid".to_string(), json!(self.mid.clone()));
+        map.insert("data".to_string(), json!(self.data.clone()));
+        map.insert("last_time".to_string(), json!(self.last_time));
+        map.insert("next_time".to_string(), json!(self.next_time));
+        Ok(map)
+    }
+}
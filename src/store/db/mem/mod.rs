@@ -17,6 +17,10 @@ pub struct MemStore {
     tasks: Arc<Collect<Task>>,
     packages: Arc<Collect<Package>>,
     messages: Arc<Collect<Message>>,
+    proc_histories: Arc<Collect<Proc>>,
+    task_histories: Arc<Collect<Task>>,
+    model_versions: Arc<Collect<ModelVersion>>,
+    triggers: Arc<Collect<Trigger>>,
 }
 
 trait DbDocument: Serialize + DeserializeOwned {
@@ -31,12 +35,20 @@ impl MemStore {
         let tasks = Collect::new("tasks");
         let packages = Collect::new("packages");
         let messages = Collect::new("messages");
+        let proc_histories = Collect::new("proc_histories");
+        let task_histories = Collect::new("task_histories");
+        let model_versions = Collect::new("model_versions");
+        let triggers = Collect::new("triggers");
         let store = Self {
             models: Arc::new(models),
             procs: Arc::new(procs),
             tasks: Arc::new(tasks),
             packages: Arc::new(packages),
             messages: Arc::new(messages),
+            proc_histories: Arc::new(proc_histories),
+            task_histories: Arc::new(task_histories),
+            model_versions: Arc::new(model_versions),
+            triggers: Arc::new(triggers),
         };
 
         store.init();
@@ -68,4 +80,20 @@ impl StoreAdapter for MemStore {
     fn messages(&self) -> Arc<dyn DbSet<Item = Message>> {
         self.messages.clone()
     }
+
+    fn proc_histories(&self) -> Arc<dyn DbSet<Item = Proc>> {
+        self.proc_histories.clone()
+    }
+
+    fn task_histories(&self) -> Arc<dyn DbSet<Item = Task>> {
+        self.task_histories.clone()
+    }
+
+    fn model_versions(&self) -> Arc<dyn DbSet<Item = ModelVersion>> {
+        self.model_versions.clone()
+    }
+
+    fn triggers(&self) -> Arc<dyn DbSet<Item = Trigger>> {
+        self.triggers.clone()
+    }
 }
@@ -1,7 +1,13 @@
 #[cfg(feature = "store")]
 mod local;
 mod mem;
+#[cfg(any(feature = "store", feature = "sqlite"))]
+mod sql;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 
 #[cfg(feature = "store")]
 pub use local::LocalStore;
 pub use mem::MemStore;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStore;
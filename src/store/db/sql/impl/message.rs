@@ -0,0 +1,233 @@
+use crate::{
+    store::{
+        db::sql::{DbColumn, DbRecord, DbRow, DbSchema, DbType, DbValue},
+        Message,
+    },
+    Result,
+};
+impl DbSchema for Message {
+    fn schema() -> Result<Vec<(String, DbColumn)>> {
+        let map = vec![
+            (
+                "id".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    is_primary_key: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "name".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "tid".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    is_index: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "state".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "type".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "source".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "model".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "pid".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    is_index: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "key".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "inputs".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "outputs".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "tag".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "start_time".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    ..Default::default()
+                },
+            ),
+            (
+                "end_time".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    ..Default::default()
+                },
+            ),
+            (
+                "chan_id".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "chan_pattern".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "create_time".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    ..Default::default()
+                },
+            ),
+            (
+                "update_time".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    ..Default::default()
+                },
+            ),
+            (
+                "status".to_string(),
+                DbColumn {
+                    db_type: DbType::Int8,
+                    is_index: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "retry_times".to_string(),
+                DbColumn {
+                    db_type: DbType::Int32,
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        Ok(map)
+    }
+}
+
+impl DbRow for Message {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn from_row(row: &DbRecord) -> Result<Message> {
+        Ok(Message {
+            id: row.get::<String>(0)?,
+            name: row.get::<String>(1)?,
+            tid: row.get::<String>(2)?,
+            state: row.get::<String>(3)?,
+            r#type: row.get::<String>(4)?,
+            source: row.get::<String>(5)?,
+            model: row.get::<String>(6)?,
+            pid: row.get::<String>(7)?,
+            key: row.get::<String>(8)?,
+            inputs: row.get::<String>(9)?,
+            outputs: row.get::<String>(10)?,
+            tag: row.get::<String>(11)?,
+            start_time: row.get::<i64>(12)?,
+            end_time: row.get::<i64>(13)?,
+            chan_id: row.get::<String>(14)?,
+            chan_pattern: row.get::<String>(15)?,
+            create_time: row.get::<i64>(16)?,
+            update_time: row.get::<i64>(17)?,
+            status: row.get::<i8>(18)?.into(),
+            retry_times: row.get::<i32>(19)?,
+        })
+    }
+
+    fn to_values(&self) -> Result<Vec<(String, DbValue)>> {
+        let ret = vec![
+            ("id".to_string(), DbValue::Text(self.id.clone())),
+            ("name".to_string(), DbValue::Text(self.name.clone())),
+            ("tid".to_string(), DbValue::Text(self.tid.clone())),
+            ("state".to_string(), DbValue::Text(self.state.clone())),
+            ("type".to_string(), DbValue::Text(self.r#type.clone())),
+            ("source".to_string(), DbValue::Text(self.source.clone())),
+            ("model".to_string(), DbValue::Text(self.model.clone())),
+            ("pid".to_string(), DbValue::Text(self.pid.clone())),
+            ("key".to_string(), DbValue::Text(self.key.clone())),
+            ("inputs".to_string(), DbValue::Text(self.inputs.clone())),
+            ("outputs".to_string(), DbValue::Text(self.outputs.clone())),
+            ("tag".to_string(), DbValue::Text(self.tag.clone())),
+            ("start_time".to_string(), DbValue::Int(self.start_time)),
+            ("end_time".to_string(), DbValue::Int(self.end_time)),
+            ("chan_id".to_string(), DbValue::Text(self.chan_id.clone())),
+            (
+                "chan_pattern".to_string(),
+                DbValue::Text(self.chan_pattern.clone()),
+            ),
+            ("create_time".to_string(), DbValue::Int(self.create_time)),
+            ("update_time".to_string(), DbValue::Int(self.update_time)),
+            ("status".to_string(), DbValue::Int(self.status as i64)),
+            (
+                "retry_times".to_string(),
+                DbValue::Int(self.retry_times.into()),
+            ),
+        ];
+
+        Ok(ret)
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{
+    store::{
+        db::sql::{DbColumn, DbRecord, DbRow, DbSchema, DbType, DbValue},
+        Model, ModelVersion,
+    },
+    Result,
+};
+
+impl DbSchema for Model {
+    fn schema() -> Result<Vec<(String, DbColumn)>> {
+        let map = vec![
+            (
+                "id".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    is_primary_key: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "name".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "ver".to_string(),
+                DbColumn {
+                    db_type: DbType::Int32,
+                    is_not_null: false,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "size".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    is_not_null: false,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "time".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    is_not_null: false,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "data".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: false,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        Ok(map)
+    }
+}
+
+impl DbRow for Model {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn from_row(row: &DbRecord) -> Result<Model> {
+        Ok(Model {
+            id: row.get::<String>(0)?,
+            name: row.get::<String>(1)?,
+            ver: row.get::<u32>(2)?,
+            size: row.get::<u32>(3)?,
+            time: row.get::<i64>(4)?,
+            data: row.get::<String>(5)?,
+        })
+    }
+
+    fn to_values(&self) -> Result<Vec<(String, DbValue)>> {
+        let ret = vec![
+            ("id".to_string(), DbValue::Text(self.id.clone())),
+            ("name".to_string(), DbValue::Text(self.name.clone())),
+            ("ver".to_string(), DbValue::Int(self.ver.into())),
+            ("size".to_string(), DbValue::Int(self.size.into())),
+            ("time".to_string(), DbValue::Int(self.time)),
+            ("data".to_string(), DbValue::Text(self.data.clone())),
+        ];
+
+        Ok(ret)
+    }
+}
+
+impl DbSchema for ModelVersion {
+    fn schema() -> Result<Vec<(String, DbColumn)>> {
+        let map = vec![
+            (
+                "id".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    is_primary_key: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "mid".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: false,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "ver".to_string(),
+                DbColumn {
+                    db_type: DbType::Int32,
+                    is_not_null: false,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "name".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: false,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "size".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    is_not_null: false,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "time".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    is_not_null: false,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "data".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: false,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        Ok(map)
+    }
+}
+
+impl DbRow for ModelVersion {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn from_row(row: &DbRecord) -> Result<ModelVersion> {
+        Ok(ModelVersion {
+            id: row.get::<String>(0)?,
+            mid: row.get::<String>(1)?,
+            ver: row.get::<u32>(2)?,
+            name: row.get::<String>(3)?,
+            size: row.get::<u32>(4)?,
+            time: row.get::<i64>(5)?,
+            data: row.get::<String>(6)?,
+        })
+    }
+
+    fn to_values(&self) -> Result<Vec<(String, DbValue)>> {
+        let ret = vec![
+            ("id".to_string(), DbValue::Text(self.id.clone())),
+            ("mid".to_string(), DbValue::Text(self.mid.clone())),
+            ("ver".to_string(), DbValue::Int(self.ver.into())),
+            ("name".to_string(), DbValue::Text(self.name.clone())),
+            ("size".to_string(), DbValue::Int(self.size.into())),
+            ("time".to_string(), DbValue::Int(self.time)),
+            ("data".to_string(), DbValue::Text(self.data.clone())),
+        ];
+
+        Ok(ret)
+    }
+}
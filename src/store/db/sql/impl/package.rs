@@ -0,0 +1,129 @@
+use crate::{
+    store::{
+        db::sql::{DbColumn, DbRecord, DbRow, DbSchema, DbType, DbValue},
+        Package,
+    },
+    Result,
+};
+impl DbSchema for Package {
+    fn schema() -> Result<Vec<(String, DbColumn)>> {
+        let map = vec![
+            (
+                "id".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    is_primary_key: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "name".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "size".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    is_not_null: true,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "file_data".to_string(),
+                DbColumn {
+                    db_type: DbType::Binary,
+                    is_not_null: true,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "create_time".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    is_not_null: true,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "update_time".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    is_not_null: true,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "timestamp".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    is_not_null: true,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "limits".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        Ok(map)
+    }
+}
+
+impl DbRow for Package {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn from_row(row: &DbRecord) -> Result<Package> {
+        Ok(Package {
+            id: row.get::<String>(0)?,
+            name: row.get::<String>(1)?,
+            size: row.get::<u32>(2)?,
+            file_data: row.get::<Vec<u8>>(3)?,
+            create_time: row.get::<i64>(4)?,
+            update_time: row.get::<i64>(5)?,
+            timestamp: row.get::<i64>(6)?,
+            limits: match row.get::<Option<String>>(7)? {
+                Some(v) => Some(serde_json::from_str(&v)?),
+                None => None,
+            },
+        })
+    }
+
+    fn to_values(&self) -> Result<Vec<(String, DbValue)>> {
+        let ret = vec![
+            ("id".to_string(), DbValue::Text(self.id.clone())),
+            ("name".to_string(), DbValue::Text(self.name.clone())),
+            ("size".to_string(), DbValue::Int(self.size.into())),
+            (
+                "file_data".to_string(),
+                DbValue::Binary(self.file_data.clone()),
+            ),
+            ("create_time".to_string(), DbValue::Int(self.create_time)),
+            ("update_time".to_string(), DbValue::Int(self.update_time)),
+            ("timestamp".to_string(), DbValue::Int(self.timestamp)),
+            (
+                "limits".to_string(),
+                match &self.limits {
+                    Some(v) => DbValue::Text(serde_json::to_string(v)?),
+                    None => DbValue::Null,
+                },
+            ),
+        ];
+
+        Ok(ret)
+    }
+}
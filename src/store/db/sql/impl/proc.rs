@@ -0,0 +1,147 @@
+use crate::{
+    store::{
+        db::sql::{DbColumn, DbRecord, DbRow, DbSchema, DbType, DbValue},
+        Proc,
+    },
+    Result,
+};
+
+impl DbSchema for Proc {
+    fn schema() -> Result<Vec<(String, DbColumn)>> {
+        let map = vec![
+            (
+                "id".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    is_primary_key: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "name".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "state".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    is_primary_key: false,
+                    is_index: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "mid".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    is_primary_key: false,
+                    is_index: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "start_time".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    is_not_null: true,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "end_time".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    is_not_null: true,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "timestamp".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    is_not_null: true,
+                    is_primary_key: false,
+                    ..Default::default()
+                },
+            ),
+            (
+                "model".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "env_local".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "err".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        Ok(map)
+    }
+}
+
+impl DbRow for Proc {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn from_row(row: &DbRecord) -> Result<Proc> {
+        Ok(Proc {
+            id: row.get::<String>(0)?,
+            name: row.get::<String>(1)?,
+            state: row.get::<String>(2)?,
+            mid: row.get::<String>(3)?,
+            start_time: row.get::<i64>(4)?,
+            end_time: row.get::<i64>(5)?,
+            timestamp: row.get::<i64>(6)?,
+            model: row.get::<String>(7)?,
+            env_local: row.get::<String>(8)?,
+            err: row.get::<Option<String>>(9)?,
+        })
+    }
+
+    fn to_values(&self) -> Result<Vec<(String, DbValue)>> {
+        let ret = vec![
+            ("id".to_string(), DbValue::Text(self.id.clone())),
+            ("name".to_string(), DbValue::Text(self.name.clone())),
+            ("state".to_string(), DbValue::Text(self.state.clone())),
+            ("mid".to_string(), DbValue::Text(self.mid.clone())),
+            ("start_time".to_string(), DbValue::Int(self.start_time)),
+            ("end_time".to_string(), DbValue::Int(self.end_time)),
+            ("timestamp".to_string(), DbValue::Int(self.timestamp)),
+            ("model".to_string(), DbValue::Text(self.model.clone())),
+            (
+                "env_local".to_string(),
+                DbValue::Text(self.env_local.clone()),
+            ),
+            (
+                "err".to_string(),
+                match &self.err {
+                    Some(v) => DbValue::Text(v.clone()),
+                    None => DbValue::Null,
+                },
+            ),
+        ];
+
+        Ok(ret)
+    }
+}
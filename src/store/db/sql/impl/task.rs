@@ -0,0 +1,194 @@
+use crate::{
+    store::{
+        db::sql::{DbColumn, DbRecord, DbRow, DbSchema, DbType, DbValue},
+        Task,
+    },
+    Result,
+};
+
+impl DbSchema for Task {
+    fn schema() -> Result<Vec<(String, DbColumn)>> {
+        let map = vec![
+            (
+                "id".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    is_primary_key: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "name".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "pid".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_index: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "tid".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "node_data".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "kind".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "prev".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "state".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "start_time".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    ..Default::default()
+                },
+            ),
+            (
+                "end_time".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    ..Default::default()
+                },
+            ),
+            (
+                "hooks".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "timestamp".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    ..Default::default()
+                },
+            ),
+            (
+                "data".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "err".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "wait_key".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_index: true,
+                    // the tasks saved by the older version have no wait key
+                    default: Some("''".to_string()),
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        Ok(map)
+    }
+}
+
+impl DbRow for Task {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn from_row(row: &DbRecord) -> Result<Task> {
+        Ok(Task {
+            id: row.get::<String>(0)?,
+            name: row.get::<String>(1)?,
+            pid: row.get::<String>(2)?,
+            tid: row.get::<String>(3)?,
+            node_data: row.get::<String>(4)?,
+            kind: row.get::<String>(5)?,
+            prev: row.get::<Option<String>>(6)?,
+            state: row.get::<String>(7)?,
+            start_time: row.get::<i64>(8)?,
+            end_time: row.get::<i64>(9)?,
+            hooks: row.get::<String>(10)?,
+            timestamp: row.get::<i64>(11)?,
+            data: row.get::<String>(12)?,
+            err: row.get::<Option<String>>(13)?,
+            wait_key: row.get::<String>(14)?,
+        })
+    }
+
+    fn to_values(&self) -> Result<Vec<(String, DbValue)>> {
+        let ret = vec![
+            ("id".to_string(), DbValue::Text(self.id.clone())),
+            ("name".to_string(), DbValue::Text(self.name.clone())),
+            ("pid".to_string(), DbValue::Text(self.pid.clone())),
+            ("tid".to_string(), DbValue::Text(self.tid.clone())),
+            (
+                "node_data".to_string(),
+                DbValue::Text(self.node_data.clone()),
+            ),
+            ("kind".to_string(), DbValue::Text(self.kind.clone())),
+            (
+                "prev".to_string(),
+                match &self.prev {
+                    Some(v) => DbValue::Text(v.clone()),
+                    None => DbValue::Null,
+                },
+            ),
+            ("state".to_string(), DbValue::Text(self.state.clone())),
+            ("start_time".to_string(), DbValue::Int(self.start_time)),
+            ("end_time".to_string(), DbValue::Int(self.end_time)),
+            ("hooks".to_string(), DbValue::Text(self.hooks.clone())),
+            ("timestamp".to_string(), DbValue::Int(self.timestamp)),
+            ("data".to_string(), DbValue::Text(self.data.clone())),
+            (
+                "err".to_string(),
+                match &self.err {
+                    Some(v) => DbValue::Text(v.clone()),
+                    None => DbValue::Null,
+                },
+            ),
+            ("wait_key".to_string(), DbValue::Text(self.wait_key.clone())),
+        ];
+
+        Ok(ret)
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{
+    store::{
+        db::sql::{DbColumn, DbRecord, DbRow, DbSchema, DbType, DbValue},
+        Trigger,
+    },
+    Result,
+};
+
+impl DbSchema for Trigger {
+    fn schema() -> Result<Vec<(String, DbColumn)>> {
+        let map = vec![
+            (
+                "id".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_not_null: true,
+                    is_primary_key: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "mid".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    is_index: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "data".to_string(),
+                DbColumn {
+                    db_type: DbType::Text,
+                    ..Default::default()
+                },
+            ),
+            (
+                "last_time".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    ..Default::default()
+                },
+            ),
+            (
+                "next_time".to_string(),
+                DbColumn {
+                    db_type: DbType::Int64,
+                    is_index: true,
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        Ok(map)
+    }
+}
+
+impl DbRow for Trigger {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn from_row(row: &DbRecord) -> Result<Trigger> {
+        Ok(Trigger {
+            id: row.get::<String>(0)?,
+            mid: row.get::<String>(1)?,
+            data: row.get::<String>(2)?,
+            last_time: row.get::<i64>(3)?,
+            next_time: row.get::<i64>(4)?,
+        })
+    }
+
+    fn to_values(&self) -> Result<Vec<(String, DbValue)>> {
+        let ret = vec![
+            ("id".to_string(), DbValue::Text(self.id.clone())),
+            ("mid".to_string(), DbValue::Text(self.mid.clone())),
+            ("data".to_string(), DbValue::Text(self.data.clone())),
+            ("last_time".to_string(), DbValue::Int(self.last_time)),
+            ("next_time".to_string(), DbValue::Int(self.next_time)),
+        ];
+
+        Ok(ret)
+    }
+}
@@ -0,0 +1,164 @@
+mod r#impl;
+mod query;
+
+pub use query::{filter, repeat_var};
+
+use crate::{ActError, Result};
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub enum DbType {
+    Boolean,
+    Double,
+    Decimal(u32, u32),
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Text,
+    Binary,
+}
+
+#[derive(Debug)]
+pub struct DbColumn {
+    pub db_type: DbType,
+    pub is_not_null: bool,
+    pub is_primary_key: bool,
+    pub is_unique: bool,
+    pub is_index: bool,
+    pub default: Option<String>,
+}
+
+impl Default for DbColumn {
+    fn default() -> Self {
+        Self {
+            db_type: DbType::Text,
+            is_not_null: false,
+            is_primary_key: false,
+            is_unique: false,
+            is_index: false,
+            default: None,
+        }
+    }
+}
+
+/// the backend independent column value
+/// each sql backend converts it from or to its own value type
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum DbValue {
+    Null,
+    Boolean(bool),
+    Int(i64),
+    Double(f64),
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+/// a row read from the database, the values are in the schema order
+#[derive(Debug, Clone)]
+pub struct DbRecord {
+    values: Vec<DbValue>,
+}
+
+impl DbRecord {
+    pub fn new(values: Vec<DbValue>) -> Self {
+        Self { values }
+    }
+
+    pub fn get<T: FromDbValue>(&self, index: usize) -> Result<T> {
+        let value = self
+            .values
+            .get(index)
+            .ok_or(ActError::Store(format!("invalid column index {index}")))?;
+        T::from_value(value)
+    }
+}
+
+pub trait FromDbValue: Sized {
+    fn from_value(value: &DbValue) -> Result<Self>;
+}
+
+fn value_err<T>(value: &DbValue, name: &str) -> Result<T> {
+    Err(ActError::Store(format!(
+        "cannot convert {value:?} to {name}"
+    )))
+}
+
+impl FromDbValue for String {
+    fn from_value(value: &DbValue) -> Result<Self> {
+        match value {
+            DbValue::Text(v) => Ok(v.clone()),
+            v => value_err(v, "String"),
+        }
+    }
+}
+
+impl FromDbValue for Vec<u8> {
+    fn from_value(value: &DbValue) -> Result<Self> {
+        match value {
+            DbValue::Binary(v) => Ok(v.clone()),
+            DbValue::Text(v) => Ok(v.as_bytes().to_vec()),
+            v => value_err(v, "Vec<u8>"),
+        }
+    }
+}
+
+impl FromDbValue for bool {
+    fn from_value(value: &DbValue) -> Result<Self> {
+        match value {
+            DbValue::Boolean(v) => Ok(*v),
+            DbValue::Int(v) => Ok(*v != 0),
+            v => value_err(v, "bool"),
+        }
+    }
+}
+
+impl FromDbValue for f64 {
+    fn from_value(value: &DbValue) -> Result<Self> {
+        match value {
+            DbValue::Double(v) => Ok(*v),
+            DbValue::Int(v) => Ok(*v as f64),
+            v => value_err(v, "f64"),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl FromDbValue for $t {
+                fn from_value(value: &DbValue) -> Result<Self> {
+                    match value {
+                        DbValue::Int(v) => <$t>::try_from(*v)
+                            .map_err(|err| ActError::Store(err.to_string())),
+                        v => value_err(v, stringify!($t)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i8, i16, i32, i64, u32);
+
+impl<T: FromDbValue> FromDbValue for Option<T> {
+    fn from_value(value: &DbValue) -> Result<Self> {
+        match value {
+            DbValue::Null => Ok(None),
+            v => T::from_value(v).map(Some),
+        }
+    }
+}
+
+pub trait DbSchema {
+    fn schema() -> Result<Vec<(String, DbColumn)>>;
+}
+
+pub trait DbRow {
+    fn id(&self) -> &str;
+    fn from_row(row: &DbRecord) -> Result<Self>
+    where
+        Self: Sized;
+    fn to_values(&self) -> Result<Vec<(String, DbValue)>>;
+}
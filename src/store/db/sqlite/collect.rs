@@ -0,0 +1,170 @@
+use super::{database::Database, to_record};
+use crate::{
+    store::{
+        db::sql::{filter, repeat_var, DbRow, DbSchema, DbValue},
+        map_db_err, DbSet, Query,
+    },
+    Result, ShareLock,
+};
+use rusqlite::{params, params_from_iter};
+use std::{fmt::Debug, marker::PhantomData};
+use tracing::debug;
+
+#[derive(Debug)]
+pub struct Collect<T> {
+    db: ShareLock<Database>,
+    name: String,
+    _t: PhantomData<T>,
+}
+
+impl<T> Collect<T>
+where
+    T: DbSchema,
+{
+    pub fn new(db: &ShareLock<Database>, name: &str) -> Self {
+        db.write().unwrap().init(name, &T::schema().unwrap());
+        Self {
+            db: db.clone(),
+            name: name.to_string(),
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T> DbSet for Collect<T>
+where
+    T: DbSchema + DbRow + Debug + Send + Sync + Clone,
+{
+    type Item = T;
+    fn exists(&self, id: &str) -> Result<bool> {
+        debug!("sqlite::{}.exists({})", self.name, id);
+        let db = self.db.read().unwrap();
+        let conn = db.conn()?;
+
+        let mut stmt = conn
+            .prepare(&format!("select count(id) from {} where id = ?", self.name))
+            .map_err(map_db_err)?;
+        let result = stmt
+            .query_row(params![id], |row| row.get::<usize, i64>(0))
+            .map_err(map_db_err)?;
+        Ok(result > 0)
+    }
+
+    fn find(&self, id: &str) -> Result<T> {
+        debug!("sqlite::{}.find({})", self.name, id);
+        let db = self.db.read().unwrap();
+        let conn = db.conn()?;
+
+        let schema = T::schema()?;
+        let keys: Vec<&str> = schema.iter().map(|(k, _)| k.as_str()).collect();
+        let sql = format!("select {} from {} where id = ?", keys.join(","), self.name);
+        let row = conn
+            .prepare(&sql)
+            .map_err(map_db_err)?
+            .query_row(params![id], |row| to_record(row, keys.len()))
+            .map_err(map_db_err)?;
+        T::from_row(&row)
+    }
+
+    fn query(&self, q: &Query) -> Result<Vec<T>> {
+        debug!("sqlite::{}.query({:?})", self.name, q);
+        let db = self.db.read().unwrap();
+        let conn = db.conn()?;
+        let filter = filter(q)?;
+        debug!("filter: {filter}");
+        let schema = T::schema()?;
+        let keys: Vec<&str> = schema.iter().map(|(k, _)| k.as_str()).collect();
+        let mut sql = format!(
+            "select {} from {} limit {} offset {}",
+            keys.join(","),
+            self.name,
+            q.limit(),
+            q.offset(),
+        );
+        if !filter.is_empty() {
+            sql = format!(
+                "select {} from {} where {} limit {} offset {}",
+                keys.join(","),
+                self.name,
+                filter,
+                q.limit(),
+                q.offset(),
+            );
+        }
+
+        let ret = conn
+            .prepare(&sql)
+            .map_err(map_db_err)?
+            .query_map([], |row| to_record(row, keys.len()))
+            .map_err(map_db_err)?
+            .map(|v| T::from_row(&v.map_err(map_db_err)?))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(ret)
+    }
+
+    fn create(&self, model: &T) -> Result<bool> {
+        debug!("sqlite::{}.create({})", self.name, model.id());
+        let db = self.db.write().unwrap();
+        let conn = db.conn()?;
+
+        let mut keys = Vec::new();
+        let mut values = Vec::new();
+        for (k, v) in model.to_values()? {
+            keys.push(k);
+            values.push(v);
+        }
+
+        let ret = conn
+            .execute(
+                &format!(
+                    "insert into {} ( {} ) values ( {} )",
+                    self.name,
+                    keys.join(","),
+                    repeat_var(values.len())
+                ),
+                params_from_iter(values),
+            )
+            .map_err(map_db_err)?;
+        Ok(ret > 0)
+    }
+    fn update(&self, model: &T) -> Result<bool> {
+        debug!("sqlite::{}.update({})", self.name, model.id());
+        let db = self.db.write().unwrap();
+        let conn = db.conn()?;
+
+        let mut keys = Vec::new();
+        let mut values = Vec::new();
+        for (k, v) in model.to_values()? {
+            if k == "id" {
+                continue;
+            }
+            keys.push(format!("{} = ?", k.as_str()));
+            values.push(v);
+        }
+        values.push(DbValue::Text(model.id().to_string()));
+
+        let ret = conn
+            .execute(
+                &format!("update {} set {} where id = ?", self.name, keys.join(",")),
+                params_from_iter(values),
+            )
+            .map_err(map_db_err)?;
+
+        Ok(ret > 0)
+    }
+    fn delete(&self, id: &str) -> Result<bool> {
+        debug!("sqlite::{}.delete({})", self.name, id);
+        let db = self.db.write().unwrap();
+        let conn = db.conn()?;
+
+        let ret = conn
+            .execute(
+                &format!("delete from {} where id = ?", self.name),
+                params![id],
+            )
+            .map_err(map_db_err)?;
+
+        Ok(ret > 0)
+    }
+}
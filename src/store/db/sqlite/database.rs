@@ -0,0 +1,150 @@
+use crate::{
+    store::{
+        db::sql::{DbColumn, DbType},
+        map_db_err,
+    },
+    utils::consts,
+    Result,
+};
+use r2d2::PooledConnection;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::{fs, path::Path};
+use tracing::debug;
+
+pub struct Database {
+    path: String,
+    pool: r2d2::Pool<SqliteConnectionManager>,
+}
+
+impl std::fmt::Debug for Database {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Database").field("db", &self.path).finish()
+    }
+}
+
+impl Database {
+    pub fn new(path: &str, name: &str) -> Self {
+        let pool = if name == consts::MEMORY_DB {
+            // every memory connection opens a new database
+            // so the pool can only keep one connection
+            let manager = SqliteConnectionManager::memory();
+            r2d2::Pool::builder().max_size(1).build(manager).unwrap()
+        } else {
+            fs::create_dir_all(path).unwrap();
+            let manager =
+                SqliteConnectionManager::file(Path::new(path).join(name)).with_init(|conn| {
+                    conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA busy_timeout = 5000;")
+                });
+            r2d2::Pool::new(manager).unwrap()
+        };
+        Self {
+            pool,
+            path: path.to_string(),
+        }
+    }
+
+    pub fn pool(&self) -> &r2d2::Pool<SqliteConnectionManager> {
+        &self.pool
+    }
+
+    /// get a connection from the pool
+    pub fn conn(&self) -> Result<PooledConnection<SqliteConnectionManager>> {
+        self.pool.get().map_err(map_db_err)
+    }
+
+    pub fn init(&mut self, name: &str, schema: &[(String, DbColumn)]) {
+        let mut conn = self.pool().get().unwrap();
+        let mut sql = String::new();
+        sql.push_str(&format!("create table IF NOT EXISTS {} ", name));
+        sql.push('(');
+
+        let len = schema.len();
+        let mut idx_sqls = Vec::new();
+        for (index, (key, col)) in schema.iter().enumerate() {
+            sql.push_str(&format!("{key} {}", column_type(col)));
+
+            if col.is_not_null {
+                sql.push_str(" NOT NULL ");
+            }
+
+            if col.is_primary_key {
+                sql.push_str(" PRIMARY KEY ");
+            }
+
+            if col.is_unique {
+                sql.push_str(" UNIQUE ");
+            }
+
+            if let Some(default) = &col.default {
+                sql.push_str(" DEFAULT ");
+                sql.push_str(default);
+            }
+
+            if index < len - 1 {
+                sql.push(',');
+            }
+
+            if col.is_index {
+                idx_sqls.push(format!(
+                    "create {} index IF NOT EXISTS idx_{}_{} on {} ({})",
+                    if col.is_unique { "UNIQUE" } else { "" },
+                    name,
+                    key,
+                    name,
+                    key
+                ));
+            }
+        }
+        sql.push_str(");");
+        debug!("sql={}", sql);
+        let tr = conn.transaction().unwrap();
+        tr.execute(&sql, []).unwrap();
+
+        // add the new columns to the table created by the older version
+        let columns = tr
+            .prepare(&format!("PRAGMA table_info({name})"))
+            .unwrap()
+            .query_map([], |row| row.get::<usize, String>(1))
+            .unwrap()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        for (key, col) in schema.iter() {
+            if columns.contains(key) {
+                continue;
+            }
+            let mut sql = format!("alter table {name} add column {key} {}", column_type(col));
+            if let Some(default) = &col.default {
+                if col.is_not_null {
+                    sql.push_str(" NOT NULL");
+                }
+                sql.push_str(" DEFAULT ");
+                sql.push_str(default);
+            }
+            debug!("sql={}", sql);
+            tr.execute(&sql, []).unwrap();
+        }
+
+        for idx_sql in idx_sqls {
+            tr.execute(&idx_sql, []).unwrap();
+        }
+
+        tr.commit().unwrap();
+    }
+
+    pub fn close(&self) {
+        if let Ok(conn) = self.pool.get() {
+            drop(conn)
+        }
+    }
+}
+
+fn column_type(col: &DbColumn) -> String {
+    match col.db_type {
+        DbType::Boolean => "BOOLEAN".to_string(),
+        DbType::Double => "REAL".to_string(),
+        DbType::Decimal(width, scale) => format!("DECIMAL({width},{scale})"),
+        DbType::Int8 | DbType::Int16 | DbType::Int32 | DbType::Int64 => "INTEGER".to_string(),
+        DbType::Text => "TEXT".to_string(),
+        DbType::Binary => "BLOB".to_string(),
+    }
+}
@@ -0,0 +1,41 @@
+mod collect;
+mod database;
+mod store;
+
+use super::sql::{DbRecord, DbValue};
+use rusqlite::{
+    types::{ToSqlOutput, Value, ValueRef},
+    Row, ToSql,
+};
+
+impl ToSql for DbValue {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let value = match self {
+            DbValue::Null => Value::Null,
+            DbValue::Boolean(v) => Value::Integer((*v).into()),
+            DbValue::Int(v) => Value::Integer(*v),
+            DbValue::Double(v) => Value::Real(*v),
+            DbValue::Text(v) => Value::Text(v.clone()),
+            DbValue::Binary(v) => Value::Blob(v.clone()),
+        };
+        Ok(ToSqlOutput::Owned(value))
+    }
+}
+
+fn to_record(row: &Row<'_>, len: usize) -> rusqlite::Result<DbRecord> {
+    let mut values = Vec::with_capacity(len);
+    for index in 0..len {
+        let value = match row.get_ref(index)? {
+            ValueRef::Null => DbValue::Null,
+            ValueRef::Integer(v) => DbValue::Int(v),
+            ValueRef::Real(v) => DbValue::Double(v),
+            ValueRef::Text(v) => DbValue::Text(String::from_utf8_lossy(v).to_string()),
+            ValueRef::Blob(v) => DbValue::Binary(v.to_vec()),
+        };
+        values.push(value);
+    }
+
+    Ok(DbRecord::new(values))
+}
+
+pub use store::SqliteStore;
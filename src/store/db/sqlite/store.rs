@@ -0,0 +1,91 @@
+use super::{collect::Collect, database::Database};
+use crate::store::{data::*, DbSet, StoreAdapter};
+use std::sync::{Arc, RwLock};
+
+#[derive(Debug, Clone)]
+pub struct SqliteStore {
+    db: Arc<RwLock<Database>>,
+    models: Arc<Collect<Model>>,
+    procs: Arc<Collect<Proc>>,
+    tasks: Arc<Collect<Task>>,
+    packages: Arc<Collect<Package>>,
+    messages: Arc<Collect<Message>>,
+    proc_histories: Arc<Collect<Proc>>,
+    task_histories: Arc<Collect<Task>>,
+    model_versions: Arc<Collect<ModelVersion>>,
+    triggers: Arc<Collect<Trigger>>,
+}
+
+impl SqliteStore {
+    pub fn new(path: &str, name: &str) -> Self {
+        let db = Arc::new(RwLock::new(Database::new(path, name)));
+        let models = Collect::new(&db, "models");
+        let procs = Collect::new(&db, "procs");
+        let tasks = Collect::new(&db, "tasks");
+        let packages = Collect::new(&db, "packages");
+        let messages = Collect::new(&db, "messages");
+        let proc_histories = Collect::new(&db, "proc_histories");
+        let task_histories = Collect::new(&db, "task_histories");
+        let model_versions = Collect::new(&db, "model_versions");
+        let triggers = Collect::new(&db, "triggers");
+        let store = Self {
+            db: db.clone(),
+            models: Arc::new(models),
+            procs: Arc::new(procs),
+            tasks: Arc::new(tasks),
+            packages: Arc::new(packages),
+            messages: Arc::new(messages),
+            proc_histories: Arc::new(proc_histories),
+            task_histories: Arc::new(task_histories),
+            model_versions: Arc::new(model_versions),
+            triggers: Arc::new(triggers),
+        };
+
+        store.init();
+
+        store
+    }
+}
+
+impl StoreAdapter for SqliteStore {
+    fn init(&self) {}
+    fn close(&self) {
+        self.db.write().unwrap().close();
+    }
+
+    fn models(&self) -> Arc<dyn DbSet<Item = Model>> {
+        self.models.clone()
+    }
+
+    fn procs(&self) -> Arc<dyn DbSet<Item = Proc>> {
+        self.procs.clone()
+    }
+
+    fn tasks(&self) -> Arc<dyn DbSet<Item = Task>> {
+        self.tasks.clone()
+    }
+
+    fn packages(&self) -> Arc<dyn DbSet<Item = Package>> {
+        self.packages.clone()
+    }
+
+    fn messages(&self) -> Arc<dyn DbSet<Item = Message>> {
+        self.messages.clone()
+    }
+
+    fn proc_histories(&self) -> Arc<dyn DbSet<Item = Proc>> {
+        self.proc_histories.clone()
+    }
+
+    fn task_histories(&self) -> Arc<dyn DbSet<Item = Task>> {
+        self.task_histories.clone()
+    }
+
+    fn model_versions(&self) -> Arc<dyn DbSet<Item = ModelVersion>> {
+        self.model_versions.clone()
+    }
+
+    fn triggers(&self) -> Arc<dyn DbSet<Item = Trigger>> {
+        self.triggers.clone()
+    }
+}
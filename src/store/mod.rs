@@ -1,5 +1,5 @@
 pub mod data;
-mod db;
+pub(crate) mod db;
 mod query;
 mod store;
 
@@ -12,7 +12,7 @@ pub use query::*;
 pub use store::{Store, StoreKind};
 
 use crate::{ActError, Result};
-use std::{error::Error, sync::Arc};
+use std::{error::Error, marker::PhantomData, sync::Arc};
 
 fn map_db_err(err: impl Error) -> ActError {
     ActError::Store(err.to_string())
@@ -57,6 +57,12 @@ pub trait DbSet: Send + Sync {
 ///     fn close(&self) {}
 /// }
 /// ```
+///
+/// the `proc_histories` and `task_histories` are optional, which are only used by the
+/// [`crate::HistoryMode::Archive`] and return the unsupported error by default
+///
+/// the `model_versions` and `triggers` are optional too, they are empty by default,
+/// so the deployed versions are not kept and the model triggers are not scheduled
 pub trait StoreAdapter: Send + Sync {
     fn init(&self);
 
@@ -65,5 +71,99 @@ pub trait StoreAdapter: Send + Sync {
     fn tasks(&self) -> Arc<dyn DbSet<Item = Task>>;
     fn packages(&self) -> Arc<dyn DbSet<Item = Package>>;
     fn messages(&self) -> Arc<dyn DbSet<Item = Message>>;
+    fn proc_histories(&self) -> Arc<dyn DbSet<Item = Proc>> {
+        Arc::new(Unsupported::new("proc_histories"))
+    }
+    fn task_histories(&self) -> Arc<dyn DbSet<Item = Task>> {
+        Arc::new(Unsupported::new("task_histories"))
+    }
+    fn model_versions(&self) -> Arc<dyn DbSet<Item = ModelVersion>> {
+        Arc::new(Empty::new("model_versions"))
+    }
+    fn triggers(&self) -> Arc<dyn DbSet<Item = Trigger>> {
+        Arc::new(Empty::new("triggers"))
+    }
     fn close(&self);
 }
+
+/// The db set for the optional sets which are not implemented by the store adapter
+struct Unsupported<T> {
+    name: &'static str,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T> Unsupported<T> {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _item: PhantomData,
+        }
+    }
+
+    fn err(&self) -> ActError {
+        ActError::Store(format!("the '{}' is unsupported by the store", self.name))
+    }
+}
+
+impl<T> DbSet for Unsupported<T> {
+    type Item = T;
+    fn exists(&self, _id: &str) -> Result<bool> {
+        Err(self.err())
+    }
+    fn find(&self, _id: &str) -> Result<Self::Item> {
+        Err(self.err())
+    }
+    fn query(&self, _query: &Query) -> Result<Vec<Self::Item>> {
+        Err(self.err())
+    }
+    fn create(&self, _data: &Self::Item) -> Result<bool> {
+        Err(self.err())
+    }
+    fn update(&self, _data: &Self::Item) -> Result<bool> {
+        Err(self.err())
+    }
+    fn delete(&self, _id: &str) -> Result<bool> {
+        Err(self.err())
+    }
+}
+
+/// The db set for the optional sets which are ignored if not implemented by the store adapter
+/// it never keeps the data, so the query is always empty and the changes are not applied
+struct Empty<T> {
+    name: &'static str,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T> Empty<T> {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _item: PhantomData,
+        }
+    }
+}
+
+impl<T> DbSet for Empty<T> {
+    type Item = T;
+    fn exists(&self, _id: &str) -> Result<bool> {
+        Ok(false)
+    }
+    fn find(&self, id: &str) -> Result<Self::Item> {
+        Err(ActError::Store(format!(
+            "cannot find '{id}' in '{}', which is unsupported by the store",
+            self.name
+        )))
+    }
+    fn query(&self, _query: &Query) -> Result<Vec<Self::Item>> {
+        Ok(Vec::new())
+    }
+    fn create(&self, _data: &Self::Item) -> Result<bool> {
+        Ok(false)
+    }
+    fn update(&self, _data: &Self::Item) -> Result<bool> {
+        Ok(false)
+    }
+    fn delete(&self, _id: &str) -> Result<bool> {
+        Ok(false)
+    }
+}
@@ -152,18 +152,16 @@ impl Query {
     }
 
     pub fn calc(&self) -> HashSet<Box<[u8]>> {
-        let mut result = HashSet::new();
-        for cond in self.conds.iter() {
-            if result.len() == 0 {
-                result = cond.result.clone();
-            } else {
-                result = result
-                    .intersection(&cond.result)
-                    .cloned()
-                    .collect::<HashSet<_>>()
-            }
-        }
-        result
+        let mut conds = self.conds.iter();
+        let Some(first) = conds.next() else {
+            return HashSet::new();
+        };
+        conds.fold(first.result.clone(), |result, cond| {
+            result
+                .intersection(&cond.result)
+                .cloned()
+                .collect::<HashSet<_>>()
+        })
     }
 
     pub fn push(mut self, cond: Cond) -> Self {
@@ -1,12 +1,16 @@
 use crate::{
-    store::{Message, Model, Package, Proc, StoreAdapter, Task},
+    store::{
+        Cond, Expr, Message, Model, ModelVersion, Package, Proc, Query, StoreAdapter, Task, Trigger,
+    },
     utils, ActError, Result, ShareLock, Workflow,
 };
 use std::sync::{Arc, Mutex, RwLock};
-use tracing::trace;
+use tracing::{trace, warn};
 
 #[cfg(feature = "store")]
 use crate::store::db::LocalStore;
+#[cfg(feature = "sqlite")]
+use crate::store::db::SqliteStore;
 
 use super::db::MemStore;
 
@@ -47,6 +51,22 @@ impl StoreAdapter for Store {
         self.base.read().unwrap().messages()
     }
 
+    fn proc_histories(&self) -> Arc<dyn super::DbSet<Item = Proc>> {
+        self.base.read().unwrap().proc_histories()
+    }
+
+    fn task_histories(&self) -> Arc<dyn super::DbSet<Item = Task>> {
+        self.base.read().unwrap().task_histories()
+    }
+
+    fn model_versions(&self) -> Arc<dyn super::DbSet<Item = ModelVersion>> {
+        self.base.read().unwrap().model_versions()
+    }
+
+    fn triggers(&self) -> Arc<dyn super::DbSet<Item = Trigger>> {
+        self.base.read().unwrap().triggers()
+    }
+
     fn close(&self) {
         self.base.read().unwrap().close()
     }
@@ -77,9 +97,18 @@ impl Store {
         }
     }
 
+    #[cfg(feature = "sqlite")]
+    pub fn sqlite(path: &str, name: &str) -> Self {
+        let store = Arc::new(SqliteStore::new(path, name));
+        Self {
+            kind: Arc::new(Mutex::new(StoreKind::Local)),
+            base: Arc::new(RwLock::new(store)),
+        }
+    }
+
     #[cfg(test)]
     pub fn reset(&self) {
-        #[cfg(not(feature = "store"))]
+        #[cfg(not(any(feature = "store", feature = "sqlite")))]
         {
             let store = Self::default();
             *self.kind.lock().unwrap() = store.kind();
@@ -87,7 +116,13 @@ impl Store {
         }
         #[cfg(feature = "store")]
         {
-            let store = Self::local("data", "acts.db");
+            let store = Self::local("data", utils::consts::MEMORY_DB);
+            *self.kind.lock().unwrap() = store.kind();
+            *self.base.write().unwrap() = store.base();
+        }
+        #[cfg(all(feature = "sqlite", not(feature = "store")))]
+        {
+            let store = Self::sqlite("data", utils::consts::MEMORY_DB);
             *self.kind.lock().unwrap() = store.kind();
             *self.base.write().unwrap() = store.base();
         }
@@ -128,32 +163,163 @@ impl Store {
             return Err(ActError::Action("missing id in model".into()));
         }
         let models = self.base().models();
-        match models.find(&model.id) {
-            Ok(m) => {
-                let text = serde_yaml::to_string(model).unwrap();
-                let data = Model {
-                    id: model.id.clone(),
-                    name: model.name.clone(),
-                    data: text.clone(),
-                    ver: m.ver + 1,
-                    size: text.len() as u32,
-                    time: utils::time::time_millis(),
-                };
-                models.update(&data)
-            }
-            Err(_) => {
-                let text = serde_yaml::to_string(model).unwrap();
-                let data = Model {
-                    id: model.id.clone(),
-                    name: model.name.clone(),
-                    data: text.clone(),
-                    ver: 1,
-                    size: text.len() as u32,
-                    time: utils::time::time_millis(),
-                };
-                models.create(&data)
+        let mut ver = self
+            .query_versions(&model.id)?
+            .iter()
+            .map(|v| v.ver)
+            .max()
+            .unwrap_or(0);
+        if let Ok(m) = models.find(&model.id) {
+            ver = ver.max(m.ver);
+        }
+
+        let text = serde_yaml::to_string(model).unwrap();
+        let data = Model {
+            id: model.id.clone(),
+            name: model.name.clone(),
+            data: text.clone(),
+            ver: ver + 1,
+            size: text.len() as u32,
+            time: utils::time::time_millis(),
+        };
+        self.base().model_versions().create(&ModelVersion {
+            id: version_id(&data.id, data.ver),
+            mid: data.id.clone(),
+            ver: data.ver,
+            name: data.name.clone(),
+            size: data.size,
+            time: data.time,
+            data: data.data.clone(),
+        })?;
+        let ret = match models.exists(&model.id)? {
+            true => models.update(&data),
+            false => models.create(&data),
+        }?;
+        self.sync_triggers(model)?;
+        Ok(ret)
+    }
+
+    /// find the model by the deployed version
+    pub fn model_version(&self, id: &str, ver: u32) -> Result<Model> {
+        trace!("store::model_version({}, {})", id, ver);
+        let model = self
+            .base()
+            .model_versions()
+            .find(&version_id(id, ver))
+            .map_err(|_| ActError::Store(format!("cannot find model '{id}' by ver '{ver}'")))?;
+        Ok(model.into())
+    }
+
+    /// get the workflow of the deployed version, or the current model if the ver is None
+    pub fn workflow(&self, id: &str, ver: Option<u32>) -> Result<Workflow> {
+        trace!("store::workflow({}, {:?})", id, ver);
+        let model = match ver {
+            Some(ver) => self.model_version(id, ver)?,
+            None => self.base().models().find(id)?,
+        };
+        let mut workflow = serde_yaml::from_str::<Workflow>(&model.data)
+            .map_err(|err| ActError::Convert(err.to_string()))?;
+        workflow.set_ver(model.ver);
+        Ok(workflow)
+    }
+
+    /// get all of the deployed versions of the model
+    pub fn model_versions(&self, id: &str) -> Result<Vec<Model>> {
+        trace!("store::model_versions({})", id);
+        let mut versions = self.query_versions(id)?;
+        versions.sort_by_key(|v| v.ver);
+        Ok(versions.into_iter().map(|v| v.into()).collect())
+    }
+
+    /// set the deployed version as the current model
+    pub fn rollback(&self, id: &str, ver: u32) -> Result<bool> {
+        trace!("store::rollback({}, {})", id, ver);
+        let mut model = self.model_version(id, ver)?;
+        model.time = utils::time::time_millis();
+        let models = self.base().models();
+        let ret = match models.exists(id)? {
+            true => models.update(&model),
+            false => models.create(&model),
+        }?;
+        self.sync_triggers(&Workflow::from_yml(&model.data)?)?;
+        Ok(ret)
+    }
+
+    /// remove the model with all of the deployed versions
+    pub fn remove_model(&self, id: &str) -> Result<bool> {
+        trace!("store::remove_model({})", id);
+        let versions = self.base().model_versions();
+        for v in self.query_versions(id)? {
+            versions.delete(&v.id)?;
+        }
+        for trigger in self.model_triggers(id)? {
+            self.base().triggers().delete(&trigger.id)?;
+        }
+        self.base().models().delete(id)
+    }
+
+    fn query_versions(&self, mid: &str) -> Result<Vec<ModelVersion>> {
+        let query = Query::new().push(Cond::and().push(Expr::eq("mid", mid)));
+        self.base().model_versions().query(&query)
+    }
+
+    /// get the cron triggers of the model
+    pub fn model_triggers(&self, mid: &str) -> Result<Vec<Trigger>> {
+        trace!("store::model_triggers({})", mid);
+        let query = Query::new().push(Cond::and().push(Expr::eq("mid", mid)));
+        self.base().triggers().query(&query)
+    }
+
+    /// keep the stored triggers the same as the model triggers
+    /// the last fire time is kept if the trigger is not changed
+    pub fn sync_triggers(&self, model: &Workflow) -> Result<()> {
+        trace!("store::sync_triggers({})", model.id);
+        let triggers = self.base().triggers();
+        let mut olds = self.model_triggers(&model.id)?;
+        let now = utils::time::time_millis();
+        for (index, trigger) in model.triggers.iter().enumerate() {
+            let key = match trigger.id.is_empty() {
+                true => index.to_string(),
+                false => trigger.id.clone(),
+            };
+            let id = format!("{}:{key}", model.id);
+            let data = serde_json::to_string(trigger).map_err(ActError::from)?;
+            match olds.iter().position(|t| t.id == id) {
+                Some(pos) => {
+                    let old = olds.remove(pos);
+                    if old.data != data {
+                        triggers.update(&Trigger {
+                            data,
+                            last_time: now,
+                            next_time: trigger.next_time(now)?,
+                            ..old
+                        })?;
+                    }
+                }
+                None => {
+                    let created = triggers.create(&Trigger {
+                        id,
+                        mid: model.id.clone(),
+                        data,
+                        last_time: now,
+                        next_time: trigger.next_time(now)?,
+                    })?;
+                    if !created {
+                        warn!(
+                            "store::sync_triggers: the trigger of model '{}' is not scheduled by the store",
+                            model.id
+                        );
+                    }
+                }
             }
         }
+
+        // remove the triggers which are not in the model
+        for old in olds {
+            triggers.delete(&old.id)?;
+        }
+
+        Ok(())
     }
 
     pub(crate) fn base(&self) -> Arc<dyn StoreAdapter> {
@@ -164,3 +330,7 @@ impl Store {
         self.kind.lock().unwrap().clone()
     }
 }
+
+fn version_id(id: &str, ver: u32) -> String {
+    format!("{id}:{ver}")
+}
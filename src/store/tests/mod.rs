@@ -1,4 +1,9 @@
+#[macro_use]
+mod suite;
+
 #[cfg(feature = "store")]
 mod local;
 mod mem;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 mod store;
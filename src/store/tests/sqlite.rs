@@ -0,0 +1,131 @@
+use crate::store::db::SqliteStore;
+use std::{fs, path::Path, thread};
+use tokio::sync::OnceCell;
+
+static STORE: OnceCell<SqliteStore> = OnceCell::const_new();
+async fn init() -> SqliteStore {
+    let s = SqliteStore::new(&utils::test_data_dir(), "test_sqlite.db");
+    s
+}
+
+async fn store() -> &'static SqliteStore {
+    STORE.get_or_init(init).await
+}
+
+store_suite!();
+
+#[tokio::test]
+async fn sqlite_file_reopen() {
+    let path = &utils::test_data_dir();
+    let model = Model {
+        id: utils::longid(),
+        name: "name".to_string(),
+        ver: 1,
+        size: 0,
+        time: 0,
+        data: "".to_string(),
+    };
+    {
+        let store = SqliteStore::new(path, "test_reopen.db");
+        store.models().create(&model).unwrap();
+        store.close();
+    }
+
+    let store = SqliteStore::new(path, "test_reopen.db");
+    assert_eq!(store.models().find(&model.id).unwrap().id, model.id);
+}
+
+#[tokio::test]
+async fn sqlite_memory() {
+    let path = &utils::test_data_dir();
+    let store = SqliteStore::new(path, utils::consts::MEMORY_DB);
+    let model = Model {
+        id: utils::longid(),
+        name: "name".to_string(),
+        ver: 1,
+        size: 0,
+        time: 0,
+        data: "".to_string(),
+    };
+    store.models().create(&model).unwrap();
+    assert_eq!(store.models().find(&model.id).unwrap().id, model.id);
+    assert!(!Path::new(path).exists());
+}
+
+#[tokio::test]
+async fn sqlite_concurrent_connections() {
+    let store = store().await;
+    let handles = (0..8)
+        .map(|_| {
+            let procs = store.procs();
+            thread::spawn(move || {
+                let pid = utils::longid();
+                let proc = Proc {
+                    id: pid.clone(),
+                    name: "name".to_string(),
+                    mid: "m1".to_string(),
+                    state: TaskState::None.into(),
+                    start_time: 0,
+                    end_time: 0,
+                    timestamp: 0,
+                    model: "".to_string(),
+                    env_local: "{}".to_string(),
+                    err: None,
+                };
+                procs.create(&proc).unwrap();
+                procs.find(&pid).unwrap().id == pid
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        assert!(handle.join().unwrap());
+    }
+}
+
+#[tokio::test]
+async fn sqlite_migrate_tasks_wait_key() {
+    let path = utils::test_data_dir();
+    fs::create_dir_all(&path).unwrap();
+    let conn = rusqlite::Connection::open(Path::new(&path).join("test_old.db")).unwrap();
+    // the tasks table before the wait_key column is added
+    conn.execute_batch(
+        "create table tasks (id TEXT NOT NULL PRIMARY KEY, name TEXT, pid TEXT NOT NULL, tid TEXT NOT NULL, node_data TEXT, kind TEXT, prev TEXT, state TEXT, start_time INTEGER, end_time INTEGER, hooks TEXT, timestamp INTEGER, data TEXT, err TEXT);
+        insert into tasks values ('p1:t1', 'name', 'p1', 't1', '{}', 'act', NULL, 'interrupted', 0, 0, '{}', 0, '{}', NULL);
+        create index idx_tasks_pid on tasks (pid);",
+    )
+    .unwrap();
+    drop(conn);
+
+    let store = SqliteStore::new(&path, "test_old.db");
+    let task = store.tasks().find("p1:t1").unwrap();
+    assert_eq!(task.wait_key, "");
+
+    let task = Task {
+        id: "p1:t2".to_string(),
+        tid: "t2".to_string(),
+        wait_key: "k1".to_string(),
+        ..task
+    };
+    store.tasks().create(&task).unwrap();
+    let q = Query::new().push(Cond::and().push(Expr::eq("wait_key", "k1")));
+    assert_eq!(store.tasks().query(&q).unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn sqlite_migrate_packages_limits() {
+    let path = utils::test_data_dir();
+    fs::create_dir_all(&path).unwrap();
+    let conn = rusqlite::Connection::open(Path::new(&path).join("test_old_packages.db")).unwrap();
+    // the packages table before the limits column is added
+    conn.execute_batch(
+        "create table packages (id TEXT NOT NULL PRIMARY KEY, name TEXT, size INTEGER NOT NULL, file_data BLOB NOT NULL, create_time INTEGER NOT NULL, update_time INTEGER NOT NULL, timestamp INTEGER NOT NULL);
+        insert into packages values ('pack1', 'name', 0, '', 0, 0, 0);",
+    )
+    .unwrap();
+    drop(conn);
+
+    let store = SqliteStore::new(&path, "test_old_packages.db");
+    let package = store.packages().find("pack1").unwrap();
+    assert!(package.limits.is_none());
+}
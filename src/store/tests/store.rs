@@ -12,10 +12,15 @@ static STORE: OnceCell<Arc<Store>> = OnceCell::const_new();
 async fn init() -> Arc<Store> {
     #[cfg(feature = "store")]
     {
-        return Arc::new(Store::local("test_data", "test.db"));
+        return Arc::new(Store::local(&utils::test_data_dir(), "test.db"));
     }
 
-    #[cfg(not(feature = "store"))]
+    #[cfg(all(feature = "sqlite", not(feature = "store")))]
+    {
+        return Arc::new(Store::sqlite(&utils::test_data_dir(), "test.db"));
+    }
+
+    #[cfg(not(any(feature = "store", feature = "sqlite")))]
     Store::default()
 }
 
@@ -26,9 +31,9 @@ async fn store() -> &'static Arc<Store> {
 #[tokio::test]
 async fn store_kind() {
     let store = store().await;
-    #[cfg(feature = "store")]
+    #[cfg(any(feature = "store", feature = "sqlite"))]
     assert_eq!(store.kind(), StoreKind::Local);
-    #[cfg(not(feature = "store"))]
+    #[cfg(not(any(feature = "store", feature = "sqlite")))]
     assert_eq!(store.kind(), StoreKind::Memory);
 }
 
@@ -116,6 +121,141 @@ async fn store_model_deploy_ver_incr() {
     assert_eq!(model.ver, 2);
 }
 
+#[tokio::test]
+async fn store_model_deploy_keep_versions() {
+    let store = store().await;
+    let mut workflow = create_workflow();
+    workflow.id = utils::longid();
+    store.deploy(&workflow).unwrap();
+    store.deploy(&workflow).unwrap();
+
+    let versions = store.model_versions(&workflow.id).unwrap();
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[0].id, workflow.id);
+    assert_eq!(versions[1].ver, 2);
+    assert!(store.model_version(&workflow.id, 1).is_ok());
+}
+
+#[tokio::test]
+async fn store_model_versions_by_mid() {
+    let store = store().await;
+    let mut workflow = create_workflow();
+    workflow.id = utils::longid();
+    store.deploy(&workflow).unwrap();
+    store.deploy(&workflow).unwrap();
+
+    let mut other = workflow.clone();
+    other.id = format!("{}:1", workflow.id);
+    store.deploy(&other).unwrap();
+
+    let versions = store.model_versions(&workflow.id).unwrap();
+    assert_eq!(versions.iter().map(|v| v.ver).collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(store.model_versions(&other.id).unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn store_model_rollback() {
+    let store = store().await;
+    let mut workflow = create_workflow();
+    workflow.id = utils::longid();
+    store.deploy(&workflow).unwrap();
+    store.deploy(&workflow).unwrap();
+
+    store.rollback(&workflow.id, 1).unwrap();
+    let model = store.models().find(&workflow.id).unwrap();
+    assert_eq!(model.ver, 1);
+
+    store.deploy(&workflow).unwrap();
+    let model = store.models().find(&workflow.id).unwrap();
+    assert_eq!(model.ver, 3);
+}
+
+#[tokio::test]
+async fn store_model_remove_versions() {
+    let store = store().await;
+    let mut workflow = create_workflow();
+    workflow.id = utils::longid();
+    store.deploy(&workflow).unwrap();
+    store.deploy(&workflow).unwrap();
+
+    store.remove_model(&workflow.id).unwrap();
+    assert!(!store.models().exists(&workflow.id).unwrap());
+    assert_eq!(store.model_versions(&workflow.id).unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn store_model_deploy_triggers() {
+    let store = store().await;
+    let mut workflow = create_workflow()
+        .with_trigger(|t| t.with_id("t1").with_cron("0 9 * * *"))
+        .with_trigger(|t| t.with_cron("0 10 * * *"));
+    workflow.id = utils::longid();
+    store.deploy(&workflow).unwrap();
+
+    let mut triggers = store.model_triggers(&workflow.id).unwrap();
+    triggers.sort_by(|a, b| a.id.cmp(&b.id));
+    assert_eq!(triggers.len(), 2);
+    assert_eq!(triggers[0].id, format!("{}:1", workflow.id));
+    assert_eq!(triggers[1].id, format!("{}:t1", workflow.id));
+    assert!(triggers[1].next_time > triggers[1].last_time);
+}
+
+#[tokio::test]
+async fn store_model_deploy_triggers_sync() {
+    let store = store().await;
+    let mut workflow = create_workflow()
+        .with_trigger(|t| t.with_id("t1").with_cron("0 9 * * *"))
+        .with_trigger(|t| t.with_id("t2").with_cron("0 10 * * *"));
+    workflow.id = utils::longid();
+    store.deploy(&workflow).unwrap();
+
+    // keep the last time of the unchanged trigger
+    let mut t1 = store
+        .triggers()
+        .find(&format!("{}:t1", workflow.id))
+        .unwrap();
+    t1.last_time = 100;
+    store.triggers().update(&t1).unwrap();
+
+    workflow.triggers.remove(1);
+    store.deploy(&workflow).unwrap();
+    let triggers = store.model_triggers(&workflow.id).unwrap();
+    assert_eq!(triggers.len(), 1);
+    assert_eq!(triggers[0].last_time, 100);
+
+    // reset the times of the changed trigger
+    workflow.triggers[0].cron = "0 11 * * *".to_string();
+    store.deploy(&workflow).unwrap();
+    let triggers = store.model_triggers(&workflow.id).unwrap();
+    assert!(triggers[0].last_time > 100);
+}
+
+#[tokio::test]
+async fn store_model_rollback_triggers() {
+    let store = store().await;
+    let mut workflow = create_workflow().with_trigger(|t| t.with_cron("0 9 * * *"));
+    workflow.id = utils::longid();
+    store.deploy(&workflow).unwrap();
+
+    workflow.triggers.clear();
+    store.deploy(&workflow).unwrap();
+    assert_eq!(store.model_triggers(&workflow.id).unwrap().len(), 0);
+
+    store.rollback(&workflow.id, 1).unwrap();
+    assert_eq!(store.model_triggers(&workflow.id).unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn store_model_remove_triggers() {
+    let store = store().await;
+    let mut workflow = create_workflow().with_trigger(|t| t.with_cron("0 9 * * *"));
+    workflow.id = utils::longid();
+    store.deploy(&workflow).unwrap();
+
+    store.remove_model(&workflow.id).unwrap();
+    assert_eq!(store.model_triggers(&workflow.id).unwrap().len(), 0);
+}
+
 #[tokio::test]
 async fn store_models() {
     let store = store().await;
@@ -233,6 +373,42 @@ async fn store_proc_remove() {
     assert_eq!(proc.is_ok(), false);
 }
 
+#[tokio::test]
+async fn store_proc_archive() {
+    let store = store().await;
+
+    let pid = utils::longid();
+    let tid = utils::shortid();
+    let workflow = create_workflow();
+    let proc = create_proc(&pid, TaskState::Completed, &workflow);
+    store.procs().create(&proc).expect("create proc");
+
+    let task = Task {
+        id: format!("{pid}:{tid}"),
+        name: "test".to_string(),
+        prev: None,
+        kind: NodeKind::Step.to_string(),
+        pid: pid.clone(),
+        tid: tid.clone(),
+        node_data: utils::shortid(),
+        state: TaskState::Completed.to_string(),
+        start_time: 0,
+        end_time: 0,
+        hooks: "{}".to_string(),
+        timestamp: 0,
+        data: "{}".to_string(),
+        err: None,
+        wait_key: "".to_string(),
+    };
+    store.tasks().create(&task).expect("create task");
+
+    store.archive_proc(&pid).unwrap();
+    assert!(!store.procs().exists(&pid).unwrap());
+    assert!(!store.tasks().exists(&task.id).unwrap());
+    assert!(store.proc_histories().exists(&pid).unwrap());
+    assert!(store.task_histories().exists(&task.id).unwrap());
+}
+
 #[tokio::test]
 async fn store_task_create() {
     let store = store().await;
@@ -255,6 +431,7 @@ async fn store_task_create() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        wait_key: "".to_string(),
     };
 
     store.tasks().create(&task).expect("create task");
@@ -286,6 +463,7 @@ async fn store_task_update() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        wait_key: "".to_string(),
     };
 
     store.tasks().create(&task).expect("create task");
@@ -321,6 +499,7 @@ async fn store_task_remove() {
         timestamp: 0,
         data: "{}".to_string(),
         err: None,
+        wait_key: "".to_string(),
     };
 
     store.tasks().create(&task).expect("create task");
@@ -496,6 +675,7 @@ async fn store_package_create() {
         create_time: 0,
         update_time: 0,
         timestamp: 0,
+        limits: None,
     };
 
     store.packages().create(&package).unwrap();
@@ -516,6 +696,7 @@ async fn store_package_query() {
         create_time: 0,
         update_time: 0,
         timestamp: 0,
+        limits: None,
     };
     store.packages().create(&package).unwrap();
     let q = Query::new().push(Cond::and().push(Expr::eq("id", package.id)));
@@ -536,6 +717,7 @@ async fn store_package_update() {
         create_time: 0,
         update_time: 0,
         timestamp: 0,
+        limits: None,
     };
     store.packages().create(&package).unwrap();
     let mut p = store.packages().find(&package.id).unwrap();
@@ -563,6 +745,7 @@ async fn store_package_remove() {
         create_time: 0,
         update_time: 0,
         timestamp: 0,
+        limits: None,
     };
     store.packages().create(&package).unwrap();
     store.packages().delete(&package.id).unwrap();
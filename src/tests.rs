@@ -1,4 +1,4 @@
-use crate::{utils, Act, Builder, Engine, Vars, Workflow};
+use crate::{utils, Act, Builder, Engine, HistoryMode, Severity, Vars, Workflow};
 use serde_json::json;
 
 #[tokio::test]
@@ -188,6 +188,51 @@ async fn engine_build_max_message_retry_times() {
     assert_eq!(engine.config().max_message_retry_times, 100)
 }
 
+#[tokio::test]
+async fn engine_build_history_mode() {
+    let engine = Builder::new().history_mode(HistoryMode::Archive).build();
+    assert_eq!(engine.config().history_mode, HistoryMode::Archive)
+}
+
+#[tokio::test]
+async fn engine_build_worker_count() {
+    let engine = Builder::new().worker_count(4).build();
+    assert_eq!(engine.config().worker_count, 4);
+    assert_eq!(engine.workers(), 4);
+}
+
+#[tokio::test]
+async fn engine_build_deploy_severity() {
+    let engine = Builder::new().deploy_severity(Severity::Warning).build();
+    assert_eq!(engine.config().deploy_severity, Some(Severity::Warning));
+}
+
+#[tokio::test]
+async fn engine_build_script_limits_default() {
+    let engine = Builder::new().build();
+    assert_eq!(engine.config().script_timeout, None);
+    assert_eq!(engine.config().script_memory, None);
+}
+
+#[tokio::test]
+async fn engine_build_script_limits() {
+    let engine = Builder::new()
+        .script_timeout(100)
+        .script_memory(1024 * 1024)
+        .build();
+    assert_eq!(engine.config().script_timeout, Some(100));
+    assert_eq!(engine.config().script_memory, Some(1024 * 1024));
+
+    let ret = engine.runtime().env().eval::<()>("while(true) {}");
+    assert!(ret.is_err());
+}
+
+#[tokio::test]
+async fn engine_queue_depth() {
+    let engine = Engine::new();
+    assert_eq!(engine.queue_depth(), 0);
+}
+
 #[tokio::test]
 async fn engine_drop() {
     let engine = Engine::new();
@@ -9,6 +9,8 @@ pub const EVT_PUSH: &str = "push";
 pub const EVT_REMOVE: &str = "remove";
 
 pub const INITIATOR: &str = "initiator";
+pub const TRIGGER_TIME: &str = "trigger_time";
+pub const MODEL_VER: &str = "ver";
 
 pub const ACT_USE_PARENT_PROC_ID: &str = "use_parent_pid";
 pub const ACT_USE_PARENT_TASK_ID: &str = "use_parent_tid";
@@ -24,13 +26,35 @@ pub const ACT_ERR_CODE: &str = "ecode";
 
 pub const ACT_INDEX: &str = "act_index";
 pub const ACT_VALUE: &str = "act_value";
+pub const LOOP_INDEX: &str = "loop_index";
+pub const WAIT_KEY: &str = "wait_key";
+pub const DELAY_UNTIL: &str = "delay_until";
+pub const RETRY_ATTEMPT: &str = "retry_attempt";
+pub const RETRY_AT: &str = "retry_at";
+pub const COMPENSATE_STATE: &str = "compensate_state";
+pub const COMPENSATE_TASKS: &str = "compensate_tasks";
+pub const COMPENSATE_END: &str = "compensate_end";
+pub const COMPENSATE_ERR: &str = "compensate_err";
+pub const EACH_ITEMS: &str = "each_items";
+
+pub const ERR_SCRIPT_TIMEOUT: &str = "script_timeout";
+pub const ERR_SCRIPT_MEMORY: &str = "script_memory";
+pub const ERR_HANDLER: &str = "handler_error";
+pub const ERR_HANDLER_TIMEOUT: &str = "handler_timeout";
+pub const ERR_OUTPUTS: &str = "invalid_outputs";
+pub const ERR_LOOP_MAX: &str = "loop_max";
 
 pub const TASK_EMIT_DISABLED: &str = "emit_disabled";
 pub const IS_CATCH_PROCESSED: &str = "is_catch_processed";
 pub const IS_TIMEOUT_PROCESSED_PREFIX: &str = "timeout_";
 
 pub const ACT_TYPE_BLOCK: &str = "block";
+pub const ACT_TYPE_LOOP: &str = "loop";
+pub const ACT_TYPE_EACH: &str = "each";
+pub const ACT_TYPE_WAIT: &str = "wait";
+pub const ACT_TYPE_DELAY: &str = "delay";
 pub const ACT_TYPE_MSG: &str = "msg";
+pub const ACT_TYPE_REQ: &str = "req";
 pub const ACT_OUTPUTS: &str = "outputs";
 
 pub const ACT_TO: &str = "to";
@@ -38,3 +62,6 @@ pub const ACT_TO: &str = "to";
 pub const ACT_DEFAULT_OUTPUTS: &str = "default_outputs";
 
 pub const TASK_ROOT_TID: &str = "$";
+
+#[cfg(any(feature = "store", feature = "sqlite"))]
+pub const MEMORY_DB: &str = ":memory:";
@@ -1,4 +1,4 @@
-use crate::{sch::Task, Context, Vars};
+use crate::{sch::Task, Context, Result, Vars};
 use regex::Regex;
 use serde_json::Value as JsonValue;
 use std::sync::Arc;
@@ -11,9 +11,7 @@ pub fn fill_inputs<'a>(inputs: &'a Vars, ctx: &Context) -> Vars {
     for (k, v) in inputs {
         if let JsonValue::String(string) = v {
             if let Some(expr) = get_expr(string) {
-                let result = Context::scope(ctx.clone(), move || {
-                    ctx.runtime.env().eval::<JsonValue>(&expr)
-                });
+                let result = ctx.eval::<JsonValue>(&expr);
                 let new_value = match result {
                     Ok(v) => v,
                     Err(err) => {
@@ -42,9 +40,7 @@ pub fn fill_outputs(outputs: &Vars, ctx: &Context) -> Vars {
     for (k, v) in outputs {
         if let JsonValue::String(string) = v {
             if let Some(expr) = get_expr(string) {
-                let result = Context::scope(ctx.clone(), move || {
-                    ctx.runtime.env().eval::<JsonValue>(&expr)
-                });
+                let result = ctx.eval::<JsonValue>(&expr);
                 let new_value = match result {
                     Ok(v) => v,
                     Err(_err) => JsonValue::Null,
@@ -77,8 +73,7 @@ pub fn fill_proc_vars<'a>(task: &Arc<Task>, values: &'a Vars, ctx: &Context) ->
     for (k, v) in values {
         if let JsonValue::String(string) = v {
             if let Some(expr) = get_expr(string) {
-                let result =
-                    Context::scope(ctx.clone(), || ctx.runtime.env().eval::<JsonValue>(&expr));
+                let result = ctx.eval::<JsonValue>(&expr);
                 let new_value = match result {
                     Ok(v) => v,
                     Err(_err) => JsonValue::Null,
@@ -100,6 +95,36 @@ pub fn fill_proc_vars<'a>(task: &Arc<Task>, values: &'a Vars, ctx: &Context) ->
     ret
 }
 
+/// fill the template by replacing every `${ }` expression with its value
+/// such as `order:${ $("order_id") }`
+pub fn fill_template(text: &str, ctx: &Context) -> Result<String> {
+    let re = Regex::new(r"\$\{(.+?)\}").unwrap();
+    let mut ret = String::new();
+    let mut last = 0;
+    for caps in re.captures_iter(text) {
+        let m = caps.get(0).unwrap();
+        let expr = caps.get(1).map_or("", |m| m.as_str()).trim().to_string();
+        let value = ctx.eval::<JsonValue>(&expr)?;
+        ret.push_str(&text[last..m.start()]);
+        match value {
+            JsonValue::String(s) => ret.push_str(&s),
+            v => ret.push_str(&v.to_string()),
+        }
+        last = m.end();
+    }
+    ret.push_str(&text[last..]);
+
+    Ok(ret)
+}
+
+/// get all of the `${ }` expressions in the template
+pub fn get_template_exprs(text: &str) -> Vec<String> {
+    let re = Regex::new(r"\$\{(.+?)\}").unwrap();
+    re.captures_iter(text)
+        .filter_map(|caps| caps.get(1).map(|m| m.as_str().trim().to_string()))
+        .collect()
+}
+
 pub fn get_expr(text: &str) -> Option<String> {
     let re = Regex::new(r"^\$\{(.+)\}$").unwrap();
     let caps = re.captures(&text);
@@ -5,3 +5,19 @@ pub mod time;
 
 pub use convert::*;
 pub use id::*;
+
+/// a new temp dir for the test databases
+#[cfg(all(test, any(feature = "store", feature = "sqlite")))]
+pub fn test_data_dir() -> String {
+    std::env::temp_dir()
+        .join(format!("acts_{}", longid()))
+        .to_string_lossy()
+        .to_string()
+}
+
+/// the local store of the test engines is opened in memory
+/// the file mode is tested by the stores
+#[cfg(all(test, any(feature = "store", feature = "sqlite")))]
+pub fn test_store_path() -> (String, String) {
+    (test_data_dir(), consts::MEMORY_DB.to_string())
+}
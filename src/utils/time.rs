@@ -9,3 +9,14 @@ pub fn timestamp() -> i64 {
     let time: chrono::DateTime<chrono::Utc> = chrono::Utc::now();
     time.timestamp_micros()
 }
+
+/// convert a millis timestamp or an RFC 3339 date to the millis timestamp
+pub fn to_millis(value: &serde_json::Value) -> Option<i64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_i64().or_else(|| n.as_f64().map(|v| v as i64)),
+        serde_json::Value::String(s) => chrono::DateTime::parse_from_rfc3339(s)
+            .map(|t| t.timestamp_millis())
+            .ok(),
+        _ => None,
+    }
+}